@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS "ScrapeRunError" CASCADE;
+DROP TABLE IF EXISTS "ScrapeRun" CASCADE;
@@ -0,0 +1,21 @@
+CREATE TABLE IF NOT EXISTS "ScrapeRun"
+(
+    id               UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    started_at       TIMESTAMPTZ NOT NULL,
+    finished_at      TIMESTAMPTZ NOT NULL,
+    restaurants_seen INT         NOT NULL,
+    menus_inserted   INT         NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS "ScrapeRunError"
+(
+    id            UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    scrape_run_id UUID NOT NULL,
+    source        TEXT NOT NULL,
+    -- Link or name of the restaurant, NULL when the whole source failed
+    restaurant    TEXT,
+    message       TEXT NOT NULL,
+    FOREIGN KEY (scrape_run_id) REFERENCES "ScrapeRun" (id)
+);
+
+CREATE INDEX scrape_run_started_at ON "ScrapeRun" (started_at);
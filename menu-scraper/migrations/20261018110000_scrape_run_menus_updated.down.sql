@@ -0,0 +1,2 @@
+ALTER TABLE "ScrapeRun"
+    DROP COLUMN IF EXISTS menus_updated;
@@ -0,0 +1,2 @@
+ALTER TABLE "ScrapeRun"
+    ADD COLUMN IF NOT EXISTS menus_updated INT NOT NULL DEFAULT 0;
@@ -0,0 +1,2 @@
+ALTER TABLE "MenuItem"
+    DROP COLUMN IF EXISTS allergens;
@@ -0,0 +1,2 @@
+ALTER TABLE "MenuItem"
+    ADD COLUMN IF NOT EXISTS allergens INT[] NOT NULL DEFAULT '{}';
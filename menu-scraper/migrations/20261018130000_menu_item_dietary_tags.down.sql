@@ -0,0 +1,4 @@
+ALTER TABLE "MenuItem"
+    DROP COLUMN IF EXISTS is_vegetarian,
+    DROP COLUMN IF EXISTS is_vegan,
+    DROP COLUMN IF EXISTS is_gluten_free;
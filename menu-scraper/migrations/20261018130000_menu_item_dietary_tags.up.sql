@@ -0,0 +1,4 @@
+ALTER TABLE "MenuItem"
+    ADD COLUMN IF NOT EXISTS is_vegetarian  BOOL NOT NULL DEFAULT FALSE,
+    ADD COLUMN IF NOT EXISTS is_vegan       BOOL NOT NULL DEFAULT FALSE,
+    ADD COLUMN IF NOT EXISTS is_gluten_free BOOL NOT NULL DEFAULT FALSE;
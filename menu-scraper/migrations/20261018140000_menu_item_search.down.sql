@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS "MenuItem_name_search_idx";
+DROP TEXT SEARCH CONFIGURATION IF EXISTS czech_unaccent;
+DROP EXTENSION IF EXISTS unaccent;
@@ -0,0 +1,10 @@
+CREATE EXTENSION IF NOT EXISTS unaccent;
+
+-- Czech has no stemmer in Postgres, words are only unaccented and lowercased, declined forms are
+-- matched by prefix queries
+CREATE TEXT SEARCH CONFIGURATION czech_unaccent (COPY = simple);
+ALTER TEXT SEARCH CONFIGURATION czech_unaccent
+    ALTER MAPPING FOR hword, hword_part, word WITH unaccent, simple;
+
+CREATE INDEX IF NOT EXISTS "MenuItem_name_search_idx"
+    ON "MenuItem" USING GIN (to_tsvector('czech_unaccent', name));
@@ -0,0 +1,15 @@
+CREATE TABLE IF NOT EXISTS "ApiToken"
+(
+    id           UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id      UUID        NOT NULL,
+    name         TEXT        NOT NULL,
+    -- Argon2 hash of the secret part of the token, the token itself is never stored
+    token_hash   TEXT        NOT NULL,
+    created_at   TIMESTAMPTZ NOT NULL DEFAULT now(),
+    last_used_at TIMESTAMPTZ,
+    -- Revoked tokens are kept for the history
+    deleted_at   TIMESTAMPTZ,
+    FOREIGN KEY (user_id) REFERENCES "User" (id)
+);
+
+CREATE INDEX IF NOT EXISTS api_token_user_id ON "ApiToken" (user_id);
@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS "CalendarFeed"
+(
+    user_id    UUID PRIMARY KEY,
+    -- Secret part of the feed URL, kept readable so the URL can be shown again
+    token      TEXT        NOT NULL UNIQUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    FOREIGN KEY (user_id) REFERENCES "User" (id)
+);
@@ -0,0 +1,7 @@
+DROP TABLE IF EXISTS "LunchRsvp";
+
+DROP TYPE IF EXISTS rsvp_status;
+
+ALTER TABLE "Lunch"
+    DROP COLUMN IF EXISTS start_time,
+    DROP COLUMN IF EXISTS meeting_point;
@@ -0,0 +1,18 @@
+-- Lunches created before times were known start at noon
+ALTER TABLE "Lunch"
+    ADD COLUMN IF NOT EXISTS start_time    TIME NOT NULL DEFAULT '12:00',
+    ADD COLUMN IF NOT EXISTS meeting_point TEXT;
+
+CREATE TYPE rsvp_status AS ENUM ('going', 'not_going', 'maybe');
+
+-- Answer of a member whether they are coming to the lunch, independent of their vote
+CREATE TABLE IF NOT EXISTS "LunchRsvp"
+(
+    lunch_id   UUID        NOT NULL,
+    user_id    UUID        NOT NULL,
+    status     rsvp_status NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (lunch_id, user_id),
+    FOREIGN KEY (lunch_id) REFERENCES "Lunch" (id),
+    FOREIGN KEY (user_id) REFERENCES "User" (id)
+);
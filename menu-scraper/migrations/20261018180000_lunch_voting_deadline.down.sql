@@ -0,0 +1,11 @@
+DROP INDEX IF EXISTS "Lunch_voting_deadline_idx";
+
+ALTER TABLE "Lunch"
+    DROP COLUMN IF EXISTS finalized_at,
+    DROP COLUMN IF EXISTS winner_menu_id,
+    DROP COLUMN IF EXISTS latitude,
+    DROP COLUMN IF EXISTS longitude,
+    DROP COLUMN IF EXISTS tie_break,
+    DROP COLUMN IF EXISTS voting_deadline;
+
+DROP TYPE IF EXISTS tie_break;
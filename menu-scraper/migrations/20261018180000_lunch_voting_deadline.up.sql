@@ -0,0 +1,15 @@
+CREATE TYPE tie_break AS ENUM ('random', 'cheapest', 'closest');
+
+-- Votes are locked after the deadline and the winning menu is stored, lunches without a deadline
+-- are never finalized
+ALTER TABLE "Lunch"
+    ADD COLUMN IF NOT EXISTS voting_deadline TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS tie_break       tie_break NOT NULL DEFAULT 'random',
+    -- Location the closest restaurant is measured from
+    ADD COLUMN IF NOT EXISTS longitude       FLOAT8,
+    ADD COLUMN IF NOT EXISTS latitude        FLOAT8,
+    ADD COLUMN IF NOT EXISTS winner_menu_id  UUID REFERENCES "Menu" (id),
+    ADD COLUMN IF NOT EXISTS finalized_at    TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS "Lunch_voting_deadline_idx" ON "Lunch" (voting_deadline)
+    WHERE finalized_at IS NULL AND deleted_at IS NULL;
@@ -0,0 +1,16 @@
+-- One vote of each user in a lunch is kept, the first choice of the votes not deleted
+DELETE FROM "Vote" V
+USING "Vote" O
+WHERE O.user_id = V.user_id AND O.lunch_id = V.lunch_id AND O.id <> V.id
+  AND (V.deleted_at IS NOT NULL, COALESCE(V.rank, 0), V.id)
+    > (O.deleted_at IS NOT NULL, COALESCE(O.rank, 0), O.id);
+
+ALTER TABLE "Vote"
+    DROP CONSTRAINT IF EXISTS user_menu_vote,
+    DROP COLUMN IF EXISTS rank,
+    ADD CONSTRAINT user_vote UNIQUE (user_id, lunch_id);
+
+ALTER TABLE "Lunch"
+    DROP COLUMN IF EXISTS voting_mode;
+
+DROP TYPE IF EXISTS voting_mode;
@@ -0,0 +1,11 @@
+CREATE TYPE voting_mode AS ENUM ('plurality', 'approval', 'ranked');
+
+ALTER TABLE "Lunch"
+    ADD COLUMN IF NOT EXISTS voting_mode voting_mode NOT NULL DEFAULT 'plurality';
+
+-- Approval and ranked-choice voting allow more menus per user, one vote per menu is kept
+ALTER TABLE "Vote"
+    DROP CONSTRAINT IF EXISTS user_vote,
+    -- Preference of the menu in ranked-choice voting, 1 is the first choice
+    ADD COLUMN IF NOT EXISTS rank INT,
+    ADD CONSTRAINT user_menu_vote UNIQUE (user_id, lunch_id, menu_id);
@@ -0,0 +1,18 @@
+-- Veto of a member against a menu of a lunch, or against the whole restaurant of the menu when
+-- restaurant_id is set. Vetoed menus are left out of the tally.
+CREATE TABLE IF NOT EXISTS "Veto"
+(
+    id            UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    menu_id       UUID        NOT NULL,
+    restaurant_id UUID,
+    user_id       UUID        NOT NULL,
+    lunch_id      UUID        NOT NULL,
+    created_at    TIMESTAMPTZ NOT NULL DEFAULT now(),
+    deleted_at    TIMESTAMPTZ,
+    FOREIGN KEY (menu_id) REFERENCES "Menu" (id),
+    FOREIGN KEY (restaurant_id) REFERENCES "Restaurant" (id),
+    FOREIGN KEY (user_id) REFERENCES "User" (id),
+    FOREIGN KEY (lunch_id) REFERENCES "Lunch" (id)
+);
+
+CREATE INDEX IF NOT EXISTS veto_lunch_id ON "Veto" (lunch_id) WHERE deleted_at IS NULL;
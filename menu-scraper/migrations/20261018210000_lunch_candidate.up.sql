@@ -0,0 +1,13 @@
+-- Menu nominated to a lunch, shown in the lunch even before anybody votes for it. Menus nominated
+-- when the lunch is created have no user.
+CREATE TABLE IF NOT EXISTS "LunchCandidate"
+(
+    lunch_id   UUID        NOT NULL,
+    menu_id    UUID        NOT NULL,
+    user_id    UUID,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (lunch_id, menu_id),
+    FOREIGN KEY (lunch_id) REFERENCES "Lunch" (id),
+    FOREIGN KEY (menu_id) REFERENCES "Menu" (id),
+    FOREIGN KEY (user_id) REFERENCES "User" (id)
+);
@@ -0,0 +1,10 @@
+DROP INDEX IF EXISTS group_users_owner;
+
+-- Owners are members implicitly as authors of their groups
+DELETE FROM "GroupUsers"
+WHERE role = 'owner';
+
+ALTER TABLE "GroupUsers"
+    DROP COLUMN IF EXISTS role;
+
+DROP TYPE IF EXISTS group_role;
@@ -0,0 +1,13 @@
+CREATE TYPE group_role AS ENUM ('owner', 'admin', 'member');
+
+ALTER TABLE "GroupUsers"
+    ADD COLUMN IF NOT EXISTS role group_role NOT NULL DEFAULT 'member';
+
+-- Authors own their groups, "Group".author_id stays the owner and changes with the ownership
+INSERT INTO "GroupUsers" (user_id, group_id, role, deleted_at)
+SELECT author_id, id, 'owner', deleted_at
+FROM "Group"
+ON CONFLICT (user_id, group_id) DO UPDATE SET role = 'owner', deleted_at = EXCLUDED.deleted_at;
+
+-- Every group has exactly one owner
+CREATE UNIQUE INDEX IF NOT EXISTS group_users_owner ON "GroupUsers" (group_id) WHERE role = 'owner';
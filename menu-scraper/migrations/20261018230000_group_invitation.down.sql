@@ -0,0 +1,5 @@
+DROP TABLE IF EXISTS "GroupJoinLink";
+
+DROP TABLE IF EXISTS "GroupInvitation";
+
+DROP TYPE IF EXISTS invitation_status;
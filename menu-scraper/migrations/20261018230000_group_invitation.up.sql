@@ -0,0 +1,35 @@
+CREATE TYPE invitation_status AS ENUM ('pending', 'accepted', 'declined');
+
+-- Invitation of a user to a group, the user becomes a member only after accepting it
+CREATE TABLE IF NOT EXISTS "GroupInvitation"
+(
+    id          UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    group_id    UUID              NOT NULL,
+    user_id     UUID              NOT NULL,
+    invited_by  UUID              NOT NULL,
+    status      invitation_status NOT NULL DEFAULT 'pending',
+    created_at  TIMESTAMPTZ       NOT NULL DEFAULT now(),
+    answered_at TIMESTAMPTZ,
+    FOREIGN KEY (group_id) REFERENCES "Group" (id),
+    FOREIGN KEY (user_id) REFERENCES "User" (id),
+    FOREIGN KEY (invited_by) REFERENCES "User" (id)
+);
+
+-- A user has at most one unanswered invitation to a group
+CREATE UNIQUE INDEX IF NOT EXISTS group_invitation_pending ON "GroupInvitation" (group_id, user_id)
+    WHERE status = 'pending';
+
+-- Shareable link joining the signed user to a group until it expires
+CREATE TABLE IF NOT EXISTS "GroupJoinLink"
+(
+    id         UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    group_id   UUID        NOT NULL,
+    -- Secret part of the link URL, kept readable so the link can be shown again
+    token      TEXT        NOT NULL UNIQUE,
+    created_by UUID        NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    deleted_at TIMESTAMPTZ,
+    FOREIGN KEY (group_id) REFERENCES "Group" (id),
+    FOREIGN KEY (created_by) REFERENCES "User" (id)
+);
@@ -0,0 +1,13 @@
+-- Failed login attempt, recent attempts throttle further logins to the account and from the address
+CREATE TABLE IF NOT EXISTS "LoginAttempt"
+(
+    id         UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -- Lowercased email the attempt was made for, the account does not need to exist
+    email      TEXT        NOT NULL,
+    ip_address TEXT        NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS login_attempt_email ON "LoginAttempt" (email, created_at);
+
+CREATE INDEX IF NOT EXISTS login_attempt_ip_address ON "LoginAttempt" (ip_address, created_at);
@@ -0,0 +1,2 @@
+ALTER TABLE "Restaurant"
+    DROP COLUMN IF EXISTS geocode_failed_at;
@@ -0,0 +1,3 @@
+-- Last failed geocoding of the address, geocoding is retried only after a cooldown
+ALTER TABLE "Restaurant"
+    ADD COLUMN IF NOT EXISTS geocode_failed_at TIMESTAMPTZ;
@@ -0,0 +1,2 @@
+ALTER TABLE "LoginAttempt"
+    DROP COLUMN IF EXISTS account_cleared;
@@ -0,0 +1,3 @@
+-- Failures of an account cleared by a successful login, they still count for the address
+ALTER TABLE "LoginAttempt"
+    ADD COLUMN IF NOT EXISTS account_cleared BOOLEAN NOT NULL DEFAULT false;
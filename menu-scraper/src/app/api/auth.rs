@@ -0,0 +1,81 @@
+use crate::app::api::errors::JsonApiError;
+use crate::app::utils::api_token::parse_token;
+use crate::app::utils::password::verify_password;
+use crate::db::common::error::DbErrorType::BusinessLogic;
+use crate::db::common::DbReadOne;
+use crate::db::models::ApiTokenGetById;
+use crate::db::repositories::{ApiTokenRepository, ApiTokenRepositoryMarkUsed};
+use actix_identity::IdentityExt;
+use actix_web::dev::Payload;
+use actix_web::http::header;
+use actix_web::web::Data;
+use actix_web::{FromRequest, HttpRequest};
+use futures::future::{ready, LocalBoxFuture};
+use futures::FutureExt;
+use uuid::Uuid;
+
+/// User calling the api, authenticated by `Authorization: Bearer <personal access token>` or by
+/// the session cookie. Responds with 401 when neither is valid.
+#[derive(Debug, Clone, Copy)]
+pub struct ApiUser {
+    pub id: Uuid,
+}
+
+impl ApiUser {
+    fn from_identity(request: &HttpRequest) -> Result<Self, JsonApiError> {
+        let identity = request.get_identity()?;
+        let id = Uuid::parse_str(identity.id()?.as_ref())?;
+
+        Ok(Self { id })
+    }
+
+    async fn from_token(
+        token: String,
+        repo: Data<ApiTokenRepository>,
+    ) -> Result<Self, JsonApiError> {
+        let (id, secret) = parse_token(&token).ok_or(JsonApiError::Unauthorized)?;
+
+        // Unknown, revoked and tokens of deleted users are all just invalid
+        let api_token = repo
+            .read_one(&ApiTokenGetById::new(&id))
+            .await
+            .map_err(|err| match err.error_type {
+                BusinessLogic(_) => JsonApiError::Unauthorized,
+                _ => JsonApiError::from(err),
+            })?;
+
+        verify_password(secret, &api_token.token_hash).map_err(|_| JsonApiError::Unauthorized)?;
+
+        repo.mark_used(&ApiTokenGetById::new(&api_token.id)).await?;
+
+        Ok(Self {
+            id: api_token.user_id,
+        })
+    }
+}
+
+impl FromRequest for ApiUser {
+    type Error = JsonApiError;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(request: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let Some(authorization) = request.headers().get(header::AUTHORIZATION) else {
+            return ready(Self::from_identity(request)).boxed_local();
+        };
+
+        // Header is present, so a malformed one is not ignored in favour of the session
+        let Some(token) = authorization
+            .to_str()
+            .ok()
+            .and_then(|value| value.strip_prefix("Bearer "))
+        else {
+            return ready(Err(JsonApiError::Unauthorized)).boxed_local();
+        };
+
+        let Some(repo) = request.app_data::<Data<ApiTokenRepository>>().cloned() else {
+            return ready(Err(JsonApiError::InternalServerError)).boxed_local();
+        };
+
+        Self::from_token(token.trim().to_string(), repo).boxed_local()
+    }
+}
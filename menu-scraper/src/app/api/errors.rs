@@ -0,0 +1,177 @@
+use crate::db::common::error::DbErrorType::{BusinessLogic, SqlxError};
+use crate::db::common::error::{BusinessLogicErrorKind, DbError};
+use actix_web::http::StatusCode;
+use actix_web::{error, HttpResponse};
+use serde::Serialize;
+use std::fmt;
+use utoipa::{ToResponse, ToSchema};
+
+/// Error of the JSON API, responds with `{"error": {"code": "...", "message": "..."}}`
+#[derive(Debug)]
+pub enum JsonApiError {
+    InternalServerError,
+    NotFound(String),
+    BadRequest(String),
+    Unauthorized,
+    Forbidden(String),
+    Conflict(String),
+}
+
+/// Body of the error response
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ErrorResponse<'a> {
+    pub error: ErrorDetail<'a>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ErrorDetail<'a> {
+    /// Machine readable code of the error
+    pub code: &'a str,
+    pub message: String,
+}
+
+/// Malformed request
+#[derive(ToResponse)]
+pub struct BadRequest(pub ErrorResponse<'static>);
+
+/// Missing or invalid token or session
+#[derive(ToResponse)]
+pub struct Unauthorized(pub ErrorResponse<'static>);
+
+/// User is not a member of the group
+#[derive(ToResponse)]
+pub struct Forbidden(pub ErrorResponse<'static>);
+
+/// Resource does not exist
+#[derive(ToResponse)]
+pub struct NotFound(pub ErrorResponse<'static>);
+
+/// Resource already exists or voting of the lunch has ended
+#[derive(ToResponse)]
+pub struct Conflict(pub ErrorResponse<'static>);
+
+/// Unexpected error
+#[derive(ToResponse)]
+pub struct InternalServerError(pub ErrorResponse<'static>);
+
+impl JsonApiError {
+    /// Machine readable code of the error
+    pub fn code(&self) -> &'static str {
+        match self {
+            JsonApiError::InternalServerError => "internal_server_error",
+            JsonApiError::NotFound(_) => "not_found",
+            JsonApiError::BadRequest(_) => "bad_request",
+            JsonApiError::Unauthorized => "unauthorized",
+            JsonApiError::Forbidden(_) => "forbidden",
+            JsonApiError::Conflict(_) => "conflict",
+        }
+    }
+}
+
+impl fmt::Display for JsonApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JsonApiError::InternalServerError => {
+                write!(f, "Interní chyba serveru, zkuste to prosím později.")
+            }
+            JsonApiError::Unauthorized => write!(f, "Přihlaste se prosím."),
+            JsonApiError::NotFound(message)
+            | JsonApiError::BadRequest(message)
+            | JsonApiError::Forbidden(message)
+            | JsonApiError::Conflict(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl From<DbError> for JsonApiError {
+    fn from(err: DbError) -> Self {
+        let kind = match &err.error_type {
+            BusinessLogic(kind) => kind,
+            // Details of database errors are not presented to the client
+            SqlxError => {
+                log::error!("database error in the api: {err}");
+                return JsonApiError::InternalServerError;
+            }
+        };
+
+        let message = err.to_string();
+
+        // Matched exhaustively, so every new kind has to get its status
+        match kind {
+            BusinessLogicErrorKind::UserDoesNotExist
+            | BusinessLogicErrorKind::UserDeleted
+            | BusinessLogicErrorKind::RestaurantDoesNotExist
+            | BusinessLogicErrorKind::RestaurantDeleted
+            | BusinessLogicErrorKind::MenuDoesNotExist
+            | BusinessLogicErrorKind::MenuDeleted
+            | BusinessLogicErrorKind::GroupDoesNotExist
+            | BusinessLogicErrorKind::GroupDeleted
+            | BusinessLogicErrorKind::GroupUsersDoesNotExist
+            | BusinessLogicErrorKind::GroupUsersDeleted
+            | BusinessLogicErrorKind::LunchDoesNotExist
+            | BusinessLogicErrorKind::LunchDeleted
+            | BusinessLogicErrorKind::VoteDoesNotExist
+            | BusinessLogicErrorKind::VoteDeleted
+            | BusinessLogicErrorKind::VetoDoesNotExist
+            | BusinessLogicErrorKind::VetoDeleted
+            | BusinessLogicErrorKind::ScrapeRunDoesNotExist
+            | BusinessLogicErrorKind::ApiTokenDoesNotExist
+            | BusinessLogicErrorKind::ApiTokenRevoked
+            | BusinessLogicErrorKind::CalendarFeedDoesNotExist
+            | BusinessLogicErrorKind::InvitationDoesNotExist
+            | BusinessLogicErrorKind::JoinLinkDoesNotExist => JsonApiError::NotFound(message),
+            BusinessLogicErrorKind::EmailAlreadyUsed
+            | BusinessLogicErrorKind::UsernameAlreadyUsed
+            | BusinessLogicErrorKind::UserAlreadyInGroup
+            | BusinessLogicErrorKind::GroupOwnerCannotLeave
+            | BusinessLogicErrorKind::GroupOwnerChange
+            | BusinessLogicErrorKind::InvitationAlreadyAnswered
+            | BusinessLogicErrorKind::InvitationAlreadyPending
+            | BusinessLogicErrorKind::JoinLinkExpired
+            | BusinessLogicErrorKind::LunchForDateAlreadyExists
+            | BusinessLogicErrorKind::VotingClosed
+            | BusinessLogicErrorKind::VetoLimitReached
+            | BusinessLogicErrorKind::MenuVetoed => JsonApiError::Conflict(message),
+            BusinessLogicErrorKind::UserNotMemberOfGroup
+            | BusinessLogicErrorKind::UserNotGroupAdmin => JsonApiError::Forbidden(message),
+            BusinessLogicErrorKind::MenuWithoutItems
+            | BusinessLogicErrorKind::LunchDateDoesntMatchMenuDate
+            | BusinessLogicErrorKind::UpdateParametersEmpty => JsonApiError::BadRequest(message),
+        }
+    }
+}
+
+/// Only ids of signed users are parsed in the api, invalid id means invalid identity
+impl From<uuid::Error> for JsonApiError {
+    fn from(_: uuid::Error) -> Self {
+        JsonApiError::Unauthorized
+    }
+}
+
+impl From<actix_identity::error::GetIdentityError> for JsonApiError {
+    fn from(_: actix_identity::error::GetIdentityError) -> Self {
+        JsonApiError::Unauthorized
+    }
+}
+
+impl error::ResponseError for JsonApiError {
+    fn status_code(&self) -> StatusCode {
+        match *self {
+            JsonApiError::InternalServerError => StatusCode::INTERNAL_SERVER_ERROR,
+            JsonApiError::NotFound(_) => StatusCode::NOT_FOUND,
+            JsonApiError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            JsonApiError::Unauthorized => StatusCode::UNAUTHORIZED,
+            JsonApiError::Forbidden(_) => StatusCode::FORBIDDEN,
+            JsonApiError::Conflict(_) => StatusCode::CONFLICT,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::build(self.status_code()).json(ErrorResponse {
+            error: ErrorDetail {
+                code: self.code(),
+                message: self.to_string(),
+            },
+        })
+    }
+}
@@ -0,0 +1,162 @@
+use crate::app::api::auth::ApiUser;
+use crate::app::api::errors::{
+    BadRequest, Conflict, Forbidden, InternalServerError, JsonApiError, NotFound, Unauthorized,
+};
+use crate::app::api::requests::{LunchCreateRequest, LunchListParams};
+use crate::app::api::responses::{GroupPreviewResponse, GroupResponse, LunchResponse};
+use crate::app::authorization::ApiGroupAccess;
+use crate::app::utils::validation::Validation;
+use crate::db::common::{DbCreate, DbReadMany, DbReadOne};
+use crate::db::models::{GroupGetById, GroupGetGroupsByUser, LunchCreate, LunchGetMany};
+use crate::db::repositories::{GroupRepository, GroupRepositoryListUsers, LunchRepository};
+use actix_web::web::Data;
+use actix_web::{get, post, web, HttpResponse};
+use utoipa_actix_web::service_config::ServiceConfig;
+
+pub fn group_api_config(config: &mut ServiceConfig) {
+    config
+        .service(list_groups)
+        .service(get_group)
+        .service(list_group_lunches)
+        .service(create_lunch);
+}
+
+/// Lists groups of the signed user
+#[utoipa::path(
+    tag = "groups",
+    security(("bearerAuth" = []), ("cookieAuth" = [])),
+    responses(
+        (status = 200, description = "Groups of the user", body = [GroupPreviewResponse]),
+        (status = 401, response = Unauthorized),
+        (status = 500, response = InternalServerError),
+    )
+)]
+#[get("/groups")]
+async fn list_groups(
+    user: ApiUser,
+    group_repo: Data<GroupRepository>,
+) -> Result<HttpResponse, JsonApiError> {
+    let groups = group_repo
+        .read_many(&GroupGetGroupsByUser::new(&user.id))
+        .await?;
+
+    Ok(HttpResponse::Ok().json(
+        groups
+            .into_iter()
+            .map(GroupPreviewResponse::from)
+            .collect::<Vec<_>>(),
+    ))
+}
+
+/// Gets detail of a group with its members, only for members of the group
+#[utoipa::path(
+    tag = "groups",
+    params(("id" = Uuid, Path, description = "Id of the group")),
+    security(("bearerAuth" = []), ("cookieAuth" = [])),
+    responses(
+        (status = 200, description = "Group with its members", body = GroupResponse),
+        (status = 401, response = Unauthorized),
+        (status = 403, response = Forbidden),
+        (status = 404, response = NotFound),
+        (status = 500, response = InternalServerError),
+    )
+)]
+#[get("/groups/{id}")]
+async fn get_group(
+    access: ApiGroupAccess,
+    group_repo: Data<GroupRepository>,
+) -> Result<HttpResponse, JsonApiError> {
+    let group_id = access.group_id;
+
+    let group = group_repo.read_one(&GroupGetById::new(&group_id)).await?;
+    let members = group_repo
+        .list_group_users(&GroupGetById::new(&group_id))
+        .await?;
+
+    Ok(HttpResponse::Ok().json(GroupResponse::new(group, members)))
+}
+
+/// Lists lunches of a group between dates, only for members of the group
+#[utoipa::path(
+    tag = "lunches",
+    params(("id" = Uuid, Path, description = "Id of the group"), LunchListParams),
+    security(("bearerAuth" = []), ("cookieAuth" = [])),
+    responses(
+        (status = 200, description = "Lunches of the group", body = [LunchResponse]),
+        (status = 400, response = BadRequest),
+        (status = 401, response = Unauthorized),
+        (status = 403, response = Forbidden),
+        (status = 404, response = NotFound),
+        (status = 500, response = InternalServerError),
+    )
+)]
+#[get("/groups/{id}/lunches")]
+async fn list_group_lunches(
+    access: ApiGroupAccess,
+    params: web::Query<LunchListParams>,
+    lunch_repo: Data<LunchRepository>,
+) -> Result<HttpResponse, JsonApiError> {
+    let lunches = lunch_repo
+        .read_many(&LunchGetMany {
+            group_id: Some(access.group_id),
+            user_id: None,
+            from: params.from,
+            to: params.to,
+        })
+        .await?;
+
+    Ok(HttpResponse::Ok().json(
+        lunches
+            .into_iter()
+            .map(LunchResponse::from)
+            .collect::<Vec<_>>(),
+    ))
+}
+
+/// Creates a lunch of a group, only for members of the group
+#[utoipa::path(
+    tag = "lunches",
+    params(("id" = Uuid, Path, description = "Id of the group")),
+    request_body = LunchCreateRequest,
+    security(("bearerAuth" = []), ("cookieAuth" = [])),
+    responses(
+        (status = 201, description = "Created lunch", body = LunchResponse),
+        (status = 400, response = BadRequest),
+        (status = 401, response = Unauthorized),
+        (status = 403, response = Forbidden),
+        (status = 404, response = NotFound),
+        (status = 409, response = Conflict),
+        (status = 500, response = InternalServerError),
+    )
+)]
+#[post("/groups/{id}/lunches")]
+async fn create_lunch(
+    access: ApiGroupAccess,
+    body: web::Json<LunchCreateRequest>,
+    lunch_repo: Data<LunchRepository>,
+) -> Result<HttpResponse, JsonApiError> {
+    let lunch_create = LunchCreate {
+        date: body.date,
+        group_id: access.group_id,
+        start_time: body.start_time,
+        meeting_point: body
+            .meeting_point
+            .as_deref()
+            .map(str::trim)
+            .filter(|meeting_point| !meeting_point.is_empty())
+            .map(str::to_string),
+        voting_deadline: body.voting_deadline,
+        tie_break: body.tie_break.into(),
+        longitude: body.longitude,
+        latitude: body.latitude,
+        voting_mode: body.voting_mode.into(),
+        candidates: None,
+    };
+    lunch_create
+        .validate()
+        .map_err(|err| JsonApiError::BadRequest(err.to_string()))?;
+
+    let lunch = lunch_repo.create(&lunch_create).await?;
+
+    Ok(HttpResponse::Created().json(LunchResponse::from(lunch)))
+}
@@ -0,0 +1,114 @@
+use crate::app::api::auth::ApiUser;
+use crate::app::api::errors::{
+    BadRequest, Forbidden, InternalServerError, JsonApiError, NotFound, Unauthorized,
+};
+use crate::app::api::requests::LunchListParams;
+use crate::app::api::responses::{LunchDetailResponse, LunchResponse};
+use crate::app::authorization::{Admin, ApiLunchAccess};
+use crate::db::common::{DbDelete, DbReadMany};
+use crate::db::models::{LunchDelete, LunchGetById, LunchGetMany, VoteGetMany};
+use crate::db::repositories::{LunchRepository, LunchRepositoryFinalize, VoteRepository};
+use actix_web::web::Data;
+use actix_web::{delete, get, web, HttpResponse};
+use utoipa_actix_web::service_config::ServiceConfig;
+
+pub fn lunch_api_config(config: &mut ServiceConfig) {
+    config
+        .service(list_lunches)
+        .service(get_lunch)
+        .service(delete_lunch);
+}
+
+/// Lists lunches of all groups of the signed user between dates
+#[utoipa::path(
+    tag = "lunches",
+    params(LunchListParams),
+    security(("bearerAuth" = []), ("cookieAuth" = [])),
+    responses(
+        (status = 200, description = "Lunches with names of their groups", body = [LunchResponse]),
+        (status = 400, response = BadRequest),
+        (status = 401, response = Unauthorized),
+        (status = 500, response = InternalServerError),
+    )
+)]
+#[get("/lunches")]
+async fn list_lunches(
+    params: web::Query<LunchListParams>,
+    user: ApiUser,
+    lunch_repo: Data<LunchRepository>,
+) -> Result<HttpResponse, JsonApiError> {
+    let lunches = lunch_repo
+        .read_many(&LunchGetMany {
+            group_id: None,
+            user_id: Some(user.id),
+            from: params.from,
+            to: params.to,
+        })
+        .await?;
+
+    Ok(HttpResponse::Ok().json(
+        lunches
+            .into_iter()
+            .map(LunchResponse::from)
+            .collect::<Vec<_>>(),
+    ))
+}
+
+/// Gets a lunch with the voted menus, only for members of the group
+///
+/// The winner is stored first when the voting deadline has passed.
+#[utoipa::path(
+    tag = "lunches",
+    params(("id" = Uuid, Path, description = "Id of the lunch")),
+    security(("bearerAuth" = []), ("cookieAuth" = [])),
+    responses(
+        (status = 200, description = "Lunch with the voted menus, the most voted first", body = LunchDetailResponse),
+        (status = 401, response = Unauthorized),
+        (status = 403, response = Forbidden),
+        (status = 404, response = NotFound),
+        (status = 500, response = InternalServerError),
+    )
+)]
+#[get("/lunches/{id}")]
+async fn get_lunch(
+    access: ApiLunchAccess,
+    lunch_repo: Data<LunchRepository>,
+    vote_repo: Data<VoteRepository>,
+) -> Result<HttpResponse, JsonApiError> {
+    let lunch = lunch_repo
+        .finalize(&LunchGetById::new(&access.lunch.id))
+        .await?;
+
+    let menus = vote_repo
+        .read_many(&VoteGetMany { lunch_id: lunch.id })
+        .await?;
+
+    Ok(HttpResponse::Ok().json(LunchDetailResponse::new(lunch, menus)))
+}
+
+/// Deletes a lunch, only for admins of the group
+#[utoipa::path(
+    tag = "lunches",
+    params(("id" = Uuid, Path, description = "Id of the lunch")),
+    security(("bearerAuth" = []), ("cookieAuth" = [])),
+    responses(
+        (status = 204, description = "Lunch was deleted"),
+        (status = 401, response = Unauthorized),
+        (status = 403, response = Forbidden),
+        (status = 404, response = NotFound),
+        (status = 500, response = InternalServerError),
+    )
+)]
+#[delete("/lunches/{id}")]
+async fn delete_lunch(
+    access: ApiLunchAccess<Admin>,
+    lunch_repo: Data<LunchRepository>,
+) -> Result<HttpResponse, JsonApiError> {
+    lunch_repo
+        .delete(&LunchDelete {
+            id: access.lunch.id,
+        })
+        .await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
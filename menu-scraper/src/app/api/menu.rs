@@ -0,0 +1,118 @@
+use crate::app::api::errors::{BadRequest, InternalServerError, JsonApiError};
+use crate::app::api::requests::{MenuListParams, MenuOrderingMethod};
+use crate::app::api::responses::{MenuPageResponse, MenuResponse};
+use crate::app::forms::menu::parse_allergens;
+use crate::app::forms::ordering::Ordering;
+use crate::db::common::query_parameters::{DbOrder, DbSearchQuery};
+use crate::db::common::DbReadMany;
+use crate::db::models::{DbRestaurantOrderingMethod, MenuGetCount, MenuReadMany};
+use crate::db::repositories::{GetNumberOfMenus, MenuRepository};
+use actix_web::web::Data;
+use actix_web::{get, web, HttpResponse};
+use chrono::Local;
+use utoipa_actix_web::service_config::ServiceConfig;
+
+const DEFAULT_PAGE_SIZE: i64 = 20;
+const MAX_PAGE_SIZE: i64 = 100;
+
+pub fn menu_api_config(config: &mut ServiceConfig) {
+    config.service(list_menus);
+}
+
+/// Lists menus for a range of dates
+#[utoipa::path(
+    tag = "menus",
+    params(MenuListParams),
+    responses(
+        (status = 200, description = "One page of menus", body = MenuPageResponse),
+        (status = 400, response = BadRequest),
+        (status = 500, response = InternalServerError),
+    )
+)]
+#[get("/menus")]
+async fn list_menus(
+    params: web::Query<MenuListParams>,
+    repo: Data<MenuRepository>,
+) -> Result<HttpResponse, JsonApiError> {
+    let date_from = params.date_from.unwrap_or(Local::now().date_naive());
+    let date_to = params.date_to.unwrap_or(date_from);
+
+    if date_from > date_to {
+        return Err(JsonApiError::BadRequest("Neplatný rozsah dat.".to_string()));
+    }
+
+    let excluded_allergens = match &params.allergens {
+        Some(allergens) => parse_allergens(allergens).ok_or(JsonApiError::BadRequest(
+            "Neplatný výběr alergenů.".to_string(),
+        ))?,
+        None => vec![],
+    };
+
+    let page = params.page.unwrap_or(1);
+    let page_size = params.page_size.unwrap_or(DEFAULT_PAGE_SIZE);
+
+    if page < 1 || !(1..=MAX_PAGE_SIZE).contains(&page_size) {
+        return Err(JsonApiError::BadRequest(
+            "Neplatné stránkování.".to_string(),
+        ));
+    }
+
+    let search = params.search.as_deref().and_then(DbSearchQuery::new);
+
+    let total = repo
+        .get_number_of_menus(&MenuGetCount {
+            date_from,
+            date_to,
+            restaurant_id: params.restaurant_id,
+            excluded_allergens: excluded_allergens.clone(),
+            vegetarian_only: params.vegetarian,
+            search: search.clone(),
+        })
+        .await?;
+
+    let menus = repo
+        .read_many(&MenuReadMany {
+            date_from,
+            date_to,
+            order_by: ordering_method(&params)?,
+            restaurant_id: params.restaurant_id,
+            excluded_allergens,
+            vegetarian_only: params.vegetarian,
+            search,
+            limit: Some(page_size),
+            offset: Some((page - 1) * page_size),
+        })
+        .await?;
+
+    Ok(HttpResponse::Ok().json(MenuPageResponse {
+        total,
+        page,
+        page_size,
+        menus: menus.into_iter().map(MenuResponse::from).collect(),
+    }))
+}
+
+/// Converts ordering from the query, ordering by range needs the location of the user
+fn ordering_method(params: &MenuListParams) -> Result<DbRestaurantOrderingMethod, JsonApiError> {
+    let order = match params.ordering {
+        Some(Ordering::Desc) => DbOrder::Desc,
+        Some(Ordering::Asc) | None => DbOrder::Asc,
+    };
+
+    match params.method {
+        MenuOrderingMethod::Price => Ok(DbRestaurantOrderingMethod::Price(order)),
+        MenuOrderingMethod::Range => match (params.longitude, params.latitude) {
+            (Some(longitude), Some(latitude)) if longitude.is_finite() && latitude.is_finite() => {
+                Ok(DbRestaurantOrderingMethod::Range(
+                    order,
+                    (longitude, latitude),
+                ))
+            }
+            _ => Err(JsonApiError::BadRequest(
+                "Řazení podle vzdálenosti vyžaduje polohu.".to_string(),
+            )),
+        },
+        MenuOrderingMethod::Random => Ok(DbRestaurantOrderingMethod::Random),
+        MenuOrderingMethod::Date => Ok(DbRestaurantOrderingMethod::Date(order)),
+    }
+}
@@ -0,0 +1,56 @@
+use crate::app::api::errors::JsonApiError;
+use crate::app::api::group::group_api_config;
+use crate::app::api::lunch::lunch_api_config;
+use crate::app::api::menu::menu_api_config;
+use crate::app::api::openapi::openapi_api_config;
+use crate::app::api::restaurant::restaurant_api_config;
+use crate::app::api::vote::vote_api_config;
+use actix_web::dev::HttpServiceFactory;
+use actix_web::{web, HttpResponse};
+use utoipa_actix_web::service_config::ServiceConfig;
+use utoipa_actix_web::OpenApiFactory;
+
+pub mod auth;
+pub mod errors;
+pub mod group;
+pub mod lunch;
+pub mod menu;
+pub mod openapi;
+pub mod requests;
+pub mod responses;
+pub mod restaurant;
+pub mod vote;
+
+/// JSON api for other tools, versioned by the path prefix
+pub fn api_config(config: &mut web::ServiceConfig) {
+    ServiceConfig::new(config).service(api_scope());
+}
+
+/// Scope of the api, the OpenAPI document is collected from the routes registered in it
+fn api_scope() -> impl HttpServiceFactory + OpenApiFactory {
+    utoipa_actix_web::scope("/api/v1")
+        // Malformed requests are answered with JSON errors as well
+        .app_data(
+            web::JsonConfig::default()
+                .error_handler(|err, _| JsonApiError::BadRequest(err.to_string()).into()),
+        )
+        .app_data(
+            web::QueryConfig::default()
+                .error_handler(|err, _| JsonApiError::BadRequest(err.to_string()).into()),
+        )
+        .app_data(
+            web::PathConfig::default()
+                .error_handler(|err, _| JsonApiError::NotFound(err.to_string()).into()),
+        )
+        .configure(openapi_api_config)
+        .configure(menu_api_config)
+        .configure(restaurant_api_config)
+        .configure(group_api_config)
+        .configure(lunch_api_config)
+        .configure(vote_api_config)
+        .default_service(web::to(|| async {
+            Err::<HttpResponse, _>(JsonApiError::NotFound(
+                "Tento endpoint neexistuje.".to_string(),
+            ))
+        }))
+}
@@ -0,0 +1,102 @@
+use crate::app::api::api_scope;
+use crate::app::api::errors::{
+    BadRequest, Conflict, ErrorResponse, Forbidden, InternalServerError, NotFound, Unauthorized,
+};
+use crate::app::api::requests::MenuOrderingMethod;
+use crate::app::forms::ordering::Ordering;
+use actix_web::{get, App, HttpResponse};
+use std::sync::LazyLock;
+use utoipa::openapi::security::{ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::{Modify, OpenApi};
+use utoipa_actix_web::service_config::ServiceConfig;
+use utoipa_actix_web::AppExt;
+
+/// Parts of the OpenAPI document which are not collected from the routes of the api
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Menu Scraper API",
+        version = "1",
+        description = "Menus of Brno restaurants, groups, lunches and votes. Errors are returned as `{\"error\": {\"code\": \"...\", \"message\": \"...\"}}`."
+    ),
+    tags(
+        (name = "menus"),
+        (name = "restaurants"),
+        (name = "groups"),
+        (name = "lunches"),
+        (name = "votes"),
+        (name = "meta"),
+    ),
+    components(
+        // Only referenced by query parameters, which do not collect their schemas
+        schemas(ErrorResponse, MenuOrderingMethod, Ordering),
+        responses(
+            BadRequest,
+            Unauthorized,
+            Forbidden,
+            NotFound,
+            Conflict,
+            InternalServerError
+        )
+    ),
+    modifiers(&SecuritySchemes)
+)]
+struct ApiDoc;
+
+/// Both ways a user is authenticated in the api, see [`crate::app::api::auth::ApiUser`]
+struct SecuritySchemes;
+
+impl Modify for SecuritySchemes {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+
+        components.add_security_scheme(
+            "bearerAuth",
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .description(Some("Personal access token created on the user edit page"))
+                    .build(),
+            ),
+        );
+        components.add_security_scheme(
+            "cookieAuth",
+            SecurityScheme::ApiKey(ApiKey::Cookie(ApiKeyValue::with_description(
+                "id",
+                "Session cookie of the website, POST, PUT and DELETE requests authenticated by it \
+                 also need the `X-CSRF-Token` header with the CSRF token of the session",
+            ))),
+        );
+    }
+}
+
+/// Collected once, the routes do not change while the server runs
+static OPENAPI_DOCUMENT: LazyLock<utoipa::openapi::OpenApi> = LazyLock::new(openapi_document);
+
+/// OpenAPI document of the api, its paths and schemas are collected from the routes registered
+/// by [`api_scope`], so an undocumented route cannot be served
+pub fn openapi_document() -> utoipa::openapi::OpenApi {
+    let (_, mut document) = App::new()
+        .into_utoipa_app()
+        .openapi(ApiDoc::openapi())
+        .service(api_scope())
+        .split_for_parts();
+    // Filled from the manifest by default, the project has no license
+    document.info.license = None;
+
+    document
+}
+
+pub fn openapi_api_config(config: &mut ServiceConfig) {
+    config.service(get_openapi);
+}
+
+/// Serves the OpenAPI document for generating clients of the api
+#[utoipa::path(
+    tag = "meta",
+    responses((status = 200, description = "OpenAPI document of the api", content_type = "application/json"))
+)]
+#[get("/openapi.json")]
+async fn get_openapi() -> HttpResponse {
+    HttpResponse::Ok().json(&*OPENAPI_DOCUMENT)
+}
@@ -0,0 +1,144 @@
+use crate::app::forms::ordering::Ordering;
+use crate::db::models::{TieBreak, VotingMode};
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+/// Ordering methods of menus
+#[derive(Debug, Default, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum MenuOrderingMethod {
+    #[default]
+    Price,
+    /// Distance from `longitude` and `latitude`
+    Range,
+    Random,
+    Date,
+}
+
+/// Filters of the menu list, same as of the menu list page
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct MenuListParams {
+    /// Today when not set
+    pub date_from: Option<NaiveDate>,
+    /// Same as `date_from` when not set
+    pub date_to: Option<NaiveDate>,
+    /// Ordering by distance (`range`) needs `longitude` and `latitude`
+    #[serde(default)]
+    pub method: MenuOrderingMethod,
+    pub ordering: Option<Ordering>,
+    pub longitude: Option<f64>,
+    pub latitude: Option<f64>,
+    pub restaurant_id: Option<Uuid>,
+    /// Comma separated numbers of allergens, menus containing them are filtered out
+    pub allergens: Option<String>,
+    #[serde(default)]
+    pub vegetarian: bool,
+    /// Only menus with a dish matching this text
+    pub search: Option<String>,
+    /// Pages are numbered from 1
+    pub page: Option<i64>,
+    pub page_size: Option<i64>,
+}
+
+/// Range of dates of listed lunches
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct LunchListParams {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct LunchCreateRequest {
+    pub date: NaiveDate,
+    /// Time of the day, noon when not set
+    #[serde(default = "default_start_time")]
+    pub start_time: NaiveTime,
+    /// Where the members meet, or any other note
+    pub meeting_point: Option<String>,
+    /// Votes are locked after the deadline and the winner is chosen, voting never ends when not set
+    pub voting_deadline: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub tie_break: TieBreakMethod,
+    /// Location the closest restaurant is measured from
+    pub longitude: Option<f64>,
+    pub latitude: Option<f64>,
+    #[serde(default)]
+    pub voting_mode: VotingMethod,
+}
+
+/// How the winner is chosen from menus with the same number of votes
+#[derive(Debug, Default, Clone, Copy, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TieBreakMethod {
+    #[default]
+    Random,
+    Cheapest,
+    Closest,
+}
+
+impl From<TieBreakMethod> for TieBreak {
+    fn from(method: TieBreakMethod) -> Self {
+        match method {
+            TieBreakMethod::Random => TieBreak::Random,
+            TieBreakMethod::Cheapest => TieBreak::Cheapest,
+            TieBreakMethod::Closest => TieBreak::Closest,
+        }
+    }
+}
+
+impl From<TieBreak> for TieBreakMethod {
+    fn from(tie_break: TieBreak) -> Self {
+        match tie_break {
+            TieBreak::Random => TieBreakMethod::Random,
+            TieBreak::Cheapest => TieBreakMethod::Cheapest,
+            TieBreak::Closest => TieBreakMethod::Closest,
+        }
+    }
+}
+
+/// How members vote in a lunch
+#[derive(Debug, Default, Clone, Copy, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum VotingMethod {
+    /// One menu per member
+    #[default]
+    Plurality,
+    /// Any number of menus per member
+    Approval,
+    /// Members rank menus by posting votes in the order of their preference
+    Ranked,
+}
+
+impl From<VotingMethod> for VotingMode {
+    fn from(method: VotingMethod) -> Self {
+        match method {
+            VotingMethod::Plurality => VotingMode::Plurality,
+            VotingMethod::Approval => VotingMode::Approval,
+            VotingMethod::Ranked => VotingMode::Ranked,
+        }
+    }
+}
+
+impl From<VotingMode> for VotingMethod {
+    fn from(mode: VotingMode) -> Self {
+        match mode {
+            VotingMode::Plurality => VotingMethod::Plurality,
+            VotingMode::Approval => VotingMethod::Approval,
+            VotingMode::Ranked => VotingMethod::Ranked,
+        }
+    }
+}
+
+/// Lunches start at noon when the time is not set
+fn default_start_time() -> NaiveTime {
+    NaiveTime::from_hms_opt(12, 0, 0).unwrap_or_default()
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct VoteRequest {
+    pub menu_id: Uuid,
+}
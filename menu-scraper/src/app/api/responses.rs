@@ -0,0 +1,338 @@
+use crate::app::api::requests::{TieBreakMethod, VotingMethod};
+use crate::db::models::{
+    Group, GroupPreview, Lunch, LunchWithGroup, MenuItem, MenuWithRestaurant,
+    MenuWithRestaurantAndVotes, Restaurant, UserPreview, Vote,
+};
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// One page of menus
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MenuPageResponse {
+    /// Number of menus matching the filters on all pages
+    pub total: i64,
+    pub page: i64,
+    pub page_size: i64,
+    pub menus: Vec<MenuResponse>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MenuResponse {
+    pub id: Uuid,
+    pub date: NaiveDate,
+    pub restaurant: RestaurantPreviewResponse,
+    pub items: Vec<MenuItemResponse>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MenuItemResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub price: i32,
+    pub size: String,
+    pub is_soup: bool,
+    pub allergens: Vec<i32>,
+    pub is_vegetarian: bool,
+    pub is_vegan: bool,
+    pub is_gluten_free: bool,
+    /// Item matches the search query
+    pub is_highlighted: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RestaurantPreviewResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub street: String,
+    pub house_number: String,
+    pub zip_code: String,
+    pub city: String,
+    pub picture: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RestaurantResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub street: String,
+    pub house_number: String,
+    pub zip_code: String,
+    pub city: String,
+    pub picture: Option<String>,
+    pub phone_number: Option<String>,
+    pub website: Option<String>,
+    pub email: Option<String>,
+    /// Opening hours from monday to sunday
+    pub opening_hours: Vec<Option<String>>,
+    pub lunch_served: Option<String>,
+    pub longitude: Option<f64>,
+    pub latitude: Option<f64>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct GroupPreviewResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub picture: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct GroupResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub picture: Option<String>,
+    pub author_id: Uuid,
+    pub members: Vec<UserPreviewResponse>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UserPreviewResponse {
+    pub id: Uuid,
+    pub username: String,
+    pub profile_picture: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LunchResponse {
+    pub id: Uuid,
+    pub date: NaiveDate,
+    pub start_time: NaiveTime,
+    pub meeting_point: Option<String>,
+    pub group_id: Uuid,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_name: Option<String>,
+}
+
+/// Lunch with the voted menus, the menu with the highest score first
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LunchDetailResponse {
+    pub id: Uuid,
+    pub date: NaiveDate,
+    pub start_time: NaiveTime,
+    pub meeting_point: Option<String>,
+    pub group_id: Uuid,
+    pub voting_deadline: Option<DateTime<Utc>>,
+    pub tie_break: TieBreakMethod,
+    pub voting_mode: VotingMethod,
+    /// Menu chosen after the deadline, not set when nobody voted
+    pub winner_menu_id: Option<Uuid>,
+    pub finalized_at: Option<DateTime<Utc>>,
+    pub menus: Vec<VotedMenuResponse>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct VotedMenuResponse {
+    #[serde(flatten)]
+    pub menu: MenuResponse,
+    /// Ids of users who voted for the menu
+    pub voters: Vec<Uuid>,
+    /// Number of votes, or votes in the last round of the menu in ranked-choice voting
+    pub score: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct VoteResponse {
+    pub id: Uuid,
+    pub menu_id: Uuid,
+    pub user_id: Uuid,
+    pub lunch_id: Uuid,
+}
+
+impl MenuItemResponse {
+    fn new(item: MenuItem, is_highlighted: bool) -> Self {
+        Self {
+            id: item.id,
+            name: item.name,
+            price: item.price,
+            size: item.size,
+            is_soup: item.is_soup,
+            allergens: item.allergens,
+            is_vegetarian: item.is_vegetarian,
+            is_vegan: item.is_vegan,
+            is_gluten_free: item.is_gluten_free,
+            is_highlighted,
+        }
+    }
+}
+
+impl From<MenuWithRestaurant> for MenuResponse {
+    fn from(menu: MenuWithRestaurant) -> Self {
+        let matching_item_ids = menu.matching_item_ids;
+
+        Self {
+            id: menu.menu_id,
+            date: menu.date,
+            restaurant: RestaurantPreviewResponse {
+                id: menu.restaurant_id,
+                name: menu.name,
+                street: menu.street,
+                house_number: menu.house_number,
+                zip_code: menu.zip_code,
+                city: menu.city,
+                picture: menu.picture,
+            },
+            items: menu
+                .items
+                .into_iter()
+                .map(|item| {
+                    let is_highlighted = matching_item_ids.contains(&item.id);
+                    MenuItemResponse::new(item, is_highlighted)
+                })
+                .collect(),
+        }
+    }
+}
+
+impl VotedMenuResponse {
+    fn new(menu: MenuWithRestaurantAndVotes, score: i64) -> Self {
+        Self {
+            menu: MenuResponse {
+                id: menu.menu_id,
+                date: menu.date,
+                restaurant: RestaurantPreviewResponse {
+                    id: menu.restaurant_id,
+                    name: menu.name,
+                    street: menu.street,
+                    house_number: menu.house_number,
+                    zip_code: menu.zip_code,
+                    city: menu.city,
+                    picture: menu.picture,
+                },
+                items: menu
+                    .items
+                    .into_iter()
+                    .map(|item| MenuItemResponse::new(item, false))
+                    .collect(),
+            },
+            voters: menu.votes.into_iter().map(|vote| vote.user_id).collect(),
+            score,
+        }
+    }
+}
+
+impl From<Restaurant> for RestaurantResponse {
+    fn from(restaurant: Restaurant) -> Self {
+        Self {
+            id: restaurant.id,
+            name: restaurant.name,
+            street: restaurant.street,
+            house_number: restaurant.house_number,
+            zip_code: restaurant.zip_code,
+            city: restaurant.city,
+            picture: restaurant.picture,
+            phone_number: restaurant.phone_number,
+            website: restaurant.website,
+            email: restaurant.email,
+            opening_hours: vec![
+                restaurant.monday_open,
+                restaurant.tuesday_open,
+                restaurant.wednesday_open,
+                restaurant.thursday_open,
+                restaurant.friday_open,
+                restaurant.saturday_open,
+                restaurant.sunday_open,
+            ],
+            lunch_served: restaurant.lunch_served,
+            longitude: restaurant.longitude,
+            latitude: restaurant.latitude,
+        }
+    }
+}
+
+impl From<GroupPreview> for GroupPreviewResponse {
+    fn from(group: GroupPreview) -> Self {
+        Self {
+            id: group.id,
+            name: group.name,
+            picture: group.picture,
+        }
+    }
+}
+
+impl GroupResponse {
+    pub fn new(group: Group, members: Vec<UserPreview>) -> Self {
+        Self {
+            id: group.id,
+            name: group.name,
+            description: group.description,
+            picture: group.picture,
+            author_id: group.author_id,
+            members: members.into_iter().map(UserPreviewResponse::from).collect(),
+        }
+    }
+}
+
+impl From<UserPreview> for UserPreviewResponse {
+    fn from(user: UserPreview) -> Self {
+        Self {
+            id: user.id,
+            username: user.username,
+            profile_picture: user.profile_picture,
+        }
+    }
+}
+
+impl From<Lunch> for LunchResponse {
+    fn from(lunch: Lunch) -> Self {
+        Self {
+            id: lunch.id,
+            date: lunch.date,
+            start_time: lunch.start_time,
+            meeting_point: lunch.meeting_point,
+            group_id: lunch.group_id,
+            group_name: None,
+        }
+    }
+}
+
+impl From<LunchWithGroup> for LunchResponse {
+    fn from(lunch: LunchWithGroup) -> Self {
+        Self {
+            id: lunch.id,
+            date: lunch.date,
+            start_time: lunch.start_time,
+            meeting_point: lunch.meeting_point,
+            group_id: lunch.group_id,
+            group_name: Some(lunch.group_name),
+        }
+    }
+}
+
+impl LunchDetailResponse {
+    pub fn new(lunch: Lunch, menus: Vec<MenuWithRestaurantAndVotes>) -> Self {
+        let menus = lunch
+            .voting_mode
+            .rank(menus)
+            .into_iter()
+            .map(|(menu, score)| VotedMenuResponse::new(menu, score))
+            .collect();
+
+        Self {
+            id: lunch.id,
+            date: lunch.date,
+            start_time: lunch.start_time,
+            meeting_point: lunch.meeting_point,
+            group_id: lunch.group_id,
+            voting_deadline: lunch.voting_deadline,
+            tie_break: lunch.tie_break.into(),
+            winner_menu_id: lunch.winner_menu_id,
+            voting_mode: lunch.voting_mode.into(),
+            finalized_at: lunch.finalized_at,
+            menus,
+        }
+    }
+}
+
+impl From<Vote> for VoteResponse {
+    fn from(vote: Vote) -> Self {
+        Self {
+            id: vote.id,
+            menu_id: vote.menu_id,
+            user_id: vote.user_id,
+            lunch_id: vote.lunch_id,
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use crate::app::api::errors::{InternalServerError, JsonApiError, NotFound};
+use crate::app::api::responses::RestaurantResponse;
+use crate::db::common::DbReadOne;
+use crate::db::models::RestaurantGetById;
+use crate::db::repositories::RestaurantRepository;
+use actix_web::web::Data;
+use actix_web::{get, web, HttpResponse};
+use utoipa_actix_web::service_config::ServiceConfig;
+use uuid::Uuid;
+
+pub fn restaurant_api_config(config: &mut ServiceConfig) {
+    config.service(get_restaurant);
+}
+
+/// Gets detail of a restaurant, its menus are listed by `/menus?restaurant_id=`
+#[utoipa::path(
+    tag = "restaurants",
+    params(("id" = Uuid, Path, description = "Id of the restaurant")),
+    responses(
+        (status = 200, description = "Restaurant", body = RestaurantResponse),
+        (status = 404, response = NotFound),
+        (status = 500, response = InternalServerError),
+    )
+)]
+#[get("/restaurants/{id}")]
+async fn get_restaurant(
+    id: web::Path<Uuid>,
+    repo: Data<RestaurantRepository>,
+) -> Result<HttpResponse, JsonApiError> {
+    let restaurant = repo
+        .read_one(&RestaurantGetById::new(&id.into_inner()))
+        .await?;
+
+    Ok(HttpResponse::Ok().json(RestaurantResponse::from(restaurant)))
+}
@@ -0,0 +1,52 @@
+use crate::app::api::errors::{
+    BadRequest, Conflict, Forbidden, InternalServerError, JsonApiError, NotFound, Unauthorized,
+};
+use crate::app::api::requests::VoteRequest;
+use crate::app::api::responses::VoteResponse;
+use crate::app::authorization::ApiLunchAccess;
+use crate::db::common::DbCreate;
+use crate::db::models::VoteCreate;
+use crate::db::repositories::VoteRepository;
+use actix_web::web::Data;
+use actix_web::{post, web, HttpResponse};
+use utoipa_actix_web::service_config::ServiceConfig;
+
+pub fn vote_api_config(config: &mut ServiceConfig) {
+    config.service(post_vote);
+}
+
+/// Votes for a menu in a lunch
+///
+/// The previous vote of the user is replaced in plurality voting, the menu is ranked as the next
+/// choice of the user in ranked-choice voting.
+#[utoipa::path(
+    tag = "votes",
+    params(("id" = Uuid, Path, description = "Id of the lunch")),
+    request_body = VoteRequest,
+    security(("bearerAuth" = []), ("cookieAuth" = [])),
+    responses(
+        (status = 201, description = "Created vote", body = VoteResponse),
+        (status = 400, response = BadRequest),
+        (status = 401, response = Unauthorized),
+        (status = 403, response = Forbidden),
+        (status = 404, response = NotFound),
+        (status = 409, response = Conflict),
+        (status = 500, response = InternalServerError),
+    )
+)]
+#[post("/lunches/{id}/votes")]
+async fn post_vote(
+    access: ApiLunchAccess,
+    body: web::Json<VoteRequest>,
+    vote_repo: Data<VoteRepository>,
+) -> Result<HttpResponse, JsonApiError> {
+    let vote = vote_repo
+        .create(&VoteCreate {
+            menu_id: body.menu_id,
+            user_id: access.user_id,
+            lunch_id: access.lunch.id,
+        })
+        .await?;
+
+    Ok(HttpResponse::Created().json(VoteResponse::from(vote)))
+}
@@ -0,0 +1,225 @@
+use crate::app::api::auth::ApiUser;
+use crate::app::api::errors::JsonApiError;
+use crate::app::errors::ApiError;
+use crate::db::common::error::DbError;
+use crate::db::common::error::DbErrorType::BusinessLogic;
+use crate::db::common::DbReadOne;
+use crate::db::models::{GroupRole, GroupUserCheckRole, Lunch, LunchGetById};
+use crate::db::repositories::{GroupRepository, GroupRepositoryCheckUser, LunchRepository};
+use actix_identity::IdentityExt;
+use actix_web::dev::Payload;
+use actix_web::web::Data;
+use actix_web::{FromRequest, HttpRequest, ResponseError};
+use futures::future::{ready, LocalBoxFuture};
+use futures::FutureExt;
+use std::marker::PhantomData;
+use uuid::Uuid;
+
+/// The least role a signed user needs in a group to be authorized
+pub trait RequiredRole {
+    const ROLE: GroupRole;
+}
+
+/// Any member of the group
+#[derive(Debug, Clone, Copy)]
+pub struct Member;
+
+/// Admins and the owner of the group
+#[derive(Debug, Clone, Copy)]
+pub struct Admin;
+
+/// Only the owner of the group
+#[derive(Debug, Clone, Copy)]
+pub struct Owner;
+
+impl RequiredRole for Member {
+    const ROLE: GroupRole = GroupRole::Member;
+}
+
+impl RequiredRole for Admin {
+    const ROLE: GroupRole = GroupRole::Admin;
+}
+
+impl RequiredRole for Owner {
+    const ROLE: GroupRole = GroupRole::Owner;
+}
+
+/// How the user is authenticated, decides the errors the extractors respond with as well
+pub trait Authentication {
+    type Error: ResponseError + From<DbError> + 'static;
+
+    /// Id of the authenticated user
+    fn user_id(request: &HttpRequest) -> LocalBoxFuture<'static, Result<Uuid, Self::Error>>;
+
+    /// The path has no valid id of a group or a lunch
+    fn not_found() -> Self::Error;
+
+    /// The user does not have the required role in the group
+    fn forbidden() -> Self::Error;
+
+    fn internal_server_error() -> Self::Error;
+}
+
+/// Pages, the user is signed in by the session cookie
+#[derive(Debug, Clone, Copy)]
+pub struct Page;
+
+/// JSON api, the user is authenticated by [`ApiUser`] and errors are answered in JSON
+#[derive(Debug, Clone, Copy)]
+pub struct Api;
+
+impl Authentication for Page {
+    type Error = ApiError;
+
+    fn user_id(request: &HttpRequest) -> LocalBoxFuture<'static, Result<Uuid, Self::Error>> {
+        let user_id = request
+            .get_identity()
+            .map_err(|_| ApiError::Unauthorized)
+            .and_then(|identity| Ok(Uuid::parse_str(identity.id()?.as_ref())?));
+
+        ready(user_id).boxed_local()
+    }
+
+    fn not_found() -> Self::Error {
+        ApiError::NotFound
+    }
+
+    fn forbidden() -> Self::Error {
+        ApiError::Forbidden
+    }
+
+    fn internal_server_error() -> Self::Error {
+        ApiError::InternalServerError
+    }
+}
+
+impl Authentication for Api {
+    type Error = JsonApiError;
+
+    fn user_id(request: &HttpRequest) -> LocalBoxFuture<'static, Result<Uuid, Self::Error>> {
+        ApiUser::from_request(request, &mut Payload::None)
+            .map(|user| user.map(|user| user.id))
+            .boxed_local()
+    }
+
+    fn not_found() -> Self::Error {
+        JsonApiError::NotFound("Skupina nebo oběd neexistuje.".to_string())
+    }
+
+    fn forbidden() -> Self::Error {
+        JsonApiError::Forbidden("K této skupině nemáte přístup.".to_string())
+    }
+
+    fn internal_server_error() -> Self::Error {
+        JsonApiError::InternalServerError
+    }
+}
+
+/// Signed user authorized to access the group given by `{id}` of the path. Responds with 401
+/// without a signed user and with 403 when the user does not have the role `R` in the group.
+#[derive(Debug, Clone, Copy)]
+pub struct GroupAccess<R: RequiredRole = Member, A: Authentication = Page> {
+    pub user_id: Uuid,
+    pub group_id: Uuid,
+    /// Actual role of the user, at least `R`
+    pub role: GroupRole,
+    required: PhantomData<(R, A)>,
+}
+
+/// Signed user authorized to access the lunch given by `{id}` of the path through the group of
+/// the lunch. Responds like [`GroupAccess`] and with 404 when the lunch does not exist.
+#[derive(Debug, Clone)]
+pub struct LunchAccess<R: RequiredRole = Member, A: Authentication = Page> {
+    pub user_id: Uuid,
+    pub lunch: Lunch,
+    required: PhantomData<(R, A)>,
+}
+
+/// [`GroupAccess`] of the JSON api
+pub type ApiGroupAccess<R = Member> = GroupAccess<R, Api>;
+
+/// [`LunchAccess`] of the JSON api
+pub type ApiLunchAccess<R = Member> = LunchAccess<R, Api>;
+
+/// Id of the group or lunch the route is scoped to
+fn path_id<A: Authentication>(request: &HttpRequest) -> Result<Uuid, A::Error> {
+    request
+        .match_info()
+        .get("id")
+        .and_then(|id| Uuid::parse_str(id).ok())
+        .ok_or_else(A::not_found)
+}
+
+/// Checks the role of the user in the group, any business logic error (not a member, a lower
+/// role, a deleted group) means the user is not authorized
+async fn check_role<A: Authentication>(
+    group_repo: &GroupRepository,
+    user_id: &Uuid,
+    group_id: &Uuid,
+    required: GroupRole,
+) -> Result<GroupRole, A::Error> {
+    group_repo
+        .check_user_role(&GroupUserCheckRole::new(user_id, group_id, required))
+        .await
+        .map_err(|err| match err.error_type {
+            BusinessLogic(_) => A::forbidden(),
+            _ => A::internal_server_error(),
+        })
+}
+
+impl<R: RequiredRole + 'static, A: Authentication + 'static> FromRequest for GroupAccess<R, A> {
+    type Error = A::Error;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(request: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let user_id = A::user_id(request);
+        let group_id = path_id::<A>(request);
+        let group_repo = request.app_data::<Data<GroupRepository>>().cloned();
+
+        async move {
+            let user_id = user_id.await?;
+            let group_id = group_id?;
+            let group_repo = group_repo.ok_or_else(A::internal_server_error)?;
+
+            let role = check_role::<A>(&group_repo, &user_id, &group_id, R::ROLE).await?;
+
+            Ok(Self {
+                user_id,
+                group_id,
+                role,
+                required: PhantomData,
+            })
+        }
+        .boxed_local()
+    }
+}
+
+impl<R: RequiredRole + 'static, A: Authentication + 'static> FromRequest for LunchAccess<R, A> {
+    type Error = A::Error;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(request: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let user_id = A::user_id(request);
+        let lunch_id = path_id::<A>(request);
+        let group_repo = request.app_data::<Data<GroupRepository>>().cloned();
+        let lunch_repo = request.app_data::<Data<LunchRepository>>().cloned();
+
+        async move {
+            let user_id = user_id.await?;
+            let lunch_id = lunch_id?;
+            let (Some(group_repo), Some(lunch_repo)) = (group_repo, lunch_repo) else {
+                return Err(A::internal_server_error());
+            };
+
+            let lunch = lunch_repo.read_one(&LunchGetById { id: lunch_id }).await?;
+            check_role::<A>(&group_repo, &user_id, &lunch.group_id, R::ROLE).await?;
+
+            Ok(Self {
+                user_id,
+                lunch,
+                required: PhantomData,
+            })
+        }
+        .boxed_local()
+    }
+}
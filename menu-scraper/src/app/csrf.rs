@@ -0,0 +1,160 @@
+use crate::app::api::errors::JsonApiError;
+use crate::app::errors::{handle_error_template, ApiError};
+use crate::app::utils::api_token::generate_token_secret;
+use crate::app::utils::secret::constant_time_eq;
+use actix_session::{Session, SessionExt};
+use actix_web::body::EitherBody;
+use actix_web::dev::{forward_ready, Payload, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::http::{header, Method, StatusCode};
+use actix_web::{error, Error, FromRequest, HttpMessage, HttpRequest, HttpResponse};
+use futures::future::{ready, LocalBoxFuture, Ready};
+use futures::FutureExt;
+
+/// Header carrying the token, htmx sends it with every request from `hx-headers` of the page body
+pub const CSRF_HEADER: &str = "X-CSRF-Token";
+
+/// Key of the token in the session
+const SESSION_KEY: &str = "csrf_token";
+
+/// Paths authenticated by a Bearer token instead of the session cookie, the api with personal
+/// access tokens and the scrape trigger with SCRAPE_TRIGGER_TOKEN
+const BEARER_PATHS: &[&str] = &["/api/", "/scraping/"];
+
+/// Protects the session cookie against cross-site request forgery. Every session gets a random
+/// token, requests changing anything (POST, PUT, PATCH, DELETE) are rejected with 403 unless they
+/// send the token of their session in the `X-CSRF-Token` header. Requests of the api and of the
+/// scrape trigger authenticated by a Bearer token do not use the cookie, so they are not checked.
+///
+/// Needs the session, it has to be wrapped inside of the session middleware.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Csrf;
+
+impl<S, B> Transform<S, ServiceRequest> for Csrf
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Transform = CsrfMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(CsrfMiddleware { service }))
+    }
+}
+
+pub struct CsrfMiddleware<S> {
+    service: S,
+}
+
+impl<S, B> Service<ServiceRequest> for CsrfMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, request: ServiceRequest) -> Self::Future {
+        let token = match session_token(&request.get_session()) {
+            Ok(token) => token,
+            Err(err) => return ready(Err(err.into())).boxed_local(),
+        };
+
+        if needs_token(&request) && !sends_token(&request, &token) {
+            let response = rejection(&request).map_into_right_body();
+            return ready(Ok(request.into_response(response))).boxed_local();
+        }
+
+        // Pages put the token to their body by the CsrfToken extractor
+        request.extensions_mut().insert(CsrfToken(token));
+
+        self.service
+            .call(request)
+            .map(|response| response.map(ServiceResponse::map_into_left_body))
+            .boxed_local()
+    }
+}
+
+/// Token of the session, a new one is generated for a new session (including after logout)
+fn session_token(session: &Session) -> Result<String, ApiError> {
+    if let Some(token) = session.get::<String>(SESSION_KEY)? {
+        return Ok(token);
+    }
+
+    let token = generate_token_secret();
+    session.insert(SESSION_KEY, &token)?;
+
+    Ok(token)
+}
+
+/// Whether the request changes anything and may be authenticated by the session cookie. Only the
+/// [`BEARER_PATHS`] accept Bearer tokens instead of the cookie, any other `Authorization` header
+/// (e.g. Basic credentials cached by the browser) is sent by the browser with forged requests too.
+fn needs_token(request: &ServiceRequest) -> bool {
+    let changes_state =
+        [Method::POST, Method::PUT, Method::PATCH, Method::DELETE].contains(request.method());
+    let accepts_bearer = BEARER_PATHS
+        .iter()
+        .any(|path| request.path().starts_with(path));
+
+    changes_state && !(accepts_bearer && sends_bearer_token(request))
+}
+
+fn sends_bearer_token(request: &ServiceRequest) -> bool {
+    request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("Bearer "))
+}
+
+fn sends_token(request: &ServiceRequest, token: &str) -> bool {
+    request
+        .headers()
+        .get(CSRF_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|sent| constant_time_eq(sent.as_bytes(), token.as_bytes()))
+}
+
+/// Api answers with its JSON error, pages with the error banner
+fn rejection(request: &ServiceRequest) -> HttpResponse {
+    let message = "Platnost stránky vypršela, načtěte ji prosím znovu.".to_string();
+
+    if request.path().starts_with("/api/") {
+        return error::ResponseError::error_response(&JsonApiError::Forbidden(message));
+    }
+
+    handle_error_template(message, StatusCode::FORBIDDEN)
+}
+
+/// CSRF token of the session, rendered to `hx-headers` of the body of every page
+#[derive(Debug, Clone)]
+pub struct CsrfToken(String);
+
+impl CsrfToken {
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+impl FromRequest for CsrfToken {
+    type Error = ApiError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(request: &HttpRequest, _: &mut Payload) -> Self::Future {
+        // Missing only when the middleware is not used
+        ready(
+            request
+                .extensions()
+                .get::<CsrfToken>()
+                .cloned()
+                .ok_or(ApiError::InternalServerError),
+        )
+    }
+}
@@ -1,11 +1,11 @@
 use crate::app::templates::error::ErrorBannerTemplate;
+use crate::db::common::error::DbErrorType::BusinessLogic;
+use crate::db::common::error::{BusinessLogicErrorKind, DbError};
 use actix_web::error;
 use actix_web::http::StatusCode;
 use actix_web::HttpResponse;
 use argon2::password_hash;
 use askama::Template;
-use db::db::common::error::DbErrorType::BusinessLogic;
-use db::db::common::error::{BusinessLogicErrorKind, DbError};
 use serde::Serialize;
 use std::fmt;
 
@@ -17,6 +17,7 @@ pub enum ApiError {
     NotFound,
     BadRequest,
     Unauthorized,
+    Forbidden,
 }
 
 /// Htmx error (returns error banner)
@@ -35,6 +36,7 @@ impl fmt::Display for ApiError {
             ApiError::NotFound => write!(f, "Not found"),
             ApiError::BadRequest => write!(f, "Bad request"),
             ApiError::Unauthorized => write!(f, "Unauthorized"),
+            ApiError::Forbidden => write!(f, "Forbidden"),
         }
     }
 }
@@ -53,7 +55,7 @@ impl fmt::Display for HtmxError {
 impl From<DbError> for ApiError {
     fn from(err: DbError) -> Self {
         match &err.error_type {
-            BusinessLogic(kind) => match kind {
+            BusinessLogic(
                 BusinessLogicErrorKind::UserDoesNotExist
                 | BusinessLogicErrorKind::UserDeleted
                 | BusinessLogicErrorKind::RestaurantDoesNotExist
@@ -67,9 +69,17 @@ impl From<DbError> for ApiError {
                 | BusinessLogicErrorKind::LunchDoesNotExist
                 | BusinessLogicErrorKind::LunchDeleted
                 | BusinessLogicErrorKind::VoteDoesNotExist
-                | BusinessLogicErrorKind::VoteDeleted => ApiError::NotFound,
-                _ => ApiError::InternalServerError,
-            },
+                | BusinessLogicErrorKind::VoteDeleted
+                | BusinessLogicErrorKind::VetoDoesNotExist
+                | BusinessLogicErrorKind::VetoDeleted
+                | BusinessLogicErrorKind::ScrapeRunDoesNotExist
+                | BusinessLogicErrorKind::ApiTokenDoesNotExist
+                | BusinessLogicErrorKind::ApiTokenRevoked
+                | BusinessLogicErrorKind::CalendarFeedDoesNotExist
+                | BusinessLogicErrorKind::InvitationDoesNotExist
+                | BusinessLogicErrorKind::JoinLinkDoesNotExist
+                | BusinessLogicErrorKind::JoinLinkExpired,
+            ) => ApiError::NotFound,
             _ => ApiError::InternalServerError,
         }
     }
@@ -171,6 +181,7 @@ impl error::ResponseError for ApiError {
             ApiError::NotFound => StatusCode::NOT_FOUND,
             ApiError::BadRequest => StatusCode::BAD_REQUEST,
             ApiError::Unauthorized => StatusCode::UNAUTHORIZED,
+            ApiError::Forbidden => StatusCode::FORBIDDEN,
         }
     }
 
@@ -180,6 +191,7 @@ impl error::ResponseError for ApiError {
             ApiError::NotFound => HttpResponse::build(self.status_code()).finish(),
             ApiError::BadRequest => HttpResponse::build(self.status_code()).finish(),
             ApiError::Unauthorized => HttpResponse::build(self.status_code()).finish(),
+            ApiError::Forbidden => HttpResponse::build(self.status_code()).finish(),
         }
     }
 }
@@ -0,0 +1,24 @@
+use crate::app::utils::validation::Validation;
+use anyhow::Error;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct ApiTokenCreateFormData {
+    pub name: String,
+}
+
+impl Validation for ApiTokenCreateFormData {
+    fn validate(&self) -> Result<(), Error> {
+        if self.name.trim().is_empty() {
+            return Err(anyhow::anyhow!("Zadejte název tokenu."));
+        }
+
+        if self.name.chars().count() > 100 {
+            return Err(anyhow::anyhow!(
+                "Název tokenu může mít maximálně 100 znaků."
+            ));
+        }
+
+        Ok(())
+    }
+}
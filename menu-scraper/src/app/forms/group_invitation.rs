@@ -0,0 +1,36 @@
+use crate::app::utils::validation::Validation;
+use anyhow::Error;
+use serde::Deserialize;
+
+/// Join links are valid for a week by default
+const DEFAULT_JOIN_LINK_DAYS: i64 = 7;
+
+/// Join links are valid for at most a month
+const MAX_JOIN_LINK_DAYS: i64 = 30;
+
+#[derive(Debug, Deserialize)]
+pub struct GroupInvitationAnswerFormData {
+    pub accept: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GroupJoinLinkCreateFormData {
+    #[serde(rename = "valid-days", default = "default_join_link_days")]
+    pub valid_days: i64,
+}
+
+fn default_join_link_days() -> i64 {
+    DEFAULT_JOIN_LINK_DAYS
+}
+
+impl Validation for GroupJoinLinkCreateFormData {
+    fn validate(&self) -> Result<(), Error> {
+        if !(1..=MAX_JOIN_LINK_DAYS).contains(&self.valid_days) {
+            return Err(anyhow::anyhow!(
+                "Odkaz může platit 1 až {MAX_JOIN_LINK_DAYS} dní."
+            ));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,33 @@
+use crate::db::models::GroupRole;
+use serde::Deserialize;
+use uuid::Uuid;
+
+/// Role given to a member by the owner, the owner is only changed by the transfer
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupRoleForm {
+    Admin,
+    Member,
+}
+
+impl From<GroupRoleForm> for GroupRole {
+    fn from(role: GroupRoleForm) -> Self {
+        match role {
+            GroupRoleForm::Admin => GroupRole::Admin,
+            GroupRoleForm::Member => GroupRole::Member,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GroupUserRoleFormData {
+    #[serde(rename = "user-id")]
+    pub user_id: Uuid,
+    pub role: GroupRoleForm,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GroupOwnerFormData {
+    #[serde(rename = "user-id")]
+    pub user_id: Uuid,
+}
@@ -6,6 +6,26 @@ use serde::{Deserialize, Serialize};
 pub struct LoginFormData {
     pub email: String,
     pub password: String,
+    /// Page to return to after signing in
+    #[serde(default)]
+    pub next: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct LoginQuery {
+    /// Page to return to after signing in
+    pub next: Option<String>,
+}
+
+/// Page to return to after signing in, only paths of this app are followed so that the login
+/// page cannot redirect elsewhere
+pub fn next_path(next: Option<&str>) -> &str {
+    match next {
+        Some(path) if path.starts_with('/') && !path.starts_with("//") && !path.contains('\\') => {
+            path
+        }
+        _ => "/",
+    }
 }
 
 impl Validation for LoginFormData {
@@ -1,4 +1,12 @@
-use chrono::NaiveDate;
+use crate::app::utils::date::local_date_time_to_utc;
+use crate::app::utils::validation::Validation;
+use crate::db::common::query_parameters::DbOrder;
+use crate::db::models::{
+    DbRestaurantOrderingMethod, LunchCandidatesCreate, LunchCreate, RsvpStatus, TieBreak,
+    VotingMode,
+};
+use anyhow::Error;
+use chrono::{Local, NaiveDate, NaiveTime};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -12,4 +20,195 @@ pub struct GetLunchPreviewsQuery {
 pub struct CreateLunchFormData {
     #[serde(rename = "create-lunch-date-picker")]
     pub date: NaiveDate,
+    #[serde(rename = "create-lunch-time-picker")]
+    pub start_time: NaiveTime,
+    #[serde(rename = "create-lunch-meeting-point", default)]
+    pub meeting_point: String,
+    /// Time on the day of the lunch, empty input means voting never ends
+    #[serde(rename = "create-lunch-voting-deadline", default)]
+    pub voting_deadline: String,
+    #[serde(rename = "create-lunch-tie-break", default)]
+    pub tie_break: TieBreakForm,
+    /// Location of the user filled in by the browser for the closest restaurant
+    #[serde(rename = "create-lunch-longitude", default)]
+    pub longitude: String,
+    #[serde(rename = "create-lunch-latitude", default)]
+    pub latitude: String,
+    #[serde(rename = "create-lunch-voting-mode", default)]
+    pub voting_mode: VotingModeForm,
+    /// Menus nominated to the lunch right away
+    #[serde(rename = "create-lunch-candidates", default)]
+    pub candidates: CandidatesForm,
+    #[serde(
+        rename = "create-lunch-candidate-count",
+        default = "default_candidate_count"
+    )]
+    pub candidate_count: i64,
+}
+
+fn default_candidate_count() -> i64 {
+    3
+}
+
+impl CreateLunchFormData {
+    /// Meeting point is optional, empty input means none
+    pub fn meeting_point(&self) -> Option<String> {
+        let meeting_point = self.meeting_point.trim();
+        (!meeting_point.is_empty()).then(|| meeting_point.to_string())
+    }
+
+    /// Voting deadline is optional, empty input means none
+    pub fn voting_deadline(&self) -> Result<Option<NaiveTime>, Error> {
+        if self.voting_deadline.is_empty() {
+            return Ok(None);
+        }
+
+        NaiveTime::parse_from_str(&self.voting_deadline, "%H:%M")
+            .map(Some)
+            .map_err(|_| anyhow::anyhow!("Neplatný konec hlasování."))
+    }
+
+    /// Longitude and latitude, None when the browser did not share the location
+    pub fn location(&self) -> Option<(f64, f64)> {
+        let longitude = self.longitude.parse().ok()?;
+        let latitude = self.latitude.parse().ok()?;
+
+        Some((longitude, latitude))
+    }
+
+    /// Menus nominated when the lunch is created, the closest ones need the location
+    pub fn candidates(&self) -> Option<LunchCandidatesCreate> {
+        let order_by = match self.candidates {
+            CandidatesForm::None => return None,
+            CandidatesForm::Cheapest => DbRestaurantOrderingMethod::Price(DbOrder::Asc),
+            CandidatesForm::Closest => {
+                DbRestaurantOrderingMethod::Range(DbOrder::Asc, self.location()?)
+            }
+        };
+
+        Some(LunchCandidatesCreate {
+            order_by,
+            count: self.candidate_count,
+        })
+    }
+}
+
+/// Checks of the lunch shared by the form and the api, the form checks its own inputs before
+impl Validation for LunchCreate {
+    fn validate(&self) -> Result<(), Error> {
+        if self.date < Local::now().date_naive() {
+            return Err(anyhow::anyhow!("Oběd nelze vytvořit v minulosti."));
+        }
+
+        if self
+            .meeting_point
+            .as_ref()
+            .is_some_and(|meeting_point| meeting_point.chars().count() > 200)
+        {
+            return Err(anyhow::anyhow!("Místo srazu může mít maximálně 200 znaků."));
+        }
+
+        if self
+            .voting_deadline
+            .is_some_and(|deadline| deadline > local_date_time_to_utc(self.date, self.start_time))
+        {
+            return Err(anyhow::anyhow!(
+                "Hlasování musí skončit nejpozději v čase oběda."
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Validation for CreateLunchFormData {
+    fn validate(&self) -> Result<(), Error> {
+        self.voting_deadline()?;
+
+        if self.candidates != CandidatesForm::None && !(1..=10).contains(&self.candidate_count) {
+            return Err(anyhow::anyhow!("Navrhnout lze 1 až 10 menu."));
+        }
+
+        if self.candidates == CandidatesForm::Closest && self.location().is_none() {
+            return Err(anyhow::anyhow!(
+                "Pro návrh nejbližších menu povolte prosím zjištění polohy."
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// How the winner of the lunch is chosen when more menus have the most votes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TieBreakForm {
+    #[default]
+    Random,
+    Cheapest,
+    Closest,
+}
+
+impl From<TieBreakForm> for TieBreak {
+    fn from(tie_break: TieBreakForm) -> Self {
+        match tie_break {
+            TieBreakForm::Random => TieBreak::Random,
+            TieBreakForm::Cheapest => TieBreak::Cheapest,
+            TieBreakForm::Closest => TieBreak::Closest,
+        }
+    }
+}
+
+/// Which menus are nominated when the lunch is created
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CandidatesForm {
+    #[default]
+    None,
+    Cheapest,
+    Closest,
+}
+
+/// How members vote in the lunch
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VotingModeForm {
+    #[default]
+    Plurality,
+    Approval,
+    Ranked,
+}
+
+impl From<VotingModeForm> for VotingMode {
+    fn from(voting_mode: VotingModeForm) -> Self {
+        match voting_mode {
+            VotingModeForm::Plurality => VotingMode::Plurality,
+            VotingModeForm::Approval => VotingMode::Approval,
+            VotingModeForm::Ranked => VotingMode::Ranked,
+        }
+    }
+}
+
+/// Answer whether the user is coming to the lunch
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RsvpFormStatus {
+    Going,
+    NotGoing,
+    Maybe,
+}
+
+impl From<RsvpFormStatus> for RsvpStatus {
+    fn from(status: RsvpFormStatus) -> Self {
+        match status {
+            RsvpFormStatus::Going => RsvpStatus::Going,
+            RsvpFormStatus::NotGoing => RsvpStatus::NotGoing,
+            RsvpFormStatus::Maybe => RsvpStatus::Maybe,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct LunchRsvpFormData {
+    pub status: RsvpFormStatus,
 }
@@ -10,4 +10,49 @@ pub struct MenuListQuery {
     pub page: usize,
     pub longitude: Option<f64>,
     pub latitude: Option<f64>,
+    /// Comma separated numbers of allergens, menus containing them are filtered out
+    pub allergens: Option<String>,
+    /// Only menus with a vegetarian main course
+    #[serde(default)]
+    pub vegetarian: bool,
+    /// Only menus with a dish matching this text
+    pub search: Option<String>,
+}
+
+/// Search of dishes in menus for a range of dates
+#[derive(Debug, Deserialize)]
+pub struct MenuSearchQuery {
+    pub query: String,
+    pub date_from: NaiveDate,
+    pub date_to: NaiveDate,
+    pub method: RestaurantOrderingMethod,
+    pub ordering: Ordering,
+    pub page: usize,
+    pub longitude: Option<f64>,
+    pub latitude: Option<f64>,
+}
+
+impl MenuListQuery {
+    /// Parses numbers of excluded allergens, None when the list is malformed
+    pub fn excluded_allergens(&self) -> Option<Vec<i32>> {
+        match &self.allergens {
+            Some(allergens) => parse_allergens(allergens),
+            None => Some(Vec::new()),
+        }
+    }
+}
+
+/// Parses comma separated numbers of allergens, None when the list is malformed
+pub fn parse_allergens(allergens: &str) -> Option<Vec<i32>> {
+    allergens
+        .split(',')
+        .map(str::trim)
+        .filter(|allergen| !allergen.is_empty())
+        .map(|allergen| {
+            allergen
+                .parse::<i32>()
+                .ok()
+                .filter(|number| (1..=14).contains(number))
+        })
+        .collect()
 }
@@ -1,5 +1,8 @@
+pub mod api_token;
 pub mod group_creation;
 pub mod group_edit;
+pub mod group_invitation;
+pub mod group_role;
 pub mod login;
 pub mod lunch;
 pub mod menu;
@@ -5,6 +5,4 @@ use uuid::Uuid;
 pub struct UserDeleteFromGroup {
     #[serde(rename = "user-id")]
     pub user_id: Uuid,
-    #[serde(rename = "group-id")]
-    pub group_id: Uuid,
 }
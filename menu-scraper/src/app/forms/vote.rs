@@ -4,5 +4,12 @@ use uuid::Uuid;
 #[derive(Serialize, Deserialize, Debug)]
 pub struct AddVoteFormData {
     pub menu_id: Uuid,
-    pub lunch_id: Uuid,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct VetoFormData {
+    pub menu_id: Uuid,
+    /// Vetoes the whole restaurant of the menu instead of the menu
+    #[serde(default)]
+    pub restaurant: bool,
 }
@@ -0,0 +1,72 @@
+use crate::app::errors::HtmxError;
+use crate::app::forms::api_token::ApiTokenCreateFormData;
+use crate::app::templates::user_edit::ApiTokenListTemplate;
+use crate::app::utils::api_token::{format_token, generate_token_secret};
+use crate::app::utils::password::hash_password;
+use crate::app::utils::validation::Validation;
+use crate::app::view_models::api_token::{ApiTokenView, CreatedApiTokenView};
+use crate::db::common::{DbCreate, DbDelete, DbReadMany};
+use crate::db::models::{ApiTokenCreate, ApiTokenDelete, ApiTokenGetByUser};
+use crate::db::repositories::ApiTokenRepository;
+use actix_identity::Identity;
+use actix_web::web::Data;
+use actix_web::{web, HttpResponse};
+use askama::Template;
+use uuid::Uuid;
+
+pub fn api_token_config(config: &mut web::ServiceConfig) {
+    config
+        .service(web::resource("/api-tokens").route(web::post().to(post_api_token)))
+        .service(web::resource("/api-tokens/{id}").route(web::delete().to(delete_api_token)));
+}
+
+/// Creates a personal access token, the token is shown only in this response
+async fn post_api_token(
+    form: web::Form<ApiTokenCreateFormData>,
+    token_repo: Data<ApiTokenRepository>,
+    user: Identity,
+) -> Result<HttpResponse, HtmxError> {
+    form.validate()?;
+
+    let user_id = Uuid::parse_str(user.id()?.as_ref())?;
+
+    // Only the hash of the secret is stored, like with passwords
+    let secret = generate_token_secret();
+    let token = token_repo
+        .create(&ApiTokenCreate {
+            user_id,
+            name: form.name.trim().to_string(),
+            token_hash: hash_password(&secret)?,
+        })
+        .await?;
+
+    let tokens = token_repo.read_many(&ApiTokenGetByUser { user_id }).await?;
+
+    let template = ApiTokenListTemplate {
+        tokens: tokens.into_iter().map(ApiTokenView::from).collect(),
+        created_token: Some(CreatedApiTokenView {
+            token: format_token(&token.id, &secret),
+            name: token.name,
+        }),
+    };
+
+    let body = template.render()?;
+
+    Ok(HttpResponse::Ok().content_type("text/html").body(body))
+}
+
+/// Revokes a personal access token of the signed user
+async fn delete_api_token(
+    id: web::Path<Uuid>,
+    token_repo: Data<ApiTokenRepository>,
+    user: Identity,
+) -> Result<HttpResponse, HtmxError> {
+    token_repo
+        .delete(&ApiTokenDelete {
+            id: id.into_inner(),
+            user_id: Uuid::parse_str(user.id()?.as_ref())?,
+        })
+        .await?;
+
+    Ok(HttpResponse::Ok().finish())
+}
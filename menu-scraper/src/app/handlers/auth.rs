@@ -1,16 +1,20 @@
+use crate::app::csrf::CsrfToken;
 use crate::app::errors::{ApiError, HtmxError};
-use crate::app::forms::login::LoginFormData;
+use crate::app::forms::login::{next_path, LoginFormData, LoginQuery};
 use crate::app::templates::login::LoginTemplate;
 use crate::app::utils::password::verify_password;
 use crate::app::utils::validation::Validation;
 use crate::app::view_models::signed_user::SignedUser;
+use crate::db::models::{LoginAttemptBegin, LoginAttemptClear, LoginAttemptCreate, UserLogin};
+use crate::db::repositories::{
+    GetUserByEmail, LoginAttemptRepository, LoginAttemptRepositoryThrottle, UserRepository,
+};
 use actix_identity::Identity;
 use actix_session::Session;
 use actix_web::web::Data;
 use actix_web::{web, HttpMessage, HttpRequest, HttpResponse};
 use askama::Template;
-use db::db::models::UserLogin;
-use db::db::repositories::{GetUserByEmail, UserRepository};
+use chrono::Duration;
 
 pub fn auth_config(config: &mut web::ServiceConfig) {
     config
@@ -22,44 +26,82 @@ pub fn auth_config(config: &mut web::ServiceConfig) {
         );
 }
 
-/// Gets empty login form
-async fn get_login(user: Option<Identity>) -> Result<HttpResponse, ApiError> {
+/// Gets empty login form, the user returns to the `next` page after signing in
+async fn get_login(
+    user: Option<Identity>,
+    csrf: CsrfToken,
+    query: web::Query<LoginQuery>,
+) -> Result<HttpResponse, ApiError> {
     if user.is_some() {
-        // Already signed in, redirect to main page
+        // Already signed in, redirect to the requested or main page
         return Ok(HttpResponse::Found()
-            .append_header(("Location", "/"))
+            .append_header(("Location", next_path(query.next.as_deref())))
             .finish());
     }
 
-    let template = LoginTemplate {};
+    let template = LoginTemplate {
+        csrf_token: csrf.into_inner(),
+        next: query.into_inner().next,
+    };
 
     let body = template.render().map_err(ApiError::from)?;
 
     Ok(HttpResponse::Ok().content_type("text/html").body(body))
 }
 
-/// Submits login form
+/// Submits login form, failed attempts slow down further logins to the same account and from
+/// the same address until they are locked out for a while
 async fn post_login(
     form: web::Form<LoginFormData>,
     user_repo: Data<UserRepository>,
+    login_attempt_repo: Data<LoginAttemptRepository>,
     request: HttpRequest,
     session: Session,
 ) -> Result<HttpResponse, HtmxError> {
     // Check inputs
     form.validate()?;
 
-    // Get user by email
-    let user = user_repo
+    // Address of the connection itself, forwarded headers can be set by anyone
+    let ip_address = request
+        .peer_addr()
+        .map(|address| address.ip().to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+
+    // Refuse before verifying the password, so that guesses are not checked at all. The attempt
+    // is recorded right away, so concurrent guesses are counted as well.
+    let attempt = match login_attempt_repo
+        .begin(&LoginAttemptCreate::new(&form.email, &ip_address))
+        .await?
+    {
+        LoginAttemptBegin::Recorded(attempt) => attempt,
+        LoginAttemptBegin::Throttled(wait) => {
+            return Err(HtmxError::BannerError(format!(
+                "Příliš mnoho neúspěšných pokusů o přihlášení. Zkuste to znovu za {}.",
+                format_wait(wait)
+            )));
+        }
+    };
+
+    // Get user by email and check if password match
+    let user = match user_repo
         .login(&UserLogin {
             email: form.email.clone(),
         })
         .await
-        // Map error to same error to reduce info retrieved (whether email or password is wrong)
-        .map_err(|_| HtmxError::BannerError("Chybný email nebo heslo.".to_string()))?;
+    {
+        Ok(user) if verify_password(form.password.as_ref(), &user.password_hash).is_ok() => user,
+        // Same error for both, to reduce info retrieved (whether email or password is wrong)
+        _ => {
+            return Err(HtmxError::BannerError(
+                "Chybný email nebo heslo.".to_string(),
+            ));
+        }
+    };
 
-    // Check if password match
-    verify_password(form.password.as_ref(), &user.password_hash)
-        .map_err(|_| HtmxError::BannerError("Chybný email nebo heslo.".to_string()))?;
+    // The account is not slowed down for its owner anymore
+    login_attempt_repo
+        .clear(&LoginAttemptClear::new(&attempt))
+        .await?;
 
     // Login user
     Identity::login(&request.extensions(), String::from(user.id))
@@ -73,10 +115,21 @@ async fn post_login(
     )?;
 
     Ok(HttpResponse::Ok()
-        .append_header(("HX-Redirect", "/"))
+        .append_header(("HX-Redirect", next_path(form.next.as_deref())))
         .finish())
 }
 
+/// Rounds the wait up to whole seconds, or whole minutes when it takes longer than a minute
+fn format_wait(wait: Duration) -> String {
+    let seconds = (wait.num_milliseconds() + 999) / 1000;
+
+    if seconds > 60 {
+        format!("{} min", (seconds + 59) / 60)
+    } else {
+        format!("{} s", seconds.max(1))
+    }
+}
+
 /// Signs out user
 async fn logout(user: Identity) -> Result<HttpResponse, ApiError> {
     user.logout();
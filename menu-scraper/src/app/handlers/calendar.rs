@@ -0,0 +1,196 @@
+use crate::app::errors::{ApiError, HtmxError};
+use crate::app::public_url::PublicUrl;
+use crate::app::templates::user_edit::CalendarFeedTemplate;
+use crate::app::utils::api_token::generate_token_secret;
+use crate::app::utils::calendar::{render_calendar, CalendarEvent};
+use crate::db::common::{DbCreate, DbReadMany, DbReadOne};
+use crate::db::models::{
+    CalendarFeedCreate, CalendarFeedGetByToken, LunchGetMany, LunchWithGroup,
+    MenuWithRestaurantAndVotes, VoteGetManyByLunches,
+};
+use crate::db::repositories::{CalendarFeedRepository, LunchRepository, VoteRepository};
+use actix_identity::Identity;
+use actix_web::web::Data;
+use actix_web::{web, HttpResponse};
+use askama::Template;
+use chrono::{DateTime, Duration, Local, TimeZone, Utc};
+use chrono_tz::Europe::Prague;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Lunches older than this are not exported, so the feed does not grow forever
+const FEED_HISTORY_DAYS: i64 = 60;
+
+/// Lunches are expected to take an hour
+const LUNCH_DURATION_MINUTES: i64 = 60;
+
+pub fn calendar_config(config: &mut web::ServiceConfig) {
+    config
+        .service(web::resource("/calendar/{token}.ics").route(web::get().to(get_calendar)))
+        .service(web::resource("/calendar-feed").route(web::post().to(post_calendar_feed)));
+}
+
+/// Absolute URL of the feed, calendar apps subscribe to it without a session
+pub fn calendar_feed_url(public_url: &PublicUrl, token: &str) -> String {
+    public_url.url(&format!("/calendar/{token}.ics"))
+}
+
+/// Exports lunches of the owner of the feed, the secret token in the path replaces signing in
+async fn get_calendar(
+    public_url: Data<PublicUrl>,
+    token: web::Path<String>,
+    feed_repo: Data<CalendarFeedRepository>,
+    lunch_repo: Data<LunchRepository>,
+    vote_repo: Data<VoteRepository>,
+) -> Result<HttpResponse, ApiError> {
+    let feed = feed_repo
+        .read_one(&CalendarFeedGetByToken::new(&token.into_inner()))
+        .await?;
+
+    let lunches = lunch_repo
+        .read_many(&LunchGetMany {
+            group_id: None,
+            user_id: Some(feed.user_id),
+            from: Some(Local::now().date_naive() - Duration::days(FEED_HISTORY_DAYS)),
+            to: None,
+        })
+        .await?;
+
+    // Votes of all lunches at once, the feed is refreshed often by calendar apps
+    let mut menus: HashMap<Uuid, Vec<MenuWithRestaurantAndVotes>> = HashMap::new();
+    for menu in vote_repo
+        .read_many(&VoteGetManyByLunches {
+            lunch_ids: lunches.iter().map(|lunch| lunch.id).collect(),
+        })
+        .await?
+    {
+        menus.entry(menu.lunch_id).or_default().push(menu.into());
+    }
+
+    let events: Vec<CalendarEvent> = lunches
+        .into_iter()
+        .map(|lunch| {
+            let menu = LunchMenu::new(&lunch, menus.remove(&lunch.id).unwrap_or_default());
+            lunch_event(lunch, menu, &public_url)
+        })
+        .collect();
+
+    let body = render_calendar("Obědy", &events, Utc::now());
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/calendar; charset=utf-8")
+        .body(body))
+}
+
+/// Menu the lunch is held at
+enum LunchMenu {
+    /// Winner stored after the voting deadline, the lunches due are finalized in the background
+    Winner(MenuWithRestaurantAndVotes),
+    /// Menu leading the voting so far
+    Leading {
+        menu: MenuWithRestaurantAndVotes,
+        score: i64,
+    },
+}
+
+impl LunchMenu {
+    /// The stored winner, otherwise the menu leading the tally of the lunch page. Menus nobody
+    /// voted for (only nominated) do not lead.
+    fn new(lunch: &LunchWithGroup, menus: Vec<MenuWithRestaurantAndVotes>) -> Option<Self> {
+        if let Some(winner_id) = lunch.winner_menu_id {
+            return menus
+                .into_iter()
+                .find(|menu| menu.menu_id == winner_id)
+                .map(LunchMenu::Winner);
+        }
+
+        lunch
+            .voting_mode
+            .rank(menus)
+            .into_iter()
+            .next()
+            .filter(|(_, score)| *score > 0)
+            .map(|(menu, score)| LunchMenu::Leading { menu, score })
+    }
+}
+
+/// Event of a lunch, located in the winning or the currently leading restaurant
+fn lunch_event(
+    lunch: LunchWithGroup,
+    menu: Option<LunchMenu>,
+    public_url: &PublicUrl,
+) -> CalendarEvent {
+    let start = lunch_start(&lunch);
+    let meeting_point = lunch
+        .meeting_point
+        .map(|meeting_point| format!("Sraz: {meeting_point}\n"))
+        .unwrap_or_default();
+    let url = public_url.url(&format!("/groups/{}", lunch.group_id));
+
+    let (menu, state) = match menu {
+        Some(LunchMenu::Winner(menu)) => {
+            let state = format!("Vybrána restaurace {}.", menu.name);
+            (menu, state)
+        }
+        Some(LunchMenu::Leading { menu, score }) => {
+            let state = format!("Zatím vede {} (hlasů: {score}).", menu.name);
+            (menu, state)
+        }
+        None => {
+            return CalendarEvent {
+                uid: format!("{}@menu-scraper", lunch.id),
+                start,
+                end: start + Duration::minutes(LUNCH_DURATION_MINUTES),
+                summary: format!("Oběd – {}", lunch.group_name),
+                location: None,
+                description: Some(format!("{meeting_point}Zatím nikdo nehlasoval.\n{url}")),
+            }
+        }
+    };
+
+    CalendarEvent {
+        uid: format!("{}@menu-scraper", lunch.id),
+        start,
+        end: start + Duration::minutes(LUNCH_DURATION_MINUTES),
+        summary: format!("Oběd – {}: {}", lunch.group_name, menu.name),
+        location: Some(format!(
+            "{}, {} {}, {} {}",
+            menu.name, menu.street, menu.house_number, menu.zip_code, menu.city
+        )),
+        description: Some(format!("{meeting_point}{state}\n{url}")),
+    }
+}
+
+/// Start of the lunch, times of lunches are local times of Brno restaurants
+fn lunch_start(lunch: &LunchWithGroup) -> DateTime<Utc> {
+    let start = lunch.date.and_time(lunch.start_time);
+
+    // Times skipped by the change to summer time do not exist, they are taken as UTC
+    Prague
+        .from_local_datetime(&start)
+        .earliest()
+        .unwrap_or_else(|| Prague.from_utc_datetime(&start))
+        .with_timezone(&Utc)
+}
+
+/// Creates a new secret feed URL of the signed user, the previous URL stops working
+async fn post_calendar_feed(
+    public_url: Data<PublicUrl>,
+    feed_repo: Data<CalendarFeedRepository>,
+    user: Identity,
+) -> Result<HttpResponse, HtmxError> {
+    let feed = feed_repo
+        .create(&CalendarFeedCreate {
+            user_id: Uuid::parse_str(user.id()?.as_ref())?,
+            token: generate_token_secret(),
+        })
+        .await?;
+
+    let template = CalendarFeedTemplate {
+        feed_url: Some(calendar_feed_url(&public_url, &feed.token)),
+    };
+
+    let body = template.render()?;
+
+    Ok(HttpResponse::Ok().content_type("text/html").body(body))
+}
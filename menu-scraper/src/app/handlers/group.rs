@@ -1,22 +1,47 @@
+use crate::app::authorization::{Admin, GroupAccess, LunchAccess, Owner};
+use crate::app::csrf::CsrfToken;
 use crate::app::errors::{ApiError, HtmxError};
 use crate::app::forms::group_creation::GroupCreationFormData;
 use crate::app::forms::group_edit::GroupEditFormData;
-use crate::app::forms::lunch::CreateLunchFormData;
+use crate::app::forms::group_role::{GroupOwnerFormData, GroupUserRoleFormData};
+use crate::app::forms::lunch::{CreateLunchFormData, LunchRsvpFormData};
 use crate::app::forms::user_add_in_group::UserAddInGroupForm;
 use crate::app::forms::user_delete_from_group::UserDeleteFromGroup;
-use crate::app::forms::vote::AddVoteFormData;
+use crate::app::forms::vote::{AddVoteFormData, VetoFormData};
 use crate::app::templates::group::GroupEditTemplate;
 use crate::app::templates::group::{
     GroupCreateLunchFormTemplate, GroupCreateLunchTemplate, GroupCreationTemplate,
     GroupDetailsTemplate, GroupLunchMenusTemplate, GroupsTemplate,
 };
 use crate::app::templates::user_group::{UserGroup, UserGroupPreview};
+use crate::app::utils::date::local_date_time_to_utc;
 use crate::app::utils::picture::validate_and_save_picture;
 use crate::app::utils::validation::Validation;
-use crate::app::view_models::group::GroupView;
-use crate::app::view_models::lunch::MenuWithRestaurantAndVotesView;
+use crate::app::view_models::group::{GroupMemberView, GroupView};
+use crate::app::view_models::group_invitation::GroupInvitationView;
+use crate::app::view_models::lunch::{
+    LunchAttendanceView, LunchVetoesView, LunchVotingView, MenuWithRestaurantAndVotesView,
+};
 use crate::app::view_models::signed_user::SignedUser;
 use crate::app::view_models::user_preview::UserPreviewView;
+use crate::db::common::{DbCreate, DbDelete, DbReadMany, DbReadOne, DbUpdate};
+use crate::db::models::{
+    GroupCreate, GroupDelete, GroupGetById, GroupGetGroupsByUser, GroupInvitationGetByUser,
+    GroupOwnershipTransfer, GroupRole, GroupUpdate, GroupUserCreate, GroupUserDelete,
+    GroupUserRoleUpdate,
+};
+use crate::db::models::{
+    LunchCreate, LunchGetById, LunchGetMany, LunchRsvpCreate, VetoCreate, VetoDelete, VoteCreate,
+    VoteDelete, VoteGetMany,
+};
+use crate::db::repositories::{
+    GroupInvitationRepository, GroupRepository, GroupRepositoryAddUser, GroupRepositoryListUsers,
+    GroupRepositoryRemoveUser, GroupRepositoryRoles,
+};
+use crate::db::repositories::{
+    LunchRepository, LunchRepositoryFinalize, LunchRepositoryRsvp, VoteRepository,
+    VoteRepositoryVeto,
+};
 use actix_identity::Identity;
 use actix_multipart::form::MultipartForm;
 use actix_session::Session;
@@ -24,20 +49,10 @@ use actix_web::web::Data;
 use actix_web::{web, HttpResponse};
 use askama::Template;
 use chrono::Local;
-use db::db::common::{DbCreate, DbDelete, DbReadMany, DbReadOne, DbUpdate};
-use db::db::models::{
-    GetGroupUserByIds, LunchCreate, LunchGetById, LunchGetMany, VoteCreate, VoteGetMany,
-};
-use db::db::models::{
-    GroupCreate, GroupDelete, GroupGetById, GroupGetGroupsByUser, GroupUpdate, GroupUserCreate,
-    GroupUserDelete,
-};
-use db::db::repositories::{
-    GroupRepository, GroupRepositoryAddUser, GroupRepositoryListUsers, GroupRepositoryRemoveUser,
-};
-use db::db::repositories::{GroupRepositoryCheckUser, LunchRepository, VoteRepository};
 use uuid::Uuid;
 
+/// Routes of a group or a lunch take its id as `{id}` of the path, the signed user is authorized
+/// for it by [`GroupAccess`] or [`LunchAccess`] before the handler runs
 pub fn group_config(config: &mut web::ServiceConfig) {
     config
         .service(
@@ -53,37 +68,55 @@ pub fn group_config(config: &mut web::ServiceConfig) {
         )
         .service(web::resource("/group-create").route(web::get().to(get_group_create_form)))
         .service(web::resource("/group-edit/{id}").route(web::get().to(get_group_edit_form)))
+        .service(web::resource("/group-user").route(web::get().to(get_group_user)))
         .service(
-            web::resource("/group-user")
-                .route(web::get().to(get_group_user))
+            web::resource("/group-user/{id}")
                 .route(web::post().to(post_group_user))
                 .route(web::delete().to(delete_group_user)),
         )
-        .service(web::resource("/group-leave").route(web::delete().to(user_leave)))
+        .service(web::resource("/group-user-role/{id}").route(web::put().to(put_group_user_role)))
+        .service(web::resource("/group-owner/{id}").route(web::put().to(put_group_owner)))
+        .service(web::resource("/group-leave/{id}").route(web::delete().to(user_leave)))
         .service(web::resource("/group-create-lunch/{id}").route(web::post().to(create_lunch)))
         .service(
             web::resource("/group-create-lunch-form/{id}").route(web::post().to(create_lunch_form)),
         )
         .service(web::resource("/group-lunch/{id}").route(web::get().to(group_lunch_menus)))
-        .service(web::resource("/menu-vote").route(web::post().to(menu_vote)));
+        .service(web::resource("/menu-vote/{id}").route(web::post().to(menu_vote)))
+        .service(
+            web::resource("/menu-vote/{id}/{vote_id}").route(web::delete().to(delete_menu_vote)),
+        )
+        .service(web::resource("/menu-veto/{id}").route(web::post().to(menu_veto)))
+        .service(
+            web::resource("/lunch-vetoes/{id}/{veto_id}")
+                .route(web::delete().to(delete_lunch_veto)),
+        )
+        .service(web::resource("/lunch-rsvp/{id}").route(web::post().to(lunch_rsvp)));
 }
 
 async fn group_index(
     repo: Data<GroupRepository>,
+    invitation_repo: Data<GroupInvitationRepository>,
     user: Identity,
     session: Session,
+    csrf: CsrfToken,
 ) -> Result<HttpResponse, ApiError> {
     let signed_user = session.get::<SignedUser>("signed_user")?;
+    let user_id = Uuid::parse_str(user.id()?.as_ref())?;
 
-    let group_previews = repo
-        .read_many(&GroupGetGroupsByUser {
-            user_id: Uuid::parse_str(user.id()?.as_ref())?,
-        })
+    let group_previews = repo.read_many(&GroupGetGroupsByUser { user_id }).await?;
+    let invitations = invitation_repo
+        .read_many(&GroupInvitationGetByUser::new(&user_id))
         .await?;
 
     let template = GroupsTemplate {
         group_previews,
+        invitations: invitations
+            .into_iter()
+            .map(GroupInvitationView::from)
+            .collect(),
         signed_user,
+        csrf_token: csrf.into_inner(),
     };
 
     let body = template.render()?;
@@ -92,30 +125,26 @@ async fn group_index(
 }
 
 /// Gets empty group creation form
-async fn get_group_create_form(_: Identity) -> Result<HttpResponse, ApiError> {
-    let template = GroupCreationTemplate {};
+async fn get_group_create_form(_: Identity, csrf: CsrfToken) -> Result<HttpResponse, ApiError> {
+    let template = GroupCreationTemplate {
+        csrf_token: csrf.into_inner(),
+    };
     let body = template.render()?;
     Ok(HttpResponse::Ok().content_type("text/html").body(body))
 }
 
 /// Gets group edit form
 async fn get_group_edit_form(
-    user: Identity,
-    id: web::Path<Uuid>,
+    access: GroupAccess<Admin>,
     group_repo: Data<GroupRepository>,
+    csrf: CsrfToken,
 ) -> Result<HttpResponse, ApiError> {
-    let group = group_repo.read_one(&GroupGetById { id: *id }).await?;
-
-    // Check if signed user is the author of this group
-    if group.author_id != Uuid::parse_str(user.id()?.as_ref())? {
-        return Err(ApiError::Unauthorized);
-    }
+    let group_by_id = GroupGetById {
+        id: access.group_id,
+    };
 
-    let users = group_repo
-        .list_group_users(&GroupGetById {
-            id: id.into_inner(),
-        })
-        .await?;
+    let group = group_repo.read_one(&group_by_id).await?;
+    let members = group_repo.list_group_members(&group_by_id).await?;
 
     let template = GroupEditTemplate {
         group: GroupView {
@@ -123,14 +152,16 @@ async fn get_group_edit_form(
             name: group.name,
             description: group.description,
             picture: group.picture,
-            users: users
+            users: members
                 .into_iter()
-                .map(UserPreviewView::from)
-                // Don't list group author
-                .filter(|u| u.id != group.author_id)
+                .map(GroupMemberView::from)
+                // Don't list group owner
+                .filter(|m| m.role != GroupRole::Owner)
                 .collect(),
         },
         group_id: group.id, // Workaround, because askama does not support passing attributes to includes, for some reason
+        is_owner: access.role == GroupRole::Owner,
+        csrf_token: csrf.into_inner(),
     };
 
     let body = template.render()?;
@@ -179,24 +210,16 @@ async fn post_group(
 
 /// Edit already existing group
 async fn put_group(
-    id: web::Path<Uuid>,
+    access: GroupAccess<Admin>,
     MultipartForm(form): MultipartForm<GroupEditFormData>,
     group_repo: Data<GroupRepository>,
-    user: Identity,
 ) -> Result<HttpResponse, HtmxError> {
     let group = group_repo
         .read_one(&GroupGetById {
-            id: id.into_inner(),
+            id: access.group_id,
         })
         .await?;
 
-    // Check if signed user is the author of this group
-    if group.author_id != Uuid::parse_str(user.id()?.as_ref())? {
-        return Err(HtmxError::BannerError(
-            "Tento uživatel nemůže měnit tuto skupinu.".to_string(),
-        ));
-    }
-
     // Check inputs
     form.validate()?;
 
@@ -231,19 +254,19 @@ async fn put_group(
 }
 
 async fn group_details(
-    id: web::Path<Uuid>,
+    access: GroupAccess,
     group_repo: Data<GroupRepository>,
     lunch_repo: Data<LunchRepository>,
     session: Session,
-    identity: Identity,
+    csrf: CsrfToken,
 ) -> Result<HttpResponse, ApiError> {
-    let group_id = id.into_inner();
-    let user_id = Uuid::parse_str(identity.id()?.as_ref())?;
+    let GroupAccess {
+        user_id,
+        group_id,
+        role,
+        ..
+    } = access;
     let signed_user = session.get::<SignedUser>("signed_user")?;
-    group_repo
-        .check_user_is_member(&GetGroupUserByIds { user_id, group_id })
-        .await
-        .map_err(|_| ApiError::Unauthorized)?;
 
     let group_by_id = GroupGetById { id: group_id };
 
@@ -260,12 +283,13 @@ async fn group_details(
         .await?;
 
     let template = GroupDetailsTemplate {
-        is_author: user_id == group.author_id,
+        is_admin: role >= GroupRole::Admin,
+        is_owner: role == GroupRole::Owner,
         group,
         signed_user,
         group_members: members,
         group_lunches: lunches,
-        user_id,
+        csrf_token: csrf.into_inner(),
     };
 
     let body = template.render()?;
@@ -273,9 +297,9 @@ async fn group_details(
     Ok(HttpResponse::Ok().body(body))
 }
 
-async fn create_lunch(group_id: web::Path<Uuid>) -> Result<HttpResponse, HtmxError> {
+async fn create_lunch(access: GroupAccess) -> Result<HttpResponse, HtmxError> {
     let template = GroupCreateLunchTemplate {
-        group_id: group_id.into_inner(),
+        group_id: access.group_id,
         min_selection_date: Local::now().date_naive(),
     };
     let body = template.render()?;
@@ -284,34 +308,33 @@ async fn create_lunch(group_id: web::Path<Uuid>) -> Result<HttpResponse, HtmxErr
 
 async fn create_lunch_form(
     lunch_repo: Data<LunchRepository>,
-    group_repo: Data<GroupRepository>,
     form: web::Form<CreateLunchFormData>,
-    group_id: web::Path<Uuid>,
-    identity: Identity,
+    access: GroupAccess,
 ) -> Result<HttpResponse, HtmxError> {
-    let user_id = Uuid::parse_str(identity.id()?.as_ref())?;
-
-    group_repo
-        .check_user_is_member(&GetGroupUserByIds {
-            user_id,
-            group_id: *group_id,
-        })
-        .await
-        .map_err(|_| {
-            HtmxError::BannerError(
-                "Tento uživatel nemůže vytvořit oběd, protože není ve skupině.".to_string(),
-            )
-        })?;
+    form.validate()?;
 
     let date = form.date;
-    let group_id = group_id.into_inner();
-    let lunch = lunch_repo.create(&LunchCreate { date, group_id }).await?;
-
-    let template = GroupCreateLunchFormTemplate {
-        group_id,
-        lunch,
+    let group_id = access.group_id;
+    let location = form.location();
+    let lunch_create = LunchCreate {
         date,
+        group_id,
+        start_time: form.start_time,
+        meeting_point: form.meeting_point(),
+        voting_deadline: form
+            .voting_deadline()?
+            .map(|deadline| local_date_time_to_utc(date, deadline)),
+        tie_break: form.tie_break.into(),
+        longitude: location.map(|(longitude, _)| longitude),
+        latitude: location.map(|(_, latitude)| latitude),
+        voting_mode: form.voting_mode.into(),
+        candidates: form.candidates(),
     };
+    lunch_create.validate()?;
+
+    let lunch = lunch_repo.create(&lunch_create).await?;
+
+    let template = GroupCreateLunchFormTemplate { lunch };
     let body = template.render()?;
 
     Ok(HttpResponse::Ok().body(body))
@@ -319,25 +342,52 @@ async fn create_lunch_form(
 
 // Displaying the lunch menus
 async fn group_lunch_menus(
-    lunch_id: web::Path<Uuid>,
+    access: LunchAccess,
     vote_repo: Data<VoteRepository>,
     lunch_repo: Data<LunchRepository>,
     session: Session,
-    user_id: Identity,
 ) -> Result<HttpResponse, HtmxError> {
-    let lunch_id = lunch_id.into_inner();
-    let menus = vote_repo.read_many(&VoteGetMany { lunch_id }).await?;
     let signed_user = session.get::<SignedUser>("signed_user")?;
-    let lunch = lunch_repo.read_one(&LunchGetById { id: lunch_id }).await?;
-    let user_id = user_id.id()?.parse()?;
+
+    render_lunch_menus(
+        access.lunch.id,
+        access.user_id,
+        signed_user,
+        &vote_repo,
+        &lunch_repo,
+    )
+    .await
+}
+
+/// Renders the lunch with voted menus and answers of the members
+async fn render_lunch_menus(
+    lunch_id: Uuid,
+    user_id: Uuid,
+    signed_user: Option<SignedUser>,
+    vote_repo: &VoteRepository,
+    lunch_repo: &LunchRepository,
+) -> Result<HttpResponse, HtmxError> {
+    // Winner is stored when the lunch is shown first after the deadline
+    let lunch = lunch_repo.finalize(&LunchGetById { id: lunch_id }).await?;
+    let menus = vote_repo.read_many(&VoteGetMany { lunch_id }).await?;
+    let menus: Vec<_> = lunch
+        .voting_mode
+        .rank(menus)
+        .into_iter()
+        .map(|(m, score)| MenuWithRestaurantAndVotesView::new(m, user_id, score))
+        .collect();
+    let rsvps = lunch_repo
+        .list_rsvps(&LunchGetById { id: lunch_id })
+        .await?;
+    let vetoes = vote_repo.list_vetoes(&VoteGetMany { lunch_id }).await?;
 
     let template = GroupLunchMenusTemplate {
         signed_user,
+        voting: LunchVotingView::new(&lunch, &menus),
         lunch,
-        menus: menus
-            .into_iter()
-            .map(|m| MenuWithRestaurantAndVotesView::new(m, user_id))
-            .collect(),
+        menus,
+        attendance: LunchAttendanceView::new(&rsvps, user_id),
+        vetoes: LunchVetoesView::new(vetoes, user_id),
     };
     let body = template.render()?;
     Ok(HttpResponse::Ok().body(body))
@@ -345,16 +395,16 @@ async fn group_lunch_menus(
 
 // Voting for a specific menu, returning the updated menu
 async fn menu_vote(
+    access: LunchAccess,
     vote_repo: Data<VoteRepository>,
     lunch_repo: Data<LunchRepository>,
     form: web::Form<AddVoteFormData>,
-    user_id: Identity,
     session: Session,
 ) -> Result<HttpResponse, HtmxError> {
     let signed_user = session.get::<SignedUser>("signed_user")?;
-    let user_id = user_id.id()?.parse()?;
+    let user_id = access.user_id;
     let menu_id = form.menu_id;
-    let lunch_id = form.lunch_id;
+    let lunch_id = access.lunch.id;
 
     vote_repo
         .create(&VoteCreate {
@@ -364,18 +414,112 @@ async fn menu_vote(
         })
         .await?;
 
-    let lunch = lunch_repo.read_one(&LunchGetById { id: lunch_id }).await?;
-    let menus = vote_repo.read_many(&VoteGetMany { lunch_id }).await?;
-    let template = GroupLunchMenusTemplate {
+    render_lunch_menus(lunch_id, user_id, signed_user, &vote_repo, &lunch_repo).await
+}
+
+// Withdrawing a vote of the user, returning the updated lunch
+async fn delete_menu_vote(
+    access: LunchAccess,
+    path: web::Path<(Uuid, Uuid)>,
+    vote_repo: Data<VoteRepository>,
+    lunch_repo: Data<LunchRepository>,
+    session: Session,
+) -> Result<HttpResponse, HtmxError> {
+    let signed_user = session.get::<SignedUser>("signed_user")?;
+    let (_, vote_id) = path.into_inner();
+
+    vote_repo
+        .delete(&VoteDelete {
+            id: vote_id,
+            user_id: access.user_id,
+            lunch_id: access.lunch.id,
+        })
+        .await?;
+
+    render_lunch_menus(
+        access.lunch.id,
+        access.user_id,
         signed_user,
-        lunch,
-        menus: menus
-            .into_iter()
-            .map(|m| MenuWithRestaurantAndVotesView::new(m, user_id))
-            .collect(),
-    };
-    let body = template.render()?;
-    Ok(HttpResponse::Ok().body(body))
+        &vote_repo,
+        &lunch_repo,
+    )
+    .await
+}
+
+// Vetoing a menu or its restaurant, returning the updated lunch
+async fn menu_veto(
+    access: LunchAccess,
+    vote_repo: Data<VoteRepository>,
+    lunch_repo: Data<LunchRepository>,
+    form: web::Form<VetoFormData>,
+    session: Session,
+) -> Result<HttpResponse, HtmxError> {
+    let signed_user = session.get::<SignedUser>("signed_user")?;
+    let user_id = access.user_id;
+    let lunch_id = access.lunch.id;
+
+    vote_repo
+        .veto(&VetoCreate {
+            menu_id: form.menu_id,
+            user_id,
+            lunch_id,
+            restaurant: form.restaurant,
+        })
+        .await?;
+
+    render_lunch_menus(lunch_id, user_id, signed_user, &vote_repo, &lunch_repo).await
+}
+
+// Withdrawing a veto of the user, returning the updated lunch
+async fn delete_lunch_veto(
+    access: LunchAccess,
+    path: web::Path<(Uuid, Uuid)>,
+    vote_repo: Data<VoteRepository>,
+    lunch_repo: Data<LunchRepository>,
+    session: Session,
+) -> Result<HttpResponse, HtmxError> {
+    let signed_user = session.get::<SignedUser>("signed_user")?;
+    let (_, veto_id) = path.into_inner();
+
+    vote_repo
+        .remove_veto(&VetoDelete {
+            id: veto_id,
+            user_id: access.user_id,
+            lunch_id: access.lunch.id,
+        })
+        .await?;
+
+    render_lunch_menus(
+        access.lunch.id,
+        access.user_id,
+        signed_user,
+        &vote_repo,
+        &lunch_repo,
+    )
+    .await
+}
+
+// Answering whether the user is coming to the lunch, returning the updated lunch
+async fn lunch_rsvp(
+    access: LunchAccess,
+    vote_repo: Data<VoteRepository>,
+    lunch_repo: Data<LunchRepository>,
+    form: web::Form<LunchRsvpFormData>,
+    session: Session,
+) -> Result<HttpResponse, HtmxError> {
+    let signed_user = session.get::<SignedUser>("signed_user")?;
+    let user_id = access.user_id;
+    let lunch_id = access.lunch.id;
+
+    lunch_repo
+        .rsvp(&LunchRsvpCreate {
+            lunch_id,
+            user_id,
+            status: form.status.into(),
+        })
+        .await?;
+
+    render_lunch_menus(lunch_id, user_id, signed_user, &vote_repo, &lunch_repo).await
 }
 
 /// Gets user preview, does not persist anything - usable for creating a new group
@@ -401,6 +545,7 @@ async fn get_group_user(form: web::Query<UserAddInGroupForm>) -> Result<HttpResp
 
 /// Adds user to the group and persists change in the db
 async fn post_group_user(
+    access: GroupAccess<Admin>,
     form: web::Form<UserAddInGroupForm>,
     group_repo: Data<GroupRepository>,
 ) -> Result<HttpResponse, HtmxError> {
@@ -410,23 +555,28 @@ async fn post_group_user(
         Some(form.profile_picture.clone())
     };
 
-    let group_id = form.group_id.ok_or(HtmxError::BannerErrorDefault)?;
+    let group_id = access.group_id;
 
     // Add user to group
     group_repo
         .add_user_to_group(&GroupUserCreate {
             user_id: form.id,
             group_id,
+            added_by: access.user_id,
         })
         .await?;
 
     let template = UserGroup {
-        user_preview: UserPreviewView {
-            id: form.id,
-            username: form.0.username,
-            profile_picture,
+        member: GroupMemberView {
+            user_preview: UserPreviewView {
+                id: form.id,
+                username: form.0.username,
+                profile_picture,
+            },
+            role: GroupRole::Member,
         },
         group_id,
+        is_owner: access.role == GroupRole::Owner,
     };
 
     let body = template.render()?;
@@ -436,18 +586,12 @@ async fn post_group_user(
 
 /// Deletes user from the group
 async fn delete_group_user(
+    access: GroupAccess,
     form: web::Form<UserDeleteFromGroup>,
     group_repo: Data<GroupRepository>,
-    user: Identity,
 ) -> Result<HttpResponse, HtmxError> {
-    let group = group_repo
-        .read_one(&GroupGetById { id: form.group_id })
-        .await?;
-
-    let signed_user = Uuid::parse_str(user.id()?.as_ref())?;
-
-    // Check if signed user is the author of this group or user being removed
-    if group.author_id != signed_user && form.user_id != signed_user {
+    // Members remove only themselves, others are removed by admins
+    if form.user_id != access.user_id && access.role < GroupRole::Admin {
         return Err(HtmxError::BannerError(
             "Tento uživatel nemůže odstranit uživatele ze skupiny.".to_string(),
         ));
@@ -457,7 +601,7 @@ async fn delete_group_user(
     group_repo
         .remove_user_from_group(&GroupUserDelete {
             user_id: form.user_id,
-            group_id: form.group_id,
+            group_id: access.group_id,
         })
         .await?;
 
@@ -465,23 +609,14 @@ async fn delete_group_user(
 }
 
 async fn user_leave(
-    form: web::Form<UserDeleteFromGroup>,
+    access: GroupAccess,
     group_repo: Data<GroupRepository>,
-    user: Identity,
 ) -> Result<HttpResponse, HtmxError> {
-    let signed_user = Uuid::parse_str(user.id()?.as_ref())?;
-
-    // Check that signed user si the one leaving group
-    if form.user_id != signed_user {
-        return Err(HtmxError::BannerError(
-            "Tento uživatel nemůže odstranit uživatele ze skupiny.".to_string(),
-        ));
-    }
-
+    // Signed user is the one leaving the group
     group_repo
         .remove_user_from_group(&GroupUserDelete {
-            user_id: form.user_id,
-            group_id: form.group_id,
+            user_id: access.user_id,
+            group_id: access.group_id,
         })
         .await?;
 
@@ -492,29 +627,54 @@ async fn user_leave(
 
 /// Deletes user from the group
 async fn delete_group(
+    access: GroupAccess<Owner>,
     group_repo: Data<GroupRepository>,
-    user: Identity,
-    id: web::Path<Uuid>,
 ) -> Result<HttpResponse, HtmxError> {
-    let group = group_repo
-        .read_one(&GroupGetById {
-            id: id.into_inner(),
+    // Remove group
+    group_repo
+        .delete(&GroupDelete {
+            id: access.group_id,
         })
         .await?;
 
-    let signed_user = Uuid::parse_str(user.id()?.as_ref())?;
+    Ok(HttpResponse::Ok()
+        .append_header(("HX-Redirect", "/groups"))
+        .finish())
+}
 
-    // Check if signed user is the author of this group
-    if group.author_id != signed_user {
-        return Err(HtmxError::BannerError(
-            "Tento uživatel nemůže odstranit tuto skupinu.".to_string(),
-        ));
-    }
+/// Changes the role of a member, only for the owner of the group
+async fn put_group_user_role(
+    access: GroupAccess<Owner>,
+    form: web::Form<GroupUserRoleFormData>,
+    group_repo: Data<GroupRepository>,
+) -> Result<HttpResponse, HtmxError> {
+    group_repo
+        .set_user_role(&GroupUserRoleUpdate {
+            user_id: form.user_id,
+            group_id: access.group_id,
+            role: form.role.into(),
+        })
+        .await?;
 
-    // Remove group
-    group_repo.delete(&GroupDelete { id: group.id }).await?;
+    Ok(HttpResponse::Ok()
+        .append_header(("HX-Redirect", format!("/group-edit/{}", access.group_id)))
+        .finish())
+}
+
+/// Transfers the ownership of the group to a member, the signed user stays as an admin
+async fn put_group_owner(
+    access: GroupAccess<Owner>,
+    form: web::Form<GroupOwnerFormData>,
+    group_repo: Data<GroupRepository>,
+) -> Result<HttpResponse, HtmxError> {
+    group_repo
+        .transfer_ownership(&GroupOwnershipTransfer {
+            group_id: access.group_id,
+            user_id: form.user_id,
+        })
+        .await?;
 
     Ok(HttpResponse::Ok()
-        .append_header(("HX-Redirect", "/groups"))
+        .append_header(("HX-Redirect", format!("/group-edit/{}", access.group_id)))
         .finish())
 }
@@ -0,0 +1,231 @@
+use crate::app::authorization::{Admin, GroupAccess};
+use crate::app::csrf::CsrfToken;
+use crate::app::errors::{ApiError, HtmxError};
+use crate::app::forms::group_invitation::{
+    GroupInvitationAnswerFormData, GroupJoinLinkCreateFormData,
+};
+use crate::app::forms::user_add_in_group::UserAddInGroupForm;
+use crate::app::public_url::PublicUrl;
+use crate::app::templates::group::{
+    GroupInvitationSentTemplate, GroupJoinLinkListTemplate, GroupJoinTemplate,
+};
+use crate::app::utils::api_token::generate_token_secret;
+use crate::app::utils::validation::Validation;
+use crate::app::view_models::group_invitation::GroupJoinLinkView;
+use crate::db::common::DbCreate;
+use crate::db::models::{
+    GroupInvitationAnswer, GroupInvitationCreate, GroupJoinLinkCreate, GroupJoinLinkDelete,
+    GroupJoinLinkGetByToken, GroupJoinLinkGetMany, GroupJoinLinkUse,
+};
+use crate::db::repositories::{
+    GroupInvitationRepository, GroupInvitationRepositoryAnswer, GroupInvitationRepositoryJoinLink,
+};
+use actix_identity::Identity;
+use actix_web::web::Data;
+use actix_web::{web, HttpResponse};
+use askama::Template;
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+pub fn group_invitation_config(config: &mut web::ServiceConfig) {
+    config
+        .service(web::resource("/group-invite/{id}").route(web::post().to(post_invitation)))
+        .service(web::resource("/group-invitations/{id}").route(web::put().to(put_invitation)))
+        .service(
+            web::resource("/group-join-links/{id}")
+                .route(web::get().to(get_join_links))
+                .route(web::post().to(post_join_link))
+                // Id of the link, the repository checks the admin of its group
+                .route(web::delete().to(delete_join_link)),
+        )
+        .service(
+            web::resource("/join/{token}")
+                .route(web::get().to(get_join_group))
+                .route(web::post().to(post_join_group)),
+        );
+}
+
+/// Absolute URL of a join link, it is shared outside of the app
+fn join_link_url(public_url: &PublicUrl, token: &str) -> String {
+    public_url.url(&format!("/join/{token}"))
+}
+
+/// Invites a user found on the group edit page, the user joins after accepting it
+async fn post_invitation(
+    access: GroupAccess<Admin>,
+    form: web::Form<UserAddInGroupForm>,
+    invitation_repo: Data<GroupInvitationRepository>,
+) -> Result<HttpResponse, HtmxError> {
+    invitation_repo
+        .create(&GroupInvitationCreate {
+            group_id: access.group_id,
+            user_id: form.id,
+            invited_by: access.user_id,
+        })
+        .await?;
+
+    let template = GroupInvitationSentTemplate {};
+    let body = template.render()?;
+
+    Ok(HttpResponse::Ok().content_type("text/html").body(body))
+}
+
+/// Accepts or declines an invitation of the signed user
+async fn put_invitation(
+    id: web::Path<Uuid>,
+    form: web::Form<GroupInvitationAnswerFormData>,
+    invitation_repo: Data<GroupInvitationRepository>,
+    user: Identity,
+) -> Result<HttpResponse, HtmxError> {
+    let invitation = invitation_repo
+        .answer(&GroupInvitationAnswer {
+            id: id.into_inner(),
+            user_id: Uuid::parse_str(user.id()?.as_ref())?,
+            accept: form.accept,
+        })
+        .await?;
+
+    // Declined invitation just disappears from the list
+    if !form.accept {
+        return Ok(HttpResponse::Ok().finish());
+    }
+
+    Ok(HttpResponse::Ok()
+        .append_header(("HX-Redirect", format!("/groups/{}", invitation.group_id)))
+        .finish())
+}
+
+/// Lists unexpired join links of a group, only for its admins
+async fn get_join_links(
+    public_url: Data<PublicUrl>,
+    access: GroupAccess<Admin>,
+    invitation_repo: Data<GroupInvitationRepository>,
+) -> Result<HttpResponse, HtmxError> {
+    render_join_links(
+        &public_url,
+        access.group_id,
+        access.user_id,
+        &invitation_repo,
+    )
+    .await
+}
+
+/// Creates a join link of a group valid for the given number of days
+async fn post_join_link(
+    public_url: Data<PublicUrl>,
+    access: GroupAccess<Admin>,
+    form: web::Form<GroupJoinLinkCreateFormData>,
+    invitation_repo: Data<GroupInvitationRepository>,
+) -> Result<HttpResponse, HtmxError> {
+    form.validate()?;
+
+    let GroupAccess {
+        group_id, user_id, ..
+    } = access;
+
+    invitation_repo
+        .create_join_link(&GroupJoinLinkCreate {
+            group_id,
+            created_by: user_id,
+            token: generate_token_secret(),
+            expires_at: Utc::now() + Duration::days(form.valid_days),
+        })
+        .await?;
+
+    render_join_links(&public_url, group_id, user_id, &invitation_repo).await
+}
+
+/// Revokes a join link, returning the updated list of the links of the group
+async fn delete_join_link(
+    public_url: Data<PublicUrl>,
+    id: web::Path<Uuid>,
+    invitation_repo: Data<GroupInvitationRepository>,
+    user: Identity,
+) -> Result<HttpResponse, HtmxError> {
+    let user_id = Uuid::parse_str(user.id()?.as_ref())?;
+
+    let link = invitation_repo
+        .revoke_join_link(&GroupJoinLinkDelete {
+            id: id.into_inner(),
+            user_id,
+        })
+        .await?;
+
+    render_join_links(&public_url, link.group_id, user_id, &invitation_repo).await
+}
+
+/// Renders join links of a group for its admin
+async fn render_join_links(
+    public_url: &PublicUrl,
+    group_id: Uuid,
+    user_id: Uuid,
+    invitation_repo: &GroupInvitationRepository,
+) -> Result<HttpResponse, HtmxError> {
+    let links = invitation_repo
+        .list_join_links(&GroupJoinLinkGetMany { group_id, user_id })
+        .await?;
+
+    let template = GroupJoinLinkListTemplate {
+        links: links
+            .into_iter()
+            .map(|link| {
+                let url = join_link_url(public_url, &link.token);
+                GroupJoinLinkView::new(link, url)
+            })
+            .collect(),
+    };
+
+    let body = template.render()?;
+
+    Ok(HttpResponse::Ok().content_type("text/html").body(body))
+}
+
+/// Shows the group of the link, the signed user joins it by confirming the page
+async fn get_join_group(
+    token: web::Path<String>,
+    invitation_repo: Data<GroupInvitationRepository>,
+    user: Option<Identity>,
+    csrf: CsrfToken,
+) -> Result<HttpResponse, ApiError> {
+    let token = token.into_inner();
+
+    if user.is_none() {
+        // The link is opened again after signing in
+        return Ok(HttpResponse::Found()
+            .append_header(("Location", format!("/login?next=/join/{token}")))
+            .finish());
+    }
+
+    let group = invitation_repo
+        .join_link_group(&GroupJoinLinkGetByToken {
+            token: token.clone(),
+        })
+        .await?;
+
+    let template = GroupJoinTemplate {
+        group,
+        token,
+        csrf_token: csrf.into_inner(),
+    };
+    let body = template.render()?;
+
+    Ok(HttpResponse::Ok().content_type("text/html").body(body))
+}
+
+/// Joins the signed user to the group of the link and shows the group
+async fn post_join_group(
+    token: web::Path<String>,
+    invitation_repo: Data<GroupInvitationRepository>,
+    user: Identity,
+) -> Result<HttpResponse, HtmxError> {
+    let group = invitation_repo
+        .join(&GroupJoinLinkUse {
+            token: token.into_inner(),
+            user_id: Uuid::parse_str(user.id()?.as_ref())?,
+        })
+        .await?;
+
+    Ok(HttpResponse::Ok()
+        .append_header(("HX-Redirect", format!("/groups/{}", group.id)))
+        .finish())
+}
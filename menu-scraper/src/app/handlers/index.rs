@@ -1,28 +1,36 @@
+use crate::app::csrf::CsrfToken;
 use crate::app::errors::ApiError;
 use crate::app::templates::index::IndexTemplate;
 use crate::app::utils::date::format_date_with_day_of_week;
 use crate::app::view_models::menu::MenuWithRestaurantView;
 use crate::app::view_models::signed_user::SignedUser;
+use crate::db::common::DbReadMany;
+use crate::db::models::{DbRestaurantOrderingMethod, MenuReadMany};
+use crate::db::repositories::MenuRepository;
 use actix_session::Session;
 use actix_web::web::Data;
 use actix_web::{web, HttpResponse};
 use askama::Template;
 use chrono::Local;
-use db::db::common::DbReadMany;
-use db::db::models::{DbRestaurantOrderingMethod, MenuReadMany};
-use db::db::repositories::MenuRepository;
 
 pub fn index_config(config: &mut web::ServiceConfig) {
     config.service(web::resource("/").route(web::get().to(index)));
 }
 
-async fn index(repo: Data<MenuRepository>, session: Session) -> Result<HttpResponse, ApiError> {
+async fn index(
+    repo: Data<MenuRepository>,
+    session: Session,
+    csrf: CsrfToken,
+) -> Result<HttpResponse, ApiError> {
     let menus = repo
         .read_many(&MenuReadMany {
             date_from: Local::now().date_naive(),
             date_to: Local::now().date_naive(),
             order_by: DbRestaurantOrderingMethod::Random, // Use random ordering for the main page
             restaurant_id: None,
+            excluded_allergens: vec![],
+            vegetarian_only: false,
+            search: None,
             limit: Some(3),
             offset: None,
         })
@@ -43,6 +51,7 @@ async fn index(repo: Data<MenuRepository>, session: Session) -> Result<HttpRespo
             format_date_with_day_of_week(Local::now().date_naive())
         ),
         signed_user,
+        csrf_token: csrf.into_inner(),
     };
     let body = template.render()?;
 
@@ -1,14 +1,15 @@
+use crate::app::authorization::{Admin, LunchAccess};
 use crate::app::errors::HtmxError;
 use crate::app::forms::lunch::GetLunchPreviewsQuery;
 use crate::app::templates::lunch::LunchPreviewListTemplate;
 use crate::app::view_models::lunch::LunchPreviewView;
+use crate::db::common::{DbDelete, DbReadMany};
+use crate::db::models::{LunchDelete, LunchGetMany};
+use crate::db::repositories::LunchRepository;
 use actix_identity::Identity;
 use actix_web::web::Data;
 use actix_web::{web, HttpResponse};
 use askama::Template;
-use db::db::common::{DbDelete, DbReadMany, DbReadOne};
-use db::db::models::{GetGroupUserByIds, LunchDelete, LunchGetById, LunchGetMany};
-use db::db::repositories::{GroupRepository, GroupRepositoryCheckUser, LunchRepository};
 use uuid::Uuid;
 
 pub fn lunch_config(config: &mut web::ServiceConfig) {
@@ -46,32 +47,16 @@ async fn get_user_lunches(
     Ok(HttpResponse::Ok().content_type("text/html").body(body))
 }
 
+/// Deletes a lunch, only for admins of the group
 async fn delete_lunch(
+    access: LunchAccess<Admin>,
     lunch_repo: Data<LunchRepository>,
-    group_repo: Data<GroupRepository>,
-    lunch_id: web::Path<Uuid>,
-    identity: Identity,
 ) -> Result<HttpResponse, HtmxError> {
-    let user_id = Uuid::parse_str(identity.id()?.as_ref())?;
-    let lunch = lunch_repo
-        .read_one(&LunchGetById {
-            id: lunch_id.into_inner(),
+    lunch_repo
+        .delete(&LunchDelete {
+            id: access.lunch.id,
         })
         .await?;
 
-    group_repo
-        .check_user_is_member(&GetGroupUserByIds {
-            user_id,
-            group_id: lunch.group_id,
-        })
-        .await
-        .map_err(|_| {
-            HtmxError::BannerError(
-                "Tento uživatel nemůže odstranit oběd, protože není ve skupině.".to_string(),
-            )
-        })?;
-
-    lunch_repo.delete(&LunchDelete { id: lunch.id }).await?;
-
     Ok(HttpResponse::Ok().finish())
 }
@@ -1,33 +1,36 @@
+use crate::app::csrf::CsrfToken;
 use crate::app::errors::{ApiError, HtmxError};
-use crate::app::forms::menu::MenuListQuery;
+use crate::app::forms::menu::{MenuListQuery, MenuSearchQuery};
 use crate::app::forms::ordering::{Ordering, RestaurantOrderingMethod};
 use crate::app::templates::menu::{MenuIndexTemplate, MenuListTemplate};
 use crate::app::view_models::menu::MenuWithRestaurantView;
 use crate::app::view_models::signed_user::SignedUser;
+use crate::db::common::query_parameters::{DbOrder, DbSearchQuery};
+use crate::db::common::DbReadMany;
+use crate::db::models::{DbRestaurantOrderingMethod, MenuGetCount, MenuReadMany};
+use crate::db::repositories::{GetNumberOfMenus, MenuRepository};
 use actix_session::Session;
 use actix_web::web::Data;
 use actix_web::{web, HttpResponse};
 use askama::Template;
 use chrono::Local;
-use db::db::common::query_parameters::DbOrder;
-use db::db::common::DbReadMany;
-use db::db::models::{DbRestaurantOrderingMethod, MenuGetCount, MenuReadMany};
-use db::db::repositories::{GetNumberOfMenus, MenuRepository};
 
 const PAGE_SIZE: usize = 10;
 
 pub fn menu_config(config: &mut web::ServiceConfig) {
     config
         .service(web::resource("/menus").route(web::get().to(menu_index)))
-        .service(web::resource("/menu-list").route(web::get().to(get_menu_list)));
+        .service(web::resource("/menu-list").route(web::get().to(get_menu_list)))
+        .service(web::resource("/menu-search").route(web::get().to(search_menus)));
 }
 
-async fn menu_index(session: Session) -> Result<HttpResponse, ApiError> {
+async fn menu_index(session: Session, csrf: CsrfToken) -> Result<HttpResponse, ApiError> {
     let signed_user = session.get::<SignedUser>("signed_user")?;
 
     let template = MenuIndexTemplate {
         date: Local::now().date_naive(),
         signed_user,
+        csrf_token: csrf.into_inner(),
     };
     let body = template.render()?;
 
@@ -40,15 +43,79 @@ async fn get_menu_list(
     repo: Data<MenuRepository>,
     session: Session,
 ) -> Result<HttpResponse, HtmxError> {
-    let order = match query.ordering {
+    let excluded_allergens = query.excluded_allergens().ok_or(HtmxError::BannerError(
+        "Neplatný výběr alergenů.".to_string(),
+    ))?;
+
+    let params = MenuReadMany {
+        date_from: query.date,
+        date_to: query.date,
+        order_by: ordering_method(
+            &query.method,
+            &query.ordering,
+            query.longitude,
+            query.latitude,
+        )?,
+        restaurant_id: None,
+        excluded_allergens,
+        vegetarian_only: query.vegetarian,
+        search: query.search.as_deref().and_then(DbSearchQuery::new),
+        limit: Some(PAGE_SIZE as i64),
+        offset: Some((PAGE_SIZE * (query.page - 1)) as i64),
+    };
+
+    render_menu_list(&repo, &session, params).await
+}
+
+/// Search dishes in menus for a range of dates, matching dishes are highlighted
+async fn search_menus(
+    query: web::Query<MenuSearchQuery>,
+    repo: Data<MenuRepository>,
+    session: Session,
+) -> Result<HttpResponse, HtmxError> {
+    let search = DbSearchQuery::new(&query.query)
+        .ok_or(HtmxError::BannerError("Zadejte hledané jídlo.".to_string()))?;
+
+    if query.date_from > query.date_to {
+        return Err(HtmxError::BannerError("Neplatný rozsah dat.".to_string()));
+    }
+
+    let params = MenuReadMany {
+        date_from: query.date_from,
+        date_to: query.date_to,
+        order_by: ordering_method(
+            &query.method,
+            &query.ordering,
+            query.longitude,
+            query.latitude,
+        )?,
+        restaurant_id: None,
+        excluded_allergens: vec![],
+        vegetarian_only: false,
+        search: Some(search),
+        limit: Some(PAGE_SIZE as i64),
+        offset: Some((PAGE_SIZE * (query.page - 1)) as i64),
+    };
+
+    render_menu_list(&repo, &session, params).await
+}
+
+/// Converts ordering from the query, ordering by range needs the location of the user
+fn ordering_method(
+    method: &RestaurantOrderingMethod,
+    ordering: &Ordering,
+    longitude: Option<f64>,
+    latitude: Option<f64>,
+) -> Result<DbRestaurantOrderingMethod, HtmxError> {
+    let order = match ordering {
         Ordering::Asc => DbOrder::Asc,
         Ordering::Desc => DbOrder::Desc,
     };
 
-    let method = match query.method {
-        RestaurantOrderingMethod::Price => DbRestaurantOrderingMethod::Price(order),
+    match method {
+        RestaurantOrderingMethod::Price => Ok(DbRestaurantOrderingMethod::Price(order)),
         RestaurantOrderingMethod::Range => {
-            if let (Some(longitude), Some(latitude)) = (query.longitude, query.latitude) {
+            if let (Some(longitude), Some(latitude)) = (longitude, latitude) {
                 Ok(DbRestaurantOrderingMethod::Range(
                     order,
                     (longitude, latitude),
@@ -56,26 +123,28 @@ async fn get_menu_list(
             } else {
                 Err(HtmxError::BannerErrorDefault)
             }
-        }?,
-    };
+        }
+    }
+}
 
+/// Renders one page of menus with the number of pages
+async fn render_menu_list(
+    repo: &MenuRepository,
+    session: &Session,
+    params: MenuReadMany,
+) -> Result<HttpResponse, HtmxError> {
     let menu_count = repo
         .get_number_of_menus(&MenuGetCount {
-            date_from: query.date,
-            date_to: query.date,
+            date_from: params.date_from,
+            date_to: params.date_to,
+            restaurant_id: params.restaurant_id,
+            excluded_allergens: params.excluded_allergens.clone(),
+            vegetarian_only: params.vegetarian_only,
+            search: params.search.clone(),
         })
         .await?;
 
-    let menus = repo
-        .read_many(&MenuReadMany {
-            date_from: query.date,
-            date_to: query.date,
-            order_by: method,
-            restaurant_id: None,
-            limit: Some(PAGE_SIZE as i64),
-            offset: Some((PAGE_SIZE * (query.page - 1)) as i64),
-        })
-        .await?;
+    let menus = repo.read_many(&params).await?;
 
     // Convert menus to view models
     let menus_view: Vec<MenuWithRestaurantView> = menus
@@ -1,10 +1,14 @@
+pub mod api_token;
 pub mod auth;
+pub mod calendar;
 pub mod index;
 pub mod registration;
 pub mod user;
 
 pub mod group;
+pub mod group_invitation;
 pub mod lunch;
 pub mod menu;
 pub mod restaurant;
+pub mod scrape_run;
 pub mod vote;
@@ -1,3 +1,4 @@
+use crate::app::csrf::CsrfToken;
 use crate::app::errors::ApiError;
 use crate::app::templates::registration::RegistrationTemplate;
 use actix_identity::Identity;
@@ -9,7 +10,10 @@ pub fn registration_config(config: &mut web::ServiceConfig) {
 }
 
 /// Gets empty registration form
-async fn get_registration(user: Option<Identity>) -> Result<HttpResponse, ApiError> {
+async fn get_registration(
+    user: Option<Identity>,
+    csrf: CsrfToken,
+) -> Result<HttpResponse, ApiError> {
     if user.is_some() {
         // Already signed in, redirect to main page
         return Ok(HttpResponse::Found()
@@ -17,7 +21,9 @@ async fn get_registration(user: Option<Identity>) -> Result<HttpResponse, ApiErr
             .finish());
     }
 
-    let template = RegistrationTemplate {};
+    let template = RegistrationTemplate {
+        csrf_token: csrf.into_inner(),
+    };
 
     let body = template.render()?;
 
@@ -1,17 +1,18 @@
+use crate::app::csrf::CsrfToken;
 use crate::app::errors::ApiError;
 use crate::app::templates::restaurant::RestaurantTemplate;
 use crate::app::view_models::menu::MenuView;
 use crate::app::view_models::restaurant::RestaurantView;
 use crate::app::view_models::signed_user::SignedUser;
+use crate::db::common::query_parameters::DbOrder;
+use crate::db::common::{DbReadMany, DbReadOne};
+use crate::db::models::{DbRestaurantOrderingMethod, MenuReadMany, RestaurantGetById};
+use crate::db::repositories::{MenuRepository, RestaurantRepository};
 use actix_session::Session;
 use actix_web::web::Data;
 use actix_web::{web, HttpResponse};
 use askama::Template;
 use chrono::{Duration, Local};
-use db::db::common::query_parameters::DbOrder;
-use db::db::common::{DbReadMany, DbReadOne};
-use db::db::models::{DbRestaurantOrderingMethod, MenuReadMany, RestaurantGetById};
-use db::db::repositories::{MenuRepository, RestaurantRepository};
 use uuid::Uuid;
 
 pub fn restaurant_config(config: &mut web::ServiceConfig) {
@@ -24,6 +25,7 @@ async fn get_restaurant(
     menu_repo: Data<MenuRepository>,
     restaurant_repo: Data<RestaurantRepository>,
     session: Session,
+    csrf: CsrfToken,
 ) -> Result<HttpResponse, ApiError> {
     let restaurant_id = id.into_inner();
 
@@ -40,6 +42,9 @@ async fn get_restaurant(
             date_to: (Local::now() + Duration::days(6)).date_naive(),
             order_by: DbRestaurantOrderingMethod::Date(DbOrder::Asc),
             restaurant_id: Some(restaurant_id),
+            excluded_allergens: vec![],
+            vegetarian_only: false,
+            search: None,
             limit: Some(7),
             offset: None,
         })
@@ -49,6 +54,7 @@ async fn get_restaurant(
         restaurant: RestaurantView::from(restaurant),
         signed_user,
         menus: menus.into_iter().map(MenuView::from).collect(),
+        csrf_token: csrf.into_inner(),
     };
 
     let body = template.render()?;
@@ -0,0 +1,115 @@
+use crate::app::csrf::CsrfToken;
+use crate::app::errors::ApiError;
+use crate::app::templates::scrape_run::ScrapeStatusTemplate;
+use crate::app::utils::secret::constant_time_eq;
+use crate::app::view_models::scrape_run::ScrapeRunView;
+use crate::app::view_models::signed_user::SignedUser;
+use crate::db::common::DbReadMany;
+use crate::db::models::ScrapeRunGetMany;
+use crate::db::repositories::ScrapeRunRepository;
+use crate::scrapping::service::scraping_service::Scraper;
+use actix_identity::Identity;
+use actix_session::Session;
+use actix_web::http::header;
+use actix_web::web::Data;
+use actix_web::{web, HttpRequest, HttpResponse};
+use askama::Template;
+use log::warn;
+use std::env;
+
+/// Number of scrape runs shown in the history
+const SCRAPE_RUN_HISTORY_LIMIT: i64 = 20;
+
+pub fn scrape_run_config(config: &mut web::ServiceConfig) {
+    config
+        .service(web::resource("/scraping-status").route(web::get().to(scraping_status)))
+        .service(web::resource("/scraping/run").route(web::post().to(start_scraping)))
+        .service(web::resource("/scraping/progress").route(web::get().to(scraping_progress)));
+}
+
+/// Checks the `Authorization: Bearer <token>` header against the SCRAPE_TRIGGER_TOKEN
+/// environment variable, triggering is disabled when the variable is not set
+fn check_trigger_token(request: &HttpRequest) -> Result<(), ApiError> {
+    let Ok(expected) = env::var("SCRAPE_TRIGGER_TOKEN") else {
+        warn!("scraping trigger called, but SCRAPE_TRIGGER_TOKEN is not set");
+        return Err(ApiError::Unauthorized);
+    };
+
+    let token = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .ok_or(ApiError::Unauthorized)?;
+
+    if expected.is_empty() || !constant_time_eq(token.as_bytes(), expected.as_bytes()) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    Ok(())
+}
+
+/// Starts scraping immediately, responds with the progress of the scrape
+async fn start_scraping(
+    request: HttpRequest,
+    scraper: Data<Scraper>,
+) -> Result<HttpResponse, ApiError> {
+    check_trigger_token(&request)?;
+
+    if !scraper.spawn() {
+        return Ok(HttpResponse::Conflict().json(scraper.progress()));
+    }
+
+    Ok(HttpResponse::Accepted()
+        .insert_header((header::LOCATION, "/scraping/progress"))
+        .json(scraper.progress()))
+}
+
+/// Progress of the running or the last finished scrape
+async fn scraping_progress(
+    request: HttpRequest,
+    scraper: Data<Scraper>,
+) -> Result<HttpResponse, ApiError> {
+    check_trigger_token(&request)?;
+
+    Ok(HttpResponse::Ok().json(scraper.progress()))
+}
+
+/// Shows when the menus were last refreshed and which restaurants failed to parse
+async fn scraping_status(
+    _user: Identity,
+    scrape_run_repo: Data<ScrapeRunRepository>,
+    scraper: Data<Scraper>,
+    session: Session,
+    csrf: CsrfToken,
+) -> Result<HttpResponse, ApiError> {
+    let signed_user = session.get::<SignedUser>("signed_user")?;
+
+    let mut history: Vec<ScrapeRunView> = scrape_run_repo
+        .read_many(&ScrapeRunGetMany {
+            limit: SCRAPE_RUN_HISTORY_LIMIT,
+        })
+        .await?
+        .into_iter()
+        .map(ScrapeRunView::from)
+        .collect();
+
+    // Runs are ordered from the newest
+    let last_run = if history.is_empty() {
+        None
+    } else {
+        Some(history.remove(0))
+    };
+
+    let template = ScrapeStatusTemplate {
+        signed_user,
+        progress: scraper.progress(),
+        last_run,
+        history,
+        csrf_token: csrf.into_inner(),
+    };
+
+    let body = template.render()?;
+
+    Ok(HttpResponse::Ok().content_type("text/html").body(body))
+}
@@ -1,26 +1,34 @@
+use crate::app::csrf::CsrfToken;
 use crate::app::errors::{ApiError, HtmxError};
 use crate::app::forms::registration::RegistrationFormData;
 use crate::app::forms::user_edit::UserEditFormData;
 use crate::app::forms::user_search::UserSearchQuery;
+use crate::app::handlers::calendar::calendar_feed_url;
+use crate::app::public_url::PublicUrl;
 use crate::app::templates::user_edit::UserEditTemplate;
 use crate::app::templates::user_preview_list::UserPreviewList;
 use crate::app::utils::password::{hash_password, verify_password};
 use crate::app::utils::picture::validate_and_save_picture;
 use crate::app::utils::validation::Validation;
+use crate::app::view_models::api_token::ApiTokenView;
 use crate::app::view_models::signed_user::SignedUser;
 use crate::app::view_models::user_edit::UserEdit;
 use crate::app::view_models::user_preview::UserPreviewView;
+use crate::db::common::{DbCreate, DbReadMany, DbReadOne, DbUpdate};
+use crate::db::models::{
+    ApiTokenGetByUser, CalendarFeedGetByUser, CheckEmailAndUsername, UserCreate, UserGetById,
+    UserGetByUsername, UserUpdate,
+};
+use crate::db::repositories::{
+    ApiTokenRepository, CalendarFeedRepository, CalendarFeedRepositoryGetByUser,
+    UserCheckEmailAndPassword, UserRepository,
+};
 use actix_identity::Identity;
 use actix_multipart::form::MultipartForm;
 use actix_session::Session;
 use actix_web::web::Data;
 use actix_web::{web, HttpMessage, HttpRequest, HttpResponse};
 use askama::Template;
-use db::db::common::{DbCreate, DbReadMany, DbReadOne, DbUpdate};
-use db::db::models::{
-    CheckEmailAndUsername, UserCreate, UserGetById, UserGetByUsername, UserUpdate,
-};
-use db::db::repositories::{UserCheckEmailAndPassword, UserRepository};
 use uuid::Uuid;
 
 pub fn user_config(config: &mut web::ServiceConfig) {
@@ -36,8 +44,12 @@ pub fn user_config(config: &mut web::ServiceConfig) {
 
 /// Get form for editing user's details
 async fn get_user_edit_form(
+    public_url: Data<PublicUrl>,
     user: Identity,
     user_repo: Data<UserRepository>,
+    token_repo: Data<ApiTokenRepository>,
+    feed_repo: Data<CalendarFeedRepository>,
+    csrf: CsrfToken,
 ) -> Result<HttpResponse, ApiError> {
     let user = user_repo
         .read_one(&UserGetById {
@@ -45,6 +57,14 @@ async fn get_user_edit_form(
         })
         .await?;
 
+    let tokens = token_repo
+        .read_many(&ApiTokenGetByUser { user_id: user.id })
+        .await?;
+
+    let feed = feed_repo
+        .get_by_user(&CalendarFeedGetByUser { user_id: user.id })
+        .await?;
+
     // Fill form with signed in user's data
     let template = UserEditTemplate {
         user: UserEdit {
@@ -53,6 +73,10 @@ async fn get_user_edit_form(
             email: user.email,
             profile_picture: user.profile_picture,
         },
+        tokens: tokens.into_iter().map(ApiTokenView::from).collect(),
+        created_token: None,
+        feed_url: feed.map(|feed| calendar_feed_url(&public_url, &feed.token)),
+        csrf_token: csrf.into_inner(),
     };
     let body = template.render()?;
 
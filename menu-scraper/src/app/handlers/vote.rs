@@ -1,40 +1,78 @@
+use crate::app::authorization::LunchAccess;
 use crate::app::errors::HtmxError;
-use crate::app::forms::vote::AddVoteFormData;
-use actix_identity::Identity;
+use crate::app::forms::vote::{AddVoteFormData, VetoFormData};
+use crate::db::common::DbCreate;
+use crate::db::models::{LunchCandidateCreate, VetoCreate, VoteCreate};
+use crate::db::repositories::{
+    LunchRepository, LunchRepositoryNominate, VoteRepository, VoteRepositoryVeto,
+};
 use actix_web::web::Data;
 use actix_web::{web, HttpResponse};
-use db::db::common::{DbCreate, DbReadOne};
-use db::db::models::{LunchGetById, VoteCreate};
-use db::db::repositories::{LunchRepository, VoteRepository};
-use uuid::Uuid;
 
 pub fn vote_config(config: &mut web::ServiceConfig) {
-    config.service(web::resource("/vote").route(web::post().to(post_vote)));
+    config
+        .service(web::resource("/vote/{id}").route(web::post().to(post_vote)))
+        .service(web::resource("/veto/{id}").route(web::post().to(post_veto)))
+        .service(web::resource("/nominate/{id}").route(web::post().to(post_nominate)));
 }
 
 /// Add vote in some lunch
 async fn post_vote(
+    access: LunchAccess,
     form: web::Form<AddVoteFormData>,
     vote_repo: Data<VoteRepository>,
-    lunch_repo: Data<LunchRepository>,
-    user: Identity,
 ) -> Result<HttpResponse, HtmxError> {
-    let id = Uuid::parse_str(user.id()?.as_ref())?;
+    vote_repo
+        .create(&VoteCreate {
+            menu_id: form.menu_id,
+            user_id: access.user_id,
+            lunch_id: access.lunch.id,
+        })
+        .await?;
 
-    let lunch = lunch_repo.read_one(&LunchGetById{
-        id: form.lunch_id,
-    }).await?;
+    // Redirect to the lunch
+    Ok(HttpResponse::Ok()
+        .append_header(("HX-Redirect", format!("/groups/{}", access.lunch.group_id)))
+        .finish())
+}
 
+/// Veto a menu in some lunch
+async fn post_veto(
+    access: LunchAccess,
+    form: web::Form<VetoFormData>,
+    vote_repo: Data<VoteRepository>,
+) -> Result<HttpResponse, HtmxError> {
     vote_repo
-        .create(&VoteCreate {
+        .veto(&VetoCreate {
+            menu_id: form.menu_id,
+            user_id: access.user_id,
+            lunch_id: access.lunch.id,
+            restaurant: form.restaurant,
+        })
+        .await?;
+
+    // Redirect to the lunch
+    Ok(HttpResponse::Ok()
+        .append_header(("HX-Redirect", format!("/groups/{}", access.lunch.group_id)))
+        .finish())
+}
+
+/// Nominate a menu to some lunch without voting for it
+async fn post_nominate(
+    access: LunchAccess,
+    form: web::Form<AddVoteFormData>,
+    lunch_repo: Data<LunchRepository>,
+) -> Result<HttpResponse, HtmxError> {
+    lunch_repo
+        .nominate(&LunchCandidateCreate {
+            lunch_id: access.lunch.id,
             menu_id: form.menu_id,
-            user_id: id,
-            lunch_id: form.lunch_id,
+            user_id: access.user_id,
         })
         .await?;
 
     // Redirect to the lunch
     Ok(HttpResponse::Ok()
-        .append_header(("HX-Redirect", format!("/groups/{}", lunch.group_id)))
+        .append_header(("HX-Redirect", format!("/groups/{}", access.lunch.group_id)))
         .finish())
 }
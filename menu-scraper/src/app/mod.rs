@@ -1,6 +1,10 @@
+pub mod api;
+mod authorization;
+pub mod csrf;
 mod errors;
 mod forms;
 pub mod handlers;
+pub mod public_url;
 mod templates;
 mod utils;
 mod view_models;
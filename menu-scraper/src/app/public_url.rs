@@ -0,0 +1,35 @@
+use anyhow::{bail, Context};
+use std::env;
+
+/// Public base URL of the app, links opened outside of it (calendar feeds, join links) are
+/// absolute. It is configured, since the Host header of a request is chosen by its sender.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicUrl {
+    base: String,
+}
+
+impl PublicUrl {
+    /// Scheme and host the app is reached at, e.g. `https://obedy.example.com`
+    pub fn new(base: &str) -> anyhow::Result<Self> {
+        let base = base.trim().trim_end_matches('/');
+
+        if !(base.starts_with("http://") || base.starts_with("https://")) {
+            bail!("public base url has to start with http:// or https://");
+        }
+
+        Ok(Self {
+            base: base.to_string(),
+        })
+    }
+
+    /// Reads the base URL from PUBLIC_BASE_URL
+    pub fn from_env() -> anyhow::Result<Self> {
+        let base = env::var("PUBLIC_BASE_URL").context("PUBLIC_BASE_URL must be set")?;
+        Self::new(&base).context("invalid value of PUBLIC_BASE_URL")
+    }
+
+    /// Absolute URL of a path of the app
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{path}", self.base)
+    }
+}
@@ -1,27 +1,54 @@
 use crate::app::view_models::group::GroupView;
-use crate::app::view_models::lunch::MenuWithRestaurantAndVotesView;
+use crate::app::view_models::group_invitation::{GroupInvitationView, GroupJoinLinkView};
+use crate::app::view_models::lunch::{
+    LunchAttendanceView, LunchVetoesView, LunchVotingView, MenuWithRestaurantAndVotesView,
+};
 use crate::app::view_models::signed_user::SignedUser;
+use crate::db::models::{Group, GroupPreview, Lunch, LunchWithGroup, UserPreview};
 use askama::Template;
 use chrono::NaiveDate;
-use db::db::models::{Group, GroupPreview, Lunch, LunchWithGroup, UserPreview};
 use uuid::Uuid;
 
 #[derive(Template)]
 #[template(path = "groups.html")]
 pub struct GroupsTemplate {
     pub group_previews: Vec<GroupPreview>,
+    pub invitations: Vec<GroupInvitationView>,
     pub signed_user: Option<SignedUser>,
+    pub csrf_token: String,
+}
+
+#[derive(Template)]
+#[template(path = "group_join_link_list.html")]
+pub struct GroupJoinLinkListTemplate {
+    pub links: Vec<GroupJoinLinkView>,
+}
+
+#[derive(Template)]
+#[template(path = "group_invitation_sent.html")]
+pub struct GroupInvitationSentTemplate {}
+
+#[derive(Template)]
+#[template(path = "group_join.html")]
+pub struct GroupJoinTemplate {
+    pub group: Group,
+    pub token: String,
+    pub csrf_token: String,
 }
 
 #[derive(Template)]
 #[template(path = "group_creation.html")]
-pub struct GroupCreationTemplate {}
+pub struct GroupCreationTemplate {
+    pub csrf_token: String,
+}
 
 #[derive(Template)]
 #[template(path = "group_edit.html")]
 pub struct GroupEditTemplate {
     pub group: GroupView,
     pub group_id: Uuid,
+    pub is_owner: bool,
+    pub csrf_token: String,
 }
 
 #[derive(Template)]
@@ -31,8 +58,9 @@ pub struct GroupDetailsTemplate {
     pub group: Group,
     pub group_members: Vec<UserPreview>,
     pub group_lunches: Vec<LunchWithGroup>,
-    pub is_author: bool,
-    pub user_id: Uuid,
+    pub is_admin: bool,
+    pub is_owner: bool,
+    pub csrf_token: String,
 }
 
 #[derive(Template)]
@@ -45,9 +73,7 @@ pub struct GroupCreateLunchTemplate {
 #[derive(Template)]
 #[template(path = "create_lunch_button.html")]
 pub struct GroupCreateLunchFormTemplate {
-    pub group_id: Uuid,
     pub lunch: Lunch,
-    pub date: NaiveDate,
 }
 
 #[derive(Template)]
@@ -56,4 +82,7 @@ pub struct GroupLunchMenusTemplate {
     pub signed_user: Option<SignedUser>,
     pub lunch: Lunch,
     pub menus: Vec<MenuWithRestaurantAndVotesView>,
+    pub attendance: LunchAttendanceView,
+    pub voting: LunchVotingView,
+    pub vetoes: LunchVetoesView,
 }
@@ -8,4 +8,5 @@ pub struct IndexTemplate {
     pub signed_user: Option<SignedUser>,
     pub menus: Vec<MenuWithRestaurantView>,
     pub date: String,
+    pub csrf_token: String,
 }
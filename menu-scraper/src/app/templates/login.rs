@@ -2,4 +2,7 @@ use askama::Template;
 
 #[derive(Template)]
 #[template(path = "login.html")]
-pub struct LoginTemplate {}
+pub struct LoginTemplate {
+    pub csrf_token: String,
+    pub next: Option<String>,
+}
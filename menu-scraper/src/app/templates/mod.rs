@@ -4,9 +4,9 @@ pub mod index;
 pub mod login;
 pub mod lunch;
 pub mod menu;
-mod nav;
 pub mod registration;
 pub mod restaurant;
+pub mod scrape_run;
 pub mod user_edit;
 pub mod user_group;
 pub mod user_preview_list;
@@ -2,4 +2,6 @@ use askama::Template;
 
 #[derive(Template)]
 #[template(path = "registration.html")]
-pub struct RegistrationTemplate {}
+pub struct RegistrationTemplate {
+    pub csrf_token: String,
+}
@@ -9,4 +9,5 @@ pub struct RestaurantTemplate {
     pub restaurant: RestaurantView,
     pub signed_user: Option<SignedUser>,
     pub menus: Vec<MenuView>,
+    pub csrf_token: String,
 }
@@ -0,0 +1,14 @@
+use crate::app::view_models::scrape_run::ScrapeRunView;
+use crate::app::view_models::signed_user::SignedUser;
+use crate::scrapping::service::progress::ScrapeProgress;
+use askama::Template;
+
+#[derive(Template)]
+#[template(path = "scrape_status.html")]
+pub struct ScrapeStatusTemplate {
+    pub signed_user: Option<SignedUser>,
+    pub progress: ScrapeProgress,
+    pub last_run: Option<ScrapeRunView>,
+    pub history: Vec<ScrapeRunView>,
+    pub csrf_token: String,
+}
@@ -1,3 +1,4 @@
+use crate::app::view_models::api_token::{ApiTokenView, CreatedApiTokenView};
 use crate::app::view_models::user_edit::UserEdit;
 use askama::Template;
 
@@ -5,4 +6,21 @@ use askama::Template;
 #[template(path = "user_edit.html")]
 pub struct UserEditTemplate {
     pub user: UserEdit,
+    pub tokens: Vec<ApiTokenView>,
+    pub created_token: Option<CreatedApiTokenView>,
+    pub feed_url: Option<String>,
+    pub csrf_token: String,
+}
+
+#[derive(Template)]
+#[template(path = "api_token_list.html")]
+pub struct ApiTokenListTemplate {
+    pub tokens: Vec<ApiTokenView>,
+    pub created_token: Option<CreatedApiTokenView>,
+}
+
+#[derive(Template)]
+#[template(path = "calendar_feed.html")]
+pub struct CalendarFeedTemplate {
+    pub feed_url: Option<String>,
 }
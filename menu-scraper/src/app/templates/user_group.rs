@@ -1,3 +1,4 @@
+use crate::app::view_models::group::GroupMemberView;
 use crate::app::view_models::user_preview::UserPreviewView;
 use askama::Template;
 use uuid::Uuid;
@@ -11,6 +12,7 @@ pub struct UserGroupPreview {
 #[derive(Template)]
 #[template(path = "user_group.html")]
 pub struct UserGroup {
-    pub user_preview: UserPreviewView,
+    pub member: GroupMemberView,
     pub group_id: Uuid,
+    pub is_owner: bool,
 }
@@ -0,0 +1,32 @@
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use uuid::Uuid;
+
+/// Prefix of personal access tokens, makes them recognizable (e.g. by secret scanners)
+const TOKEN_PREFIX: &str = "mst_";
+
+/// Number of random bytes of the secret part of a token
+const SECRET_BYTES: usize = 32;
+
+/// Generates the secret part of a new token, only its hash is stored
+pub fn generate_token_secret() -> String {
+    let mut bytes = [0u8; SECRET_BYTES];
+    OsRng.fill_bytes(&mut bytes);
+
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Formats the token given to the user, the id is needed to find the hash of the secret
+pub fn format_token(id: &Uuid, secret: &str) -> String {
+    format!("{TOKEN_PREFIX}{}_{secret}", id.simple())
+}
+
+/// Splits the token to its id and secret, None when the token is malformed
+pub fn parse_token(token: &str) -> Option<(Uuid, &str)> {
+    let (id, secret) = token.strip_prefix(TOKEN_PREFIX)?.split_once('_')?;
+
+    if secret.is_empty() {
+        return None;
+    }
+
+    Some((Uuid::parse_str(id).ok()?, secret))
+}
@@ -0,0 +1,85 @@
+use chrono::{DateTime, Utc};
+use std::fmt::Write;
+
+/// Maximal length of a line in octets, longer lines are folded (RFC 5545, section 3.1)
+const MAX_LINE_LENGTH: usize = 75;
+
+/// Event of the iCalendar feed
+#[derive(Debug, Clone)]
+pub struct CalendarEvent {
+    /// Globally unique id, calendar apps update the event with the same uid
+    pub uid: String,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub summary: String,
+    pub location: Option<String>,
+    pub description: Option<String>,
+}
+
+/// Renders events as an iCalendar (`.ics`) document
+pub fn render_calendar(name: &str, events: &[CalendarEvent], now: DateTime<Utc>) -> String {
+    let stamp = format_date_time(&now);
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//Menu Scraper//Lunches//CS".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+        format!("X-WR-CALNAME:{}", escape_text(name)),
+    ];
+
+    for event in events {
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:{}", escape_text(&event.uid)));
+        lines.push(format!("DTSTAMP:{stamp}"));
+        lines.push(format!("DTSTART:{}", format_date_time(&event.start)));
+        lines.push(format!("DTEND:{}", format_date_time(&event.end)));
+        lines.push(format!("SUMMARY:{}", escape_text(&event.summary)));
+        if let Some(location) = &event.location {
+            lines.push(format!("LOCATION:{}", escape_text(location)));
+        }
+        if let Some(description) = &event.description {
+            lines.push(format!("DESCRIPTION:{}", escape_text(description)));
+        }
+        lines.push("END:VEVENT".to_string());
+    }
+
+    lines.push("END:VCALENDAR".to_string());
+
+    lines.iter().fold(String::new(), |mut calendar, line| {
+        let _ = write!(calendar, "{}\r\n", fold_line(line));
+        calendar
+    })
+}
+
+/// Formats time in UTC, which needs no time zone definitions in the calendar
+fn format_date_time(time: &DateTime<Utc>) -> String {
+    time.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Escapes characters with a special meaning in text values
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Splits long lines, continuation lines start with a space, characters are never split
+fn fold_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len());
+    let mut length = 0;
+
+    for character in line.chars() {
+        if length + character.len_utf8() > MAX_LINE_LENGTH {
+            folded.push_str("\r\n ");
+            // The leading space counts to the length of the line
+            length = 1;
+        }
+        folded.push(character);
+        length += character.len_utf8();
+    }
+
+    folded
+}
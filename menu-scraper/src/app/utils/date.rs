@@ -1,4 +1,6 @@
-use chrono::{Datelike, NaiveDate, Weekday};
+use chrono::{DateTime, Datelike, Local, NaiveDate, NaiveTime, TimeZone, Utc, Weekday};
+
+const DATE_TIME_FORMAT: &str = "%-d. %-m. %Y %H:%M";
 
 pub fn format_date_with_day_of_week(date: NaiveDate) -> String {
     let day_of_week = match date.weekday() {
@@ -16,3 +18,23 @@ pub fn format_date_with_day_of_week(date: NaiveDate) -> String {
 
     std::format!("{} {}. {}. {}", day_of_week, day_of_month, month, year)
 }
+
+/// Formats time in the local timezone, e.g. "5. 1. 2024 12:30"
+pub fn format_date_time(date_time: DateTime<Utc>) -> String {
+    date_time
+        .with_timezone(&Local)
+        .format(DATE_TIME_FORMAT)
+        .to_string()
+}
+
+/// Converts time entered in the local timezone, times skipped by the change to summer time are
+/// taken as UTC
+pub fn local_date_time_to_utc(date: NaiveDate, time: NaiveTime) -> DateTime<Utc> {
+    let date_time = date.and_time(time);
+
+    Local
+        .from_local_datetime(&date_time)
+        .earliest()
+        .map(|date_time| date_time.with_timezone(&Utc))
+        .unwrap_or_else(|| Utc.from_utc_datetime(&date_time))
+}
@@ -1,4 +1,7 @@
+pub mod api_token;
+pub mod calendar;
 pub mod date;
 pub mod password;
 pub mod picture;
+pub mod secret;
 pub mod validation;
@@ -30,7 +30,7 @@ pub async fn validate_and_save_picture(picture: TempFile) -> Result<String, Erro
 
     // Extract file extension
     let filename = picture.file_name.unwrap_or("unknown.ext".to_string());
-    let extension = filename.split('.').last().unwrap_or_default();
+    let extension = filename.split('.').next_back().unwrap_or_default();
 
     // Check if the file extension is allowed
     if !ALLOWED_EXTENSIONS.contains(&extension.to_lowercase().as_str()) {
@@ -0,0 +1,9 @@
+/// Compares secrets (tokens) without leaking the length of the matching prefix through timing
+pub fn constant_time_eq(left: &[u8], right: &[u8]) -> bool {
+    left.len() == right.len()
+        && left
+            .iter()
+            .zip(right)
+            .fold(0, |difference, (l, r)| difference | (l ^ r))
+            == 0
+}
@@ -0,0 +1,29 @@
+use crate::app::utils::date::format_date_time;
+use crate::db::models::ApiToken;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiTokenView {
+    pub id: Uuid,
+    pub name: String,
+    pub created_at: String,
+    pub last_used_at: Option<String>,
+}
+
+/// Newly created token, shown only once right after its creation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreatedApiTokenView {
+    pub name: String,
+    pub token: String,
+}
+
+impl From<ApiToken> for ApiTokenView {
+    fn from(token: ApiToken) -> Self {
+        ApiTokenView {
+            id: token.id,
+            name: token.name,
+            created_at: format_date_time(token.created_at),
+            last_used_at: token.last_used_at.map(format_date_time),
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::app::view_models::user_preview::UserPreviewView;
+use crate::db::models::{GroupMember, GroupRole};
 use uuid::Uuid;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -7,5 +8,39 @@ pub struct GroupView {
     pub name: String,
     pub description: Option<String>,
     pub picture: Option<String>,
-    pub users: Vec<UserPreviewView>,
+    pub users: Vec<GroupMemberView>,
+}
+
+/// Member of the group shown on the edit page
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupMemberView {
+    pub user_preview: UserPreviewView,
+    pub role: GroupRole,
+}
+
+impl GroupMemberView {
+    pub fn is_admin(&self) -> bool {
+        self.role >= GroupRole::Admin
+    }
+
+    pub fn role_label(&self) -> &'static str {
+        match self.role {
+            GroupRole::Owner => "Vlastník",
+            GroupRole::Admin => "Správce",
+            GroupRole::Member => "Člen",
+        }
+    }
+}
+
+impl From<GroupMember> for GroupMemberView {
+    fn from(member: GroupMember) -> Self {
+        GroupMemberView {
+            user_preview: UserPreviewView {
+                id: member.id,
+                username: member.username,
+                profile_picture: member.profile_picture,
+            },
+            role: member.role,
+        }
+    }
 }
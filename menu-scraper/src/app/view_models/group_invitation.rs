@@ -0,0 +1,43 @@
+use crate::app::utils::date::format_date_time;
+use crate::db::models::{GroupInvitationPreview, GroupJoinLink};
+use uuid::Uuid;
+
+/// Pending invitation shown to the invited user
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupInvitationView {
+    pub id: Uuid,
+    pub group_name: String,
+    pub group_picture: Option<String>,
+    pub invited_by: String,
+    pub created_at: String,
+}
+
+impl From<GroupInvitationPreview> for GroupInvitationView {
+    fn from(invitation: GroupInvitationPreview) -> Self {
+        GroupInvitationView {
+            id: invitation.id,
+            group_name: invitation.group_name,
+            group_picture: invitation.group_picture,
+            invited_by: invitation.invited_by_username,
+            created_at: format_date_time(invitation.created_at),
+        }
+    }
+}
+
+/// Join link of a group shown to its admins
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupJoinLinkView {
+    pub id: Uuid,
+    pub url: String,
+    pub expires_at: String,
+}
+
+impl GroupJoinLinkView {
+    pub fn new(link: GroupJoinLink, url: String) -> Self {
+        GroupJoinLinkView {
+            id: link.id,
+            url,
+            expires_at: format_date_time(link.expires_at),
+        }
+    }
+}
@@ -1,5 +1,9 @@
-use chrono::NaiveDate;
-use db::db::models::{LunchWithGroup, MenuItem, MenuWithRestaurantAndVotes};
+use crate::app::utils::date::format_date_time;
+use crate::db::models::{
+    Lunch, LunchRsvp, LunchWithGroup, MenuItem, MenuWithRestaurantAndVotes, RsvpStatus, TieBreak,
+    VetoPreview, VotingMode, VETO_LIMIT,
+};
+use chrono::{NaiveDate, Utc};
 use uuid::Uuid;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -38,12 +42,21 @@ pub struct MenuWithRestaurantAndVotesView {
     pub date: NaiveDate,
     pub items: Vec<MenuItem>,
     pub votes: usize,
+    /// Score by the voting mode of the lunch
+    pub score: i64,
     pub is_voted_for: bool,
+    /// Vote of the signed user, which can be withdrawn
+    pub user_vote_id: Option<Uuid>,
+    /// Choice of the signed user in ranked-choice voting
+    pub user_rank: Option<i32>,
 }
 
 impl MenuWithRestaurantAndVotesView {
-    pub fn new(menu: MenuWithRestaurantAndVotes, user_id: Uuid) -> Self {
-        let is_voted_for = menu.votes.iter().any(|vote| vote.user_id == user_id);
+    pub fn new(menu: MenuWithRestaurantAndVotes, user_id: Uuid, score: i64) -> Self {
+        let user_vote = menu.votes.iter().find(|vote| vote.user_id == user_id);
+        let is_voted_for = user_vote.is_some();
+        let user_vote_id = user_vote.map(|vote| vote.id);
+        let user_rank = user_vote.and_then(|vote| vote.rank);
 
         MenuWithRestaurantAndVotesView {
             restaurant_id: menu.restaurant_id,
@@ -57,7 +70,136 @@ impl MenuWithRestaurantAndVotesView {
             date: menu.date,
             items: menu.items,
             votes: menu.votes.len(),
+            score,
             is_voted_for,
+            user_vote_id,
+            user_rank,
+        }
+    }
+}
+
+/// Numbers of members by their answers to a lunch, with the answer of the signed user
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LunchAttendanceView {
+    pub going: usize,
+    pub not_going: usize,
+    pub maybe: usize,
+    /// Value of the answer in the form (`going`, `not_going` or `maybe`)
+    pub user_status: Option<&'static str>,
+}
+
+impl LunchAttendanceView {
+    pub fn new(rsvps: &[LunchRsvp], user_id: Uuid) -> Self {
+        let mut attendance = Self::default();
+
+        for rsvp in rsvps {
+            let status = match rsvp.status {
+                RsvpStatus::Going => {
+                    attendance.going += 1;
+                    "going"
+                }
+                RsvpStatus::NotGoing => {
+                    attendance.not_going += 1;
+                    "not_going"
+                }
+                RsvpStatus::Maybe => {
+                    attendance.maybe += 1;
+                    "maybe"
+                }
+            };
+
+            if rsvp.user_id == user_id {
+                attendance.user_status = Some(status);
+            }
+        }
+
+        attendance
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VetoView {
+    pub id: Uuid,
+    pub restaurant_id: Uuid,
+    pub name: String,
+    /// Whether the whole restaurant is vetoed
+    pub restaurant: bool,
+    /// Whether the signed user cast the veto and can withdraw it
+    pub is_own: bool,
+}
+
+/// Vetoed menus of a lunch, with the number of vetoes the signed user has left
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LunchVetoesView {
+    pub vetoes: Vec<VetoView>,
+    pub remaining: i64,
+}
+
+impl LunchVetoesView {
+    pub fn new(vetoes: Vec<VetoPreview>, user_id: Uuid) -> Self {
+        let vetoes: Vec<_> = vetoes
+            .into_iter()
+            .map(|veto| VetoView {
+                id: veto.id,
+                restaurant_id: veto.restaurant_id,
+                name: veto.name,
+                restaurant: veto.restaurant,
+                is_own: veto.user_id == user_id,
+            })
+            .collect();
+        let cast = vetoes.iter().filter(|veto| veto.is_own).count() as i64;
+
+        LunchVetoesView {
+            vetoes,
+            remaining: (VETO_LIMIT - cast).max(0),
+        }
+    }
+}
+
+/// State of the voting of a lunch, with the winning menu once the lunch is finalized
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LunchVotingView {
+    /// Explanation of how the votes are counted
+    pub mode_description: &'static str,
+    /// Label of the voting button
+    pub vote_label: &'static str,
+    pub is_closed: bool,
+    pub is_finalized: bool,
+    pub deadline: Option<String>,
+    pub tie_break: &'static str,
+    pub winner: Option<MenuWithRestaurantAndVotesView>,
+}
+
+impl LunchVotingView {
+    pub fn new(lunch: &Lunch, menus: &[MenuWithRestaurantAndVotesView]) -> Self {
+        let tie_break = match lunch.tie_break {
+            TieBreak::Random => "náhodně",
+            TieBreak::Cheapest => "nejlevnější menu",
+            TieBreak::Closest => "nejbližší restaurace",
+        };
+
+        let (mode_description, vote_label) = match lunch.voting_mode {
+            VotingMode::Plurality => ("Každý hlasuje pro jedno menu.", "Hlasovat"),
+            VotingMode::Approval => (
+                "Každý hlasuje pro všechna menu, která mu vyhovují.",
+                "Schválit",
+            ),
+            VotingMode::Ranked => (
+                "Hlasujte pro menu v pořadí, v jakém je chcete. Menu s nejméně prvními volbami postupně vypadávají.",
+                "Další volba",
+            ),
+        };
+
+        LunchVotingView {
+            mode_description,
+            vote_label,
+            is_closed: lunch.voting_is_closed(Utc::now()),
+            is_finalized: lunch.finalized_at.is_some(),
+            deadline: lunch.voting_deadline.map(format_date_time),
+            tie_break,
+            winner: lunch
+                .winner_menu_id
+                .and_then(|winner_id| menus.iter().find(|menu| menu.menu_id == winner_id).cloned()),
         }
     }
 }
@@ -1,6 +1,6 @@
 use crate::app::utils::date::format_date_with_day_of_week;
+use crate::db::models::{MenuItem, MenuWithRestaurant};
 use chrono::NaiveDate;
-use db::db::models::{MenuItem, MenuWithRestaurant};
 use uuid::Uuid;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -33,6 +33,12 @@ pub struct MenuItemView {
     pub size: String,
     pub is_soup: bool,
     pub menu_id: Uuid,
+    pub allergens: Vec<i32>,
+    pub is_vegetarian: bool,
+    pub is_vegan: bool,
+    pub is_gluten_free: bool,
+    /// Item matches the search query
+    pub is_highlighted: bool,
 }
 
 impl From<MenuItem> for MenuItemView {
@@ -44,6 +50,11 @@ impl From<MenuItem> for MenuItemView {
             size: item.size,
             is_soup: item.is_soup,
             menu_id: item.menu_id,
+            allergens: item.allergens,
+            is_vegetarian: item.is_vegetarian,
+            is_vegan: item.is_vegan,
+            is_gluten_free: item.is_gluten_free,
+            is_highlighted: false,
         }
     }
 }
@@ -65,11 +76,10 @@ impl From<MenuWithRestaurant> for MenuWithRestaurantView {
             picture: menu_with_restaurant.picture,
             menu_id: menu_with_restaurant.menu_id,
             date: menu_with_restaurant.date,
-            items: menu_with_restaurant
-                .items
-                .into_iter()
-                .map(MenuItemView::from)
-                .collect(),
+            items: highlighted_items(
+                menu_with_restaurant.items,
+                &menu_with_restaurant.matching_item_ids,
+            ),
         }
     }
 }
@@ -85,11 +95,24 @@ impl From<MenuWithRestaurant> for MenuView {
             menu_id: menu_with_restaurant.menu_id,
             date: menu_with_restaurant.date,
             name: format_date_with_day_of_week(menu_with_restaurant.date),
-            items: menu_with_restaurant
-                .items
-                .into_iter()
-                .map(MenuItemView::from)
-                .collect(),
+            items: highlighted_items(
+                menu_with_restaurant.items,
+                &menu_with_restaurant.matching_item_ids,
+            ),
         }
     }
 }
+
+/// Converts items to view models, highlighting items matching the search query
+fn highlighted_items(items: Vec<MenuItem>, matching_item_ids: &[Uuid]) -> Vec<MenuItemView> {
+    items
+        .into_iter()
+        .map(|item| {
+            let is_highlighted = matching_item_ids.contains(&item.id);
+            MenuItemView {
+                is_highlighted,
+                ..MenuItemView::from(item)
+            }
+        })
+        .collect()
+}
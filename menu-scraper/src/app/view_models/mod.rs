@@ -1,7 +1,10 @@
+pub mod api_token;
 pub mod group;
+pub mod group_invitation;
 pub mod lunch;
 pub mod menu;
 pub mod restaurant;
+pub mod scrape_run;
 pub mod signed_user;
 pub mod user_edit;
 pub mod user_preview;
@@ -1,4 +1,4 @@
-use db::db::models::Restaurant;
+use crate::db::models::Restaurant;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
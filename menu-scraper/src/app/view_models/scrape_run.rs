@@ -0,0 +1,49 @@
+use crate::app::utils::date::format_date_time;
+use crate::db::models::{ScrapeRun, ScrapeRunError};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScrapeRunView {
+    pub started_at: String,
+    pub finished_at: String,
+    pub duration_seconds: i64,
+    pub restaurants_seen: i32,
+    pub menus_inserted: i32,
+    pub menus_updated: i32,
+    pub errors: Vec<ScrapeRunErrorView>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScrapeRunErrorView {
+    pub source: String,
+    pub restaurant: String,
+    pub message: String,
+}
+
+impl From<ScrapeRunError> for ScrapeRunErrorView {
+    fn from(error: ScrapeRunError) -> Self {
+        ScrapeRunErrorView {
+            // Whole source failed, when there is no restaurant
+            restaurant: error.restaurant.unwrap_or(error.source.clone()),
+            source: error.source,
+            message: error.message,
+        }
+    }
+}
+
+impl From<ScrapeRun> for ScrapeRunView {
+    fn from(scrape_run: ScrapeRun) -> Self {
+        ScrapeRunView {
+            started_at: format_date_time(scrape_run.started_at),
+            finished_at: format_date_time(scrape_run.finished_at),
+            duration_seconds: (scrape_run.finished_at - scrape_run.started_at).num_seconds(),
+            restaurants_seen: scrape_run.restaurants_seen,
+            menus_inserted: scrape_run.menus_inserted,
+            menus_updated: scrape_run.menus_updated,
+            errors: scrape_run
+                .errors
+                .into_iter()
+                .map(ScrapeRunErrorView::from)
+                .collect(),
+        }
+    }
+}
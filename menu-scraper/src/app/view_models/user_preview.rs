@@ -1,4 +1,4 @@
-use db::db::models::UserPreview;
+use crate::db::models::UserPreview;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -19,6 +19,7 @@ pub enum BusinessLogicErrorKind {
     // --------------------------
     MenuDoesNotExist,
     MenuDeleted,
+    MenuWithoutItems,
 
     // Group errors
     // --------------------------
@@ -30,6 +31,17 @@ pub enum BusinessLogicErrorKind {
     GroupUsersDoesNotExist,
     GroupUsersDeleted,
     UserAlreadyInGroup,
+    UserNotGroupAdmin,
+    GroupOwnerCannotLeave,
+    GroupOwnerChange,
+
+    // GroupInvitation errors
+    // --------------------------
+    InvitationDoesNotExist,
+    InvitationAlreadyAnswered,
+    InvitationAlreadyPending,
+    JoinLinkDoesNotExist,
+    JoinLinkExpired,
 
     // Lunch errors
     // --------------------------
@@ -43,6 +55,27 @@ pub enum BusinessLogicErrorKind {
     VoteDeleted,
     UserNotMemberOfGroup,
     LunchDateDoesntMatchMenuDate,
+    VotingClosed,
+
+    // Veto errors
+    // --------------------------
+    VetoDoesNotExist,
+    VetoDeleted,
+    VetoLimitReached,
+    MenuVetoed,
+
+    // ScrapeRun errors
+    // --------------------------
+    ScrapeRunDoesNotExist,
+
+    // ApiToken errors
+    // --------------------------
+    ApiTokenDoesNotExist,
+    ApiTokenRevoked,
+
+    // CalendarFeed errors
+    // --------------------------
+    CalendarFeedDoesNotExist,
 
     // Generic errors
     UpdateParametersEmpty,
@@ -75,6 +108,9 @@ impl Display for BusinessLogicErrorKind {
             MenuDeleted => {
                 write!(f, "Toto menu bylo odstraněno.")
             }
+            MenuWithoutItems => {
+                write!(f, "Menu musí obsahovat alespoň jednu položku.")
+            }
             GroupDoesNotExist => {
                 write!(f, "Taková skupina neexistuje.")
             }
@@ -93,6 +129,21 @@ impl Display for BusinessLogicErrorKind {
             UserAlreadyInGroup => {
                 write!(f, "Tento uživatel již ve skupině je.")
             }
+            UserNotGroupAdmin => {
+                write!(f, "Nemáte ve skupině dostatečná oprávnění.")
+            }
+            GroupOwnerCannotLeave => {
+                write!(
+                    f,
+                    "Vlastník nemůže opustit skupinu, nejprve předejte vlastnictví."
+                )
+            }
+            GroupOwnerChange => {
+                write!(
+                    f,
+                    "Vlastníka skupiny lze změnit pouze předáním vlastnictví."
+                )
+            }
             GroupUsersDoesNotExist => {
                 write!(f, "Tento uživatel není ve skupině.")
             }
@@ -105,6 +156,9 @@ impl Display for BusinessLogicErrorKind {
             LunchDateDoesntMatchMenuDate => {
                 write!(f, "Oběd musí být ve stejný den jako menu.")
             }
+            VotingClosed => {
+                write!(f, "Hlasování o tomto obědě již skončilo.")
+            }
             EmailAlreadyUsed => {
                 write!(f, "Tento email je již používán.")
             }
@@ -126,6 +180,45 @@ impl Display for BusinessLogicErrorKind {
             VoteDeleted => {
                 write!(f, "Tento hlas byl odstraněn.")
             }
+            VetoDoesNotExist => {
+                write!(f, "Toto veto neexistuje.")
+            }
+            VetoDeleted => {
+                write!(f, "Toto veto bylo odvoláno.")
+            }
+            VetoLimitReached => {
+                write!(f, "V tomto obědě jste již vyčerpali všechna veta.")
+            }
+            MenuVetoed => {
+                write!(f, "Toto menu bylo vetováno.")
+            }
+            ScrapeRunDoesNotExist => {
+                write!(f, "Tento běh stahování menu neexistuje.")
+            }
+            ApiTokenDoesNotExist => {
+                write!(f, "Tento přístupový token neexistuje.")
+            }
+            ApiTokenRevoked => {
+                write!(f, "Tento přístupový token byl zrušen.")
+            }
+            CalendarFeedDoesNotExist => {
+                write!(f, "Tento kalendář neexistuje.")
+            }
+            InvitationDoesNotExist => {
+                write!(f, "Tato pozvánka neexistuje.")
+            }
+            InvitationAlreadyAnswered => {
+                write!(f, "Na tuto pozvánku již bylo odpovězeno.")
+            }
+            InvitationAlreadyPending => {
+                write!(f, "Tento uživatel již byl do skupiny pozván.")
+            }
+            JoinLinkDoesNotExist => {
+                write!(f, "Tento odkaz pro připojení ke skupině neexistuje.")
+            }
+            JoinLinkExpired => {
+                write!(f, "Platnost odkazu pro připojení ke skupině vypršela.")
+            }
         }
     }
 }
@@ -1,6 +1,6 @@
 use std::fmt::{Debug, Display, Formatter};
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Eq)]
 pub enum DbOrder {
     Asc,
     Desc,
@@ -26,3 +26,43 @@ impl Debug for DbOrder {
         self.fmt(f)
     }
 }
+
+/// Full-text search query, words match beginnings of words regardless of case and diacritics.
+/// Common Czech endings are trimmed, so "svíčková" finds "svíčkovou" as well.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DbSearchQuery(String);
+
+/// Shortest word left after trimming the endings
+const MIN_STEM_LENGTH: usize = 3;
+
+impl DbSearchQuery {
+    /// Creates the query from user input, None when there is no word to search for
+    pub fn new(text: &str) -> Option<Self> {
+        let words: Vec<String> = text
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(|word| format!("{}:*", Self::stem(&word.to_lowercase())))
+            .collect();
+
+        (!words.is_empty()).then(|| Self(words.join(" & ")))
+    }
+
+    /// Query in the `to_tsquery` syntax
+    pub fn as_tsquery(&self) -> &str {
+        &self.0
+    }
+
+    /// Trims trailing vowels (declension endings) of a lowercase word
+    fn stem(word: &str) -> &str {
+        let mut stem = word;
+
+        while stem.chars().count() > MIN_STEM_LENGTH {
+            match stem.strip_suffix(|c: char| "aáeéěiíoóuúůyý".contains(c)) {
+                Some(shorter) => stem = shorter,
+                None => break,
+            }
+        }
+
+        stem
+    }
+}
@@ -106,7 +106,6 @@ pub struct PoolHandler {
 #[async_trait]
 impl DbPoolHandler for PoolHandler {
     /// Database pool constructor
-    #[must_use]
     fn new(pool: Arc<sqlx::PgPool>) -> Self {
         Self { pool }
     }
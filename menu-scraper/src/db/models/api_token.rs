@@ -0,0 +1,49 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Personal access token of a user for non-browser clients
+#[derive(sqlx::FromRow, Debug, Clone, PartialEq, Eq)]
+pub struct ApiToken {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    /// Hash of the secret part of the token
+    pub token_hash: String,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+/// Structure passed to the repository for creating a token
+#[derive(Debug, Clone)]
+pub struct ApiTokenCreate {
+    pub user_id: Uuid,
+    pub name: String,
+    pub token_hash: String,
+}
+
+/// Structure passed to the repository when trying to find a token by its id
+#[derive(Debug, Clone)]
+pub struct ApiTokenGetById {
+    pub id: Uuid,
+}
+
+impl ApiTokenGetById {
+    #[inline]
+    pub const fn new(id: &Uuid) -> Self {
+        Self { id: *id }
+    }
+}
+
+/// Structure passed to the repository for listing tokens of a user which are not revoked
+#[derive(Debug, Clone)]
+pub struct ApiTokenGetByUser {
+    pub user_id: Uuid,
+}
+
+/// Structure passed to the repository for revoking a token, only its owner can revoke it
+#[derive(Debug, Clone)]
+pub struct ApiTokenDelete {
+    pub id: Uuid,
+    pub user_id: Uuid,
+}
@@ -0,0 +1,38 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Secret iCalendar feed of lunches of a user, every user has at most one
+#[derive(sqlx::FromRow, Debug, Clone, PartialEq, Eq)]
+pub struct CalendarFeed {
+    pub user_id: Uuid,
+    pub token: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Structure passed to the repository for creating a feed, replaces the previous feed of the user
+#[derive(Debug, Clone)]
+pub struct CalendarFeedCreate {
+    pub user_id: Uuid,
+    pub token: String,
+}
+
+/// Structure passed to the repository when trying to find a feed by its secret token
+#[derive(Debug, Clone)]
+pub struct CalendarFeedGetByToken {
+    pub token: String,
+}
+
+impl CalendarFeedGetByToken {
+    #[inline]
+    pub fn new(token: &str) -> Self {
+        Self {
+            token: token.to_owned(),
+        }
+    }
+}
+
+/// Structure passed to the repository when trying to find a feed of a user
+#[derive(Debug, Clone)]
+pub struct CalendarFeedGetByUser {
+    pub user_id: Uuid,
+}
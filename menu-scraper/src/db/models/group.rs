@@ -58,12 +58,33 @@ impl GroupGetById {
     }
 }
 
+/// Role of a member in a group, roles are ordered from the least permissions
+#[derive(sqlx::Type, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[sqlx(type_name = "group_role", rename_all = "snake_case")]
+pub enum GroupRole {
+    Member,
+    /// Edits the group, adds and removes users and deletes lunches
+    Admin,
+    /// Author of the group, also deletes the group and manages the roles
+    Owner,
+}
+
 /// Connection between group and its members
 pub struct GroupUser {
     pub id: Uuid,
     pub user_id: Uuid,
     pub group_id: Uuid,
     pub deleted_at: Option<DateTime<Utc>>,
+    pub role: GroupRole,
+}
+
+/// Member of a group with their role
+#[derive(sqlx::FromRow, Debug, Clone, PartialEq, Eq)]
+pub struct GroupMember {
+    pub id: Uuid,
+    pub username: String,
+    pub profile_picture: Option<String>,
+    pub role: GroupRole,
 }
 
 /// Structure passed to the repository for getting user membership by user_id and group_id
@@ -83,11 +104,49 @@ impl GetGroupUserByIds {
     }
 }
 
-/// Structure passed to the repository for adding a user to a group
+/// Structure passed to the repository for checking that the user has at least the role in a group
+#[derive(Debug, Clone)]
+pub struct GroupUserCheckRole {
+    pub user_id: Uuid,
+    pub group_id: Uuid,
+    pub role: GroupRole,
+}
+
+impl GroupUserCheckRole {
+    #[inline]
+    pub const fn new(user_id: &Uuid, group_id: &Uuid, role: GroupRole) -> Self {
+        Self {
+            user_id: *user_id,
+            group_id: *group_id,
+            role,
+        }
+    }
+}
+
+/// Structure passed to the repository for changing the role of a member, the owner can only be
+/// changed by transferring the ownership
+#[derive(Debug, Clone)]
+pub struct GroupUserRoleUpdate {
+    pub user_id: Uuid,
+    pub group_id: Uuid,
+    pub role: GroupRole,
+}
+
+/// Structure passed to the repository for transferring the ownership of a group to its member,
+/// the previous owner becomes an admin
+#[derive(Debug, Clone)]
+pub struct GroupOwnershipTransfer {
+    pub group_id: Uuid,
+    pub user_id: Uuid,
+}
+
+/// Structure passed to the repository for adding a user to a group directly, without an
+/// invitation, only admins of the group add users
 #[derive(Debug, Clone)]
 pub struct GroupUserCreate {
     pub user_id: Uuid,
     pub group_id: Uuid,
+    pub added_by: Uuid,
 }
 
 /// Structure passed to the repository for deleting a user from a group
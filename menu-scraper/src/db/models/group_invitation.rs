@@ -0,0 +1,112 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// State of an invitation to a group
+#[derive(sqlx::Type, Debug, Clone, Copy, PartialEq, Eq)]
+#[sqlx(type_name = "invitation_status", rename_all = "snake_case")]
+pub enum InvitationStatus {
+    Pending,
+    Accepted,
+    Declined,
+}
+
+/// Invitation of a user to a group, the user joins the group by accepting it
+#[derive(sqlx::FromRow, Debug, Clone, PartialEq, Eq)]
+pub struct GroupInvitation {
+    pub id: Uuid,
+    pub group_id: Uuid,
+    pub user_id: Uuid,
+    pub invited_by: Uuid,
+    pub status: InvitationStatus,
+    pub created_at: DateTime<Utc>,
+    pub answered_at: Option<DateTime<Utc>>,
+}
+
+/// Pending invitation shown to the invited user
+#[derive(sqlx::FromRow, Debug, Clone, PartialEq, Eq)]
+pub struct GroupInvitationPreview {
+    pub id: Uuid,
+    pub group_id: Uuid,
+    pub group_name: String,
+    pub group_picture: Option<String>,
+    pub invited_by_username: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Structure passed to the repository for inviting a user, only admins of the group invite
+#[derive(Debug, Clone)]
+pub struct GroupInvitationCreate {
+    pub group_id: Uuid,
+    pub user_id: Uuid,
+    pub invited_by: Uuid,
+}
+
+/// Structure passed to the repository for answering an invitation by the invited user
+#[derive(Debug, Clone)]
+pub struct GroupInvitationAnswer {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub accept: bool,
+}
+
+/// Structure passed to the repository for getting pending invitations of a user
+#[derive(Debug, Clone)]
+pub struct GroupInvitationGetByUser {
+    pub user_id: Uuid,
+}
+
+impl GroupInvitationGetByUser {
+    #[inline]
+    pub const fn new(user_id: &Uuid) -> Self {
+        Self { user_id: *user_id }
+    }
+}
+
+/// Shareable link joining the signed user to a group until it expires
+#[derive(sqlx::FromRow, Debug, Clone, PartialEq, Eq)]
+pub struct GroupJoinLink {
+    pub id: Uuid,
+    pub group_id: Uuid,
+    pub token: String,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+/// Structure passed to the repository for creating a join link, only admins of the group create
+/// them
+#[derive(Debug, Clone)]
+pub struct GroupJoinLinkCreate {
+    pub group_id: Uuid,
+    pub created_by: Uuid,
+    pub token: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Structure passed to the repository for revoking a join link by an admin of the group
+#[derive(Debug, Clone)]
+pub struct GroupJoinLinkDelete {
+    pub id: Uuid,
+    pub user_id: Uuid,
+}
+
+/// Structure passed to the repository for listing unexpired join links of a group
+#[derive(Debug, Clone)]
+pub struct GroupJoinLinkGetMany {
+    pub group_id: Uuid,
+    pub user_id: Uuid,
+}
+
+/// Structure passed to the repository for getting the group of a join link by its secret token
+#[derive(Debug, Clone)]
+pub struct GroupJoinLinkGetByToken {
+    pub token: String,
+}
+
+/// Structure passed to the repository for joining a group by the secret token of a link
+#[derive(Debug, Clone)]
+pub struct GroupJoinLinkUse {
+    pub token: String,
+    pub user_id: Uuid,
+}
@@ -0,0 +1,168 @@
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+/// Failed login attempt, further attempts are throttled by the account and by the address
+#[derive(sqlx::FromRow, Debug, Clone, PartialEq, Eq)]
+pub struct LoginAttempt {
+    pub id: Uuid,
+    /// Email the attempt was made for, normalized, the account does not need to exist
+    pub email: String,
+    pub ip_address: String,
+    pub created_at: DateTime<Utc>,
+    /// The account was signed in to since, the attempt counts only for the address
+    pub account_cleared: bool,
+}
+
+/// Structure passed to the repository for recording a failed login attempt
+#[derive(Debug, Clone)]
+pub struct LoginAttemptCreate {
+    pub email: String,
+    pub ip_address: String,
+}
+
+impl LoginAttemptCreate {
+    #[inline]
+    pub fn new(email: &str, ip_address: &str) -> Self {
+        Self {
+            email: normalize_email(email),
+            ip_address: ip_address.to_owned(),
+        }
+    }
+}
+
+/// Result of starting a login, its attempt is recorded only when the login is not throttled
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoginAttemptBegin {
+    /// The attempt is recorded, the password can be verified
+    Recorded(LoginAttempt),
+    /// Too many recent failures, the login has to wait before the next attempt
+    Throttled(Duration),
+}
+
+/// Structure passed to the repository for getting recent failed attempts of an account and an
+/// address
+#[derive(Debug, Clone)]
+pub struct LoginAttemptGetRecent {
+    pub email: String,
+    pub ip_address: String,
+    /// Attempts before this are not counted
+    pub since: DateTime<Utc>,
+}
+
+impl LoginAttemptGetRecent {
+    /// Attempts within the window of the throttling
+    #[inline]
+    pub fn new(email: &str, ip_address: &str, now: DateTime<Utc>) -> Self {
+        Self {
+            email: normalize_email(email),
+            ip_address: ip_address.to_owned(),
+            since: now - Duration::seconds(LoginThrottlePolicy::WINDOW_SECONDS),
+        }
+    }
+}
+
+/// Structure passed to the repository for forgetting failed attempts of an account after
+/// a successful login
+#[derive(Debug, Clone)]
+pub struct LoginAttemptClear {
+    /// Attempt of the successful login, it did not fail
+    pub attempt_id: Uuid,
+    pub email: String,
+}
+
+impl LoginAttemptClear {
+    #[inline]
+    pub fn new(attempt: &LoginAttempt) -> Self {
+        Self {
+            attempt_id: attempt.id,
+            email: attempt.email.clone(),
+        }
+    }
+}
+
+/// Emails differing only in case or surrounding whitespace are the same account for throttling
+fn normalize_email(email: &str) -> String {
+    email.trim().to_lowercase()
+}
+
+/// Recent failed attempts of an account or an address
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LoginFailures {
+    pub count: i64,
+    pub last_at: Option<DateTime<Utc>>,
+}
+
+/// Recent failed attempts of the account and the address of a login
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LoginThrottle {
+    pub account: LoginFailures,
+    pub ip_address: LoginFailures,
+}
+
+impl LoginThrottle {
+    /// How long the login has to wait, None when it can be attempted right away
+    pub fn retry_after(&self, now: DateTime<Utc>) -> Option<Duration> {
+        let account = LoginThrottlePolicy::ACCOUNT.retry_after(&self.account, now);
+        let ip_address = LoginThrottlePolicy::IP_ADDRESS.retry_after(&self.ip_address, now);
+
+        account.max(ip_address)
+    }
+}
+
+/// Limits of failed login attempts. A few attempts are free, then every next one doubles the
+/// delay before another attempt, until the attempts are locked out for a while.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LoginThrottlePolicy {
+    /// Failed attempts allowed without any delay
+    pub free_attempts: i64,
+    /// Delay after the first attempt over the free ones
+    pub base_delay_seconds: i64,
+    /// Failed attempts locking out further attempts
+    pub lockout_attempts: i64,
+    pub lockout_seconds: i64,
+}
+
+impl LoginThrottlePolicy {
+    /// Failed attempts older than this are not counted
+    pub const WINDOW_SECONDS: i64 = 60 * 60;
+
+    /// Guessing a password of one account
+    pub const ACCOUNT: Self = Self {
+        free_attempts: 3,
+        base_delay_seconds: 1,
+        lockout_attempts: 10,
+        lockout_seconds: 15 * 60,
+    };
+
+    /// Trying many accounts from one address, more lenient as users may share the address
+    pub const IP_ADDRESS: Self = Self {
+        free_attempts: 10,
+        base_delay_seconds: 1,
+        lockout_attempts: 50,
+        lockout_seconds: 15 * 60,
+    };
+
+    /// Delay after the given number of failed attempts, the lockout is the longest one
+    pub fn delay(&self, failures: i64) -> Option<Duration> {
+        if failures <= self.free_attempts {
+            return None;
+        }
+
+        if failures >= self.lockout_attempts {
+            return Some(Duration::seconds(self.lockout_seconds));
+        }
+
+        // Capped, so the shift can not overflow
+        let exponent = (failures - self.free_attempts - 1).min(32) as u32;
+        let seconds = self.base_delay_seconds.saturating_mul(1 << exponent);
+
+        Some(Duration::seconds(seconds.min(self.lockout_seconds)))
+    }
+
+    /// How long the next attempt has to wait since the last failed one
+    pub fn retry_after(&self, failures: &LoginFailures, now: DateTime<Utc>) -> Option<Duration> {
+        let allowed_at = failures.last_at? + self.delay(failures.count)?;
+
+        (allowed_at > now).then(|| allowed_at - now)
+    }
+}
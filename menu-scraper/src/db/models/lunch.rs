@@ -1,13 +1,34 @@
-use chrono::{DateTime, NaiveDate, Utc};
+use crate::db::models::{DbRestaurantOrderingMethod, VotingMode};
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
 use uuid::Uuid;
 
 /// Lunch of a group
-#[derive(sqlx::FromRow, Debug, Clone, PartialEq, Eq)]
+#[derive(sqlx::FromRow, Debug, Clone, PartialEq)]
 pub struct Lunch {
     pub id: Uuid,
     pub date: NaiveDate,
     pub group_id: Uuid,
     pub deleted_at: Option<DateTime<Utc>>,
+    pub start_time: NaiveTime,
+    /// Where the members meet before going to the restaurant, or any other note
+    pub meeting_point: Option<String>,
+    /// Votes can not be changed after the deadline, no deadline means voting never ends
+    pub voting_deadline: Option<DateTime<Utc>>,
+    pub tie_break: TieBreak,
+    /// Location the closest restaurant is measured from
+    pub longitude: Option<f64>,
+    pub latitude: Option<f64>,
+    pub voting_mode: VotingMode,
+    /// Menu chosen after the deadline, None when nobody voted or the lunch is not finalized yet
+    pub winner_menu_id: Option<Uuid>,
+    pub finalized_at: Option<DateTime<Utc>>,
+}
+
+impl Lunch {
+    /// Votes are locked once the deadline passes, even before the winner is stored
+    pub fn voting_is_closed(&self, now: DateTime<Utc>) -> bool {
+        self.finalized_at.is_some() || self.voting_deadline.is_some_and(|deadline| deadline <= now)
+    }
 }
 
 /// Lunch of a group with name, usable for listing available lunches
@@ -18,13 +39,122 @@ pub struct LunchWithGroup {
     pub group_id: Uuid,
     pub group_name: String,
     pub group_picture: Option<String>,
+    pub start_time: NaiveTime,
+    pub meeting_point: Option<String>,
+    pub voting_mode: VotingMode,
+    /// Menu chosen after the deadline, None when nobody voted or the lunch is not finalized yet
+    pub winner_menu_id: Option<Uuid>,
 }
 
 /// Structure passed to the repository for creating a lunch
-#[derive(sqlx::FromRow, Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct LunchCreate {
     pub date: NaiveDate,
     pub group_id: Uuid,
+    pub start_time: NaiveTime,
+    pub meeting_point: Option<String>,
+    pub voting_deadline: Option<DateTime<Utc>>,
+    pub tie_break: TieBreak,
+    pub longitude: Option<f64>,
+    pub latitude: Option<f64>,
+    pub voting_mode: VotingMode,
+    /// Menus nominated to the lunch right away
+    pub candidates: Option<LunchCandidatesCreate>,
+}
+
+/// Menus of the day of the lunch nominated when the lunch is created, e.g. the 3 cheapest ones
+#[derive(Debug, Clone, PartialEq)]
+pub struct LunchCandidatesCreate {
+    pub order_by: DbRestaurantOrderingMethod,
+    pub count: i64,
+}
+
+/// How the winner is chosen from menus with the same number of votes
+#[derive(sqlx::Type, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[sqlx(type_name = "tie_break", rename_all = "snake_case")]
+pub enum TieBreak {
+    #[default]
+    Random,
+    /// Menu with the lowest average price of its items
+    Cheapest,
+    /// Restaurant closest to the location of the lunch, random without the location
+    Closest,
+}
+
+/// Menu voted for in a lunch, candidate for the winner
+#[derive(sqlx::FromRow, Debug, Clone, PartialEq)]
+pub struct WinnerCandidate {
+    pub menu_id: Uuid,
+    /// Score of the menu by the voting mode of the lunch
+    pub votes: i64,
+    pub average_price: Option<f64>,
+    pub longitude: Option<f64>,
+    pub latitude: Option<f64>,
+}
+
+/// Mean radius of the Earth in meters
+const EARTH_RADIUS: f64 = 6_371_000.0;
+
+impl TieBreak {
+    /// Picks the menu with the most votes, ties are broken by the method. Menus still tied after
+    /// that (and all ties of the random method) are chosen by the given random number.
+    ///
+    /// # Params
+    /// - candidates: voted menus of the lunch
+    /// - location: longitude and latitude of the lunch
+    /// - random: any random number
+    ///
+    /// # Returns
+    /// - Some(menu_id): of the winning menu
+    /// - None: when nobody voted
+    pub fn pick(
+        &self,
+        candidates: &[WinnerCandidate],
+        location: Option<(f64, f64)>,
+        random: u64,
+    ) -> Option<Uuid> {
+        let most_votes = candidates.iter().map(|candidate| candidate.votes).max()?;
+        let mut tied: Vec<&WinnerCandidate> = candidates
+            .iter()
+            .filter(|candidate| candidate.votes == most_votes)
+            .collect();
+
+        let key = |candidate: &WinnerCandidate| match (self, location) {
+            (TieBreak::Cheapest, _) => candidate.average_price,
+            (TieBreak::Closest, Some(location)) => Some(distance(
+                location,
+                (candidate.longitude?, candidate.latitude?),
+            )),
+            (TieBreak::Closest, None) | (TieBreak::Random, _) => None,
+        };
+
+        // Menus without the price or the location lose, all stay tied when none of them has it
+        if let Some(best) = tied
+            .iter()
+            .filter_map(|candidate| key(candidate))
+            .reduce(f64::min)
+        {
+            tied.retain(|candidate| key(candidate) == Some(best));
+        }
+
+        // Random choice does not depend on the order of the candidates from the database
+        tied.sort_by_key(|candidate| candidate.menu_id);
+        let index = (random % tied.len() as u64) as usize;
+
+        Some(tied[index].menu_id)
+    }
+}
+
+/// Great-circle distance of two locations (longitude, latitude) in meters
+fn distance((long_a, lat_a): (f64, f64), (long_b, lat_b): (f64, f64)) -> f64 {
+    let (lat_a, lat_b) = (lat_a.to_radians(), lat_b.to_radians());
+    let delta_lat = lat_b - lat_a;
+    let delta_long = (long_b - long_a).to_radians();
+
+    let a = (delta_lat / 2.0).sin().powi(2)
+        + lat_a.cos() * lat_b.cos() * (delta_long / 2.0).sin().powi(2);
+
+    2.0 * EARTH_RADIUS * a.sqrt().asin()
 }
 
 /// Structure passed to the repository for removing a lunch
@@ -54,3 +184,48 @@ impl LunchGetById {
         Self { id: *id }
     }
 }
+
+/// Answer of a member whether they are coming to a lunch
+#[derive(sqlx::Type, Debug, Clone, Copy, PartialEq, Eq)]
+#[sqlx(type_name = "rsvp_status", rename_all = "snake_case")]
+pub enum RsvpStatus {
+    Going,
+    NotGoing,
+    Maybe,
+}
+
+/// Answer of a member of the group to a lunch
+#[derive(sqlx::FromRow, Debug, Clone, PartialEq, Eq)]
+pub struct LunchRsvp {
+    pub lunch_id: Uuid,
+    pub user_id: Uuid,
+    pub status: RsvpStatus,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Structure passed to the repository for answering whether the user is coming, replaces the
+/// previous answer of the user
+#[derive(Debug, Clone)]
+pub struct LunchRsvpCreate {
+    pub lunch_id: Uuid,
+    pub user_id: Uuid,
+    pub status: RsvpStatus,
+}
+
+/// Menu nominated to a lunch, shown in the lunch even without votes
+#[derive(sqlx::FromRow, Debug, Clone, PartialEq, Eq)]
+pub struct LunchCandidate {
+    pub lunch_id: Uuid,
+    pub menu_id: Uuid,
+    /// Member who nominated the menu, None for menus nominated when the lunch was created
+    pub user_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Structure passed to the repository for nominating a menu to a lunch
+#[derive(Debug, Clone)]
+pub struct LunchCandidateCreate {
+    pub lunch_id: Uuid,
+    pub menu_id: Uuid,
+    pub user_id: Uuid,
+}
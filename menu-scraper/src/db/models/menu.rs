@@ -1,4 +1,4 @@
-use crate::db::common::query_parameters::DbOrder;
+use crate::db::common::query_parameters::{DbOrder, DbSearchQuery};
 use chrono::{DateTime, NaiveDate, Utc};
 use sqlx::postgres::{PgHasArrayType, PgTypeInfo};
 use uuid::Uuid;
@@ -22,6 +22,11 @@ pub struct MenuItem {
     pub size: String,
     pub is_soup: bool,
     pub menu_id: Uuid,
+    /// Numbers of EU allergens (1-14) sorted ascending
+    pub allergens: Vec<i32>,
+    pub is_vegetarian: bool,
+    pub is_vegan: bool,
+    pub is_gluten_free: bool,
 }
 
 impl PgHasArrayType for MenuItem {
@@ -45,6 +50,30 @@ pub struct MenuItemCreate {
     pub price: i32,
     pub size: String,
     pub is_soup: bool,
+    pub allergens: Vec<i32>,
+    /// Dietary tags are filled in by the scraping service
+    pub is_vegetarian: bool,
+    pub is_vegan: bool,
+    pub is_gluten_free: bool,
+}
+
+/// Result of storing a scraped menu, menu and its unchanged items keep their ids when the menu
+/// already existed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MenuUpsertResult {
+    pub menu: Menu,
+    /// Menu did not exist before and was inserted
+    pub created: bool,
+    pub items_inserted: usize,
+    pub items_updated: usize,
+    pub items_deleted: usize,
+}
+
+impl MenuUpsertResult {
+    /// Checks if anything in the database was changed
+    pub fn is_changed(&self) -> bool {
+        self.created || self.items_inserted + self.items_updated + self.items_deleted > 0
+    }
 }
 
 /// Structure passed to the repository when trying to delete a menu
@@ -80,12 +109,18 @@ pub struct MenuReadMany {
     pub date_to: NaiveDate,
     pub order_by: DbRestaurantOrderingMethod,
     pub restaurant_id: Option<Uuid>,
+    /// Menus containing an item with any of these allergens are left out
+    pub excluded_allergens: Vec<i32>,
+    /// Only menus with a vegetarian main course (not a soup)
+    pub vegetarian_only: bool,
+    /// Only menus with an item matching the query
+    pub search: Option<DbSearchQuery>,
     pub limit: Option<i64>,
     pub offset: Option<i64>,
 }
 
 /// Methods of ordering for retrieved restaurants/menus
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum DbRestaurantOrderingMethod {
     Price(DbOrder),
     Range(DbOrder, (f64, f64)), // Location of the user - longitude + latitude
@@ -112,6 +147,8 @@ pub struct MenuWithRestaurant {
     pub menu_id: Uuid,
     pub date: NaiveDate,
     pub items: Vec<MenuItem>,
+    /// Ids of items matching the search query, empty without search
+    pub matching_item_ids: Vec<Uuid>,
 }
 
 /// Structure passed to the repository for getting number of menus, used for pagination
@@ -119,6 +156,10 @@ pub struct MenuWithRestaurant {
 pub struct MenuGetCount {
     pub date_from: NaiveDate,
     pub date_to: NaiveDate,
+    pub restaurant_id: Option<Uuid>,
+    pub excluded_allergens: Vec<i32>,
+    pub vegetarian_only: bool,
+    pub search: Option<DbSearchQuery>,
 }
 
 #[derive(sqlx::FromRow, Debug, Clone, PartialEq, Eq)]
@@ -1,8 +1,16 @@
-pub use {group::*, lunch::*, menu::*, restaurant::*, user::*, vote::*};
+pub use {
+    api_token::*, calendar_feed::*, group::*, group_invitation::*, login_attempt::*, lunch::*,
+    menu::*, restaurant::*, scrape_run::*, user::*, vote::*,
+};
 
+pub mod api_token;
+pub mod calendar_feed;
 pub mod group;
+pub mod group_invitation;
+pub mod login_attempt;
 pub mod lunch;
 pub mod menu;
 pub mod restaurant;
+pub mod scrape_run;
 pub mod user;
 pub mod vote;
@@ -1,7 +1,7 @@
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
-#[derive(sqlx::FromRow, Debug, Clone, PartialEq, Eq)]
+#[derive(sqlx::FromRow, Debug, Clone, PartialEq)]
 pub struct Restaurant {
     pub id: Uuid,
     pub name: String,
@@ -21,6 +21,10 @@ pub struct Restaurant {
     pub saturday_open: Option<String>,
     pub sunday_open: Option<String>,
     pub lunch_served: Option<String>,
+    pub longitude: Option<f64>,
+    pub latitude: Option<f64>,
+    /// Last failed geocoding of the address
+    pub geocode_failed_at: Option<DateTime<Utc>>,
     pub deleted_at: Option<DateTime<Utc>>,
 }
 
@@ -58,6 +62,8 @@ pub struct RestaurantCreate {
     pub lunch_served: Option<String>,
     pub longitude: Option<f64>,
     pub latitude: Option<f64>,
+    /// Set by the scraping service when geocoding of the address failed
+    pub geocode_failed_at: Option<DateTime<Utc>>,
 }
 
 /// Structure passed to the repository when trying to update a Restaurant
@@ -73,14 +79,19 @@ pub struct RestaurantUpdate {
     pub phone_number: Option<String>,
     pub website: Option<String>,
     pub email: Option<String>,
-    pub monday_open: Option<String>,
-    pub tuesday_open: Option<String>,
-    pub wednesday_open: Option<String>,
-    pub thursday_open: Option<String>,
-    pub friday_open: Option<String>,
-    pub saturday_open: Option<String>,
-    pub sunday_open: Option<String>,
-    pub lunch_served: Option<String>,
+    /// Opening hours are replaced as a whole, Some(None) clears the stored value
+    pub monday_open: Option<Option<String>>,
+    pub tuesday_open: Option<Option<String>>,
+    pub wednesday_open: Option<Option<String>>,
+    pub thursday_open: Option<Option<String>>,
+    pub friday_open: Option<Option<String>>,
+    pub saturday_open: Option<Option<String>>,
+    pub sunday_open: Option<Option<String>>,
+    pub lunch_served: Option<Option<String>>,
+    pub longitude: Option<f64>,
+    pub latitude: Option<f64>,
+    /// Some(None) clears the failure once the restaurant is geocoded
+    pub geocode_failed_at: Option<Option<DateTime<Utc>>>,
 }
 
 /// Structure passed to the repository when trying to delete a Restaurant
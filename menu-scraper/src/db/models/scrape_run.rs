@@ -0,0 +1,99 @@
+use chrono::{DateTime, Utc};
+use sqlx::error::BoxDynError;
+use sqlx::postgres::types::PgRecordDecoder;
+use sqlx::postgres::{PgHasArrayType, PgTypeInfo, PgValueRef};
+use sqlx::{Decode, Postgres};
+use uuid::Uuid;
+
+/// One run of the scraper with its statistics and failures
+#[derive(sqlx::FromRow, Debug, Clone, PartialEq, Eq)]
+pub struct ScrapeRun {
+    pub id: Uuid,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub restaurants_seen: i32,
+    pub menus_inserted: i32,
+    pub menus_updated: i32,
+    pub errors: Vec<ScrapeRunError>,
+}
+
+/// Failure of a restaurant (or of a whole source, when restaurant is None) during a scrape run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScrapeRunError {
+    pub id: Uuid,
+    pub scrape_run_id: Uuid,
+    pub source: String,
+    pub restaurant: Option<String>,
+    pub message: String,
+}
+
+impl sqlx::Type<Postgres> for ScrapeRunError {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::with_name("ScrapeRunError")
+    }
+}
+
+/// Decoded by hand, the derived decoding of records does not accept the optional restaurant
+impl<'r> Decode<'r, Postgres> for ScrapeRunError {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        let mut decoder = PgRecordDecoder::new(value)?;
+
+        Ok(Self {
+            id: decoder.try_decode()?,
+            scrape_run_id: decoder.try_decode()?,
+            source: decoder.try_decode()?,
+            restaurant: decoder.try_decode()?,
+            message: decoder.try_decode()?,
+        })
+    }
+}
+
+impl PgHasArrayType for ScrapeRunError {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::with_name("_ScrapeRunError")
+    }
+}
+
+/// Structure passed to the repository for storing a finished scrape run
+#[derive(Debug, Clone)]
+pub struct ScrapeRunCreate {
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub restaurants_seen: i32,
+    pub menus_inserted: i32,
+    pub menus_updated: i32,
+    pub errors: Vec<ScrapeRunErrorCreate>,
+}
+
+/// Structure passed to the repository for storing a failure of a scrape run
+#[derive(Debug, Clone)]
+pub struct ScrapeRunErrorCreate {
+    pub source: String,
+    pub restaurant: Option<String>,
+    pub message: String,
+}
+
+/// Structure passed to the repository when trying to find a scrape run by its id
+#[derive(Debug, Clone)]
+pub struct ScrapeRunGetById {
+    pub id: Uuid,
+}
+
+impl ScrapeRunGetById {
+    #[inline]
+    pub const fn new(id: &Uuid) -> Self {
+        Self { id: *id }
+    }
+}
+
+/// Structure passed to the repository for getting the latest scrape runs
+#[derive(Debug, Clone)]
+pub struct ScrapeRunGetMany {
+    pub limit: i64,
+}
+
+/// Structure for manipulating with only ID of the scrape run
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct ScrapeRunId {
+    pub id: Uuid,
+}
@@ -1,5 +1,7 @@
 use crate::db::models::MenuItem;
 use chrono::{DateTime, NaiveDate, Utc};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use uuid::Uuid;
 
 /// Vote of a user in some lunch
@@ -10,12 +12,15 @@ pub struct Vote {
     pub user_id: Uuid,
     pub lunch_id: Uuid,
     pub deleted_at: Option<DateTime<Utc>>,
+    /// Preference of the menu in ranked-choice voting, 1 is the first choice
+    pub rank: Option<i32>,
 }
 
 #[derive(sqlx::Type, Debug, Clone, PartialEq, Eq)]
 pub struct VotePreview {
     pub id: Uuid,
     pub user_id: Uuid,
+    pub rank: Option<i32>,
 }
 
 /// Structure passed to the repository for creating a vote
@@ -26,10 +31,12 @@ pub struct VoteCreate {
     pub lunch_id: Uuid,
 }
 
-/// Structure passed to the repository for removing a vote
+/// Structure passed to the repository for withdrawing a vote of the user in the lunch
 #[derive(sqlx::FromRow, Debug, Clone, PartialEq, Eq)]
 pub struct VoteDelete {
     pub id: Uuid,
+    pub user_id: Uuid,
+    pub lunch_id: Uuid,
 }
 
 /// Structure passed to the repository for getting votes for some lunch
@@ -38,6 +45,12 @@ pub struct VoteGetMany {
     pub lunch_id: Uuid,
 }
 
+/// Structure passed to the repository for getting votes of several lunches at once
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VoteGetManyByLunches {
+    pub lunch_ids: Vec<Uuid>,
+}
+
 /// Structure passed to the repository when trying to find a vote by its id
 #[derive(Debug, Clone)]
 pub struct VoteGetById {
@@ -51,6 +64,65 @@ impl VoteGetById {
     }
 }
 
+/// Number of vetoes a member can cast in one lunch
+pub const VETO_LIMIT: i64 = 2;
+
+/// Veto of a user against a menu in some lunch, or against the whole restaurant of the menu
+#[derive(sqlx::FromRow, Debug, Clone, PartialEq, Eq)]
+pub struct Veto {
+    pub id: Uuid,
+    pub menu_id: Uuid,
+    /// Set when the whole restaurant is vetoed
+    pub restaurant_id: Option<Uuid>,
+    pub user_id: Uuid,
+    pub lunch_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+/// Structure passed to the repository for vetoing a menu
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VetoCreate {
+    pub menu_id: Uuid,
+    pub user_id: Uuid,
+    pub lunch_id: Uuid,
+    /// Vetoes the whole restaurant of the menu instead of the menu
+    pub restaurant: bool,
+}
+
+/// Structure passed to the repository for withdrawing a veto of the user in the lunch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VetoDelete {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub lunch_id: Uuid,
+}
+
+/// Structure passed to the repository when trying to find a veto by its id
+#[derive(Debug, Clone)]
+pub struct VetoGetById {
+    pub id: Uuid,
+}
+
+impl VetoGetById {
+    #[inline]
+    pub const fn new(id: &Uuid) -> Self {
+        Self { id: *id }
+    }
+}
+
+/// Veto with the name of the vetoed restaurant (usable in the lunch overview)
+#[derive(sqlx::FromRow, Debug, Clone, PartialEq, Eq)]
+pub struct VetoPreview {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub menu_id: Uuid,
+    pub restaurant_id: Uuid,
+    pub name: String,
+    /// Whether the whole restaurant is vetoed
+    pub restaurant: bool,
+}
+
 /// Structure for getting menu with preview of the corresponding restaurant and votes (usable in
 /// showing detail of a lunch)
 #[derive(sqlx::FromRow, Debug, Clone, PartialEq, Eq)]
@@ -67,3 +139,160 @@ pub struct MenuWithRestaurantAndVotes {
     pub items: Vec<MenuItem>,
     pub votes: Vec<VotePreview>,
 }
+
+/// Menu with votes like `MenuWithRestaurantAndVotes`, together with the lunch it is voted in
+#[derive(sqlx::FromRow, Debug, Clone, PartialEq, Eq)]
+pub struct LunchMenuWithRestaurantAndVotes {
+    pub lunch_id: Uuid,
+    pub restaurant_id: Uuid,
+    pub name: String,
+    pub street: String,
+    pub house_number: String,
+    pub zip_code: String,
+    pub city: String,
+    pub picture: Option<String>,
+    pub menu_id: Uuid,
+    pub date: NaiveDate,
+    pub items: Vec<MenuItem>,
+    pub votes: Vec<VotePreview>,
+}
+
+impl From<LunchMenuWithRestaurantAndVotes> for MenuWithRestaurantAndVotes {
+    fn from(menu: LunchMenuWithRestaurantAndVotes) -> Self {
+        Self {
+            restaurant_id: menu.restaurant_id,
+            name: menu.name,
+            street: menu.street,
+            house_number: menu.house_number,
+            zip_code: menu.zip_code,
+            city: menu.city,
+            picture: menu.picture,
+            menu_id: menu.menu_id,
+            date: menu.date,
+            items: menu.items,
+            votes: menu.votes,
+        }
+    }
+}
+
+/// How members vote in a lunch
+#[derive(sqlx::Type, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[sqlx(type_name = "voting_mode", rename_all = "snake_case")]
+pub enum VotingMode {
+    /// One menu per member, voting for another menu replaces the vote
+    #[default]
+    Plurality,
+    /// Members vote for all menus they are fine with
+    Approval,
+    /// Members rank menus, the winner is chosen by instant runoff
+    Ranked,
+}
+
+/// Vote as counted by the tally
+#[derive(sqlx::FromRow, Debug, Clone, PartialEq, Eq)]
+pub struct Ballot {
+    pub menu_id: Uuid,
+    pub user_id: Uuid,
+    pub rank: Option<i32>,
+}
+
+impl Ballot {
+    /// Votes of all menus of the lunch
+    pub fn from_menus(menus: &[MenuWithRestaurantAndVotes]) -> Vec<Ballot> {
+        menus
+            .iter()
+            .flat_map(|menu| {
+                menu.votes.iter().map(|vote| Ballot {
+                    menu_id: menu.menu_id,
+                    user_id: vote.user_id,
+                    rank: vote.rank,
+                })
+            })
+            .collect()
+    }
+}
+
+impl VotingMode {
+    /// Scores of the voted menus, the winner has the highest score
+    ///
+    /// Plurality and approval voting count votes of the menu. Ranked-choice voting counts the
+    /// first choices and eliminates the menus with the fewest of them until a menu has
+    /// the majority, votes of eliminated menus go to the next choice of the member. Eliminated
+    /// menus keep the score of the round they were eliminated in, which is lower than scores of
+    /// all menus left.
+    pub fn tally(&self, ballots: &[Ballot]) -> HashMap<Uuid, i64> {
+        match self {
+            VotingMode::Plurality | VotingMode::Approval => {
+                let mut scores = HashMap::new();
+                for ballot in ballots {
+                    *scores.entry(ballot.menu_id).or_insert(0) += 1;
+                }
+                scores
+            }
+            VotingMode::Ranked => instant_runoff(ballots),
+        }
+    }
+
+    /// Voted menus with their scores of the [`tally`](Self::tally), from the highest score
+    pub fn rank(
+        &self,
+        menus: Vec<MenuWithRestaurantAndVotes>,
+    ) -> Vec<(MenuWithRestaurantAndVotes, i64)> {
+        let scores = self.tally(&Ballot::from_menus(&menus));
+        let mut ranked: Vec<_> = menus
+            .into_iter()
+            .map(|menu| {
+                let score = scores.get(&menu.menu_id).copied().unwrap_or(0);
+                (menu, score)
+            })
+            .collect();
+        ranked.sort_by_key(|(_, score)| Reverse(*score));
+
+        ranked
+    }
+}
+
+fn instant_runoff(ballots: &[Ballot]) -> HashMap<Uuid, i64> {
+    // Choices of each member, the first choice first. Votes without the rank are the last choices.
+    let mut choices: BTreeMap<Uuid, Vec<&Ballot>> = BTreeMap::new();
+    for ballot in ballots {
+        choices.entry(ballot.user_id).or_default().push(ballot);
+    }
+    let choices: Vec<Vec<Uuid>> = choices
+        .into_values()
+        .map(|mut member_ballots| {
+            member_ballots.sort_by_key(|ballot| (ballot.rank.is_none(), ballot.rank));
+            member_ballots
+                .into_iter()
+                .map(|ballot| ballot.menu_id)
+                .collect()
+        })
+        .collect();
+
+    let mut left: BTreeSet<Uuid> = ballots.iter().map(|ballot| ballot.menu_id).collect();
+    let mut scores = HashMap::new();
+
+    while !left.is_empty() {
+        let mut round: HashMap<Uuid, i64> = left.iter().map(|menu_id| (*menu_id, 0)).collect();
+        let mut counted = 0;
+        for member_choices in &choices {
+            if let Some(choice) = member_choices.iter().find(|menu_id| left.contains(menu_id)) {
+                *round.entry(*choice).or_insert(0) += 1;
+                counted += 1;
+            }
+        }
+
+        let most = round.values().copied().max().unwrap_or(0);
+        let fewest = round.values().copied().min().unwrap_or(0);
+        scores.extend(round.iter().map(|(menu_id, score)| (*menu_id, *score)));
+
+        // Menus tied for the fewest votes are eliminated together, all of them are left when
+        // every menu has the same number of votes
+        if most * 2 > counted || most == fewest {
+            break;
+        }
+        left.retain(|menu_id| round.get(menu_id) != Some(&fewest));
+    }
+
+    scores
+}
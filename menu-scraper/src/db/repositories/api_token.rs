@@ -0,0 +1,204 @@
+use crate::db::common::error::{
+    BusinessLogicError, BusinessLogicErrorKind, DbError, DbResultMultiple, DbResultSingle,
+};
+use crate::db::common::{DbCreate, DbDelete, DbReadMany, DbReadOne, DbRepository, PoolHandler};
+use crate::db::models::{
+    ApiToken, ApiTokenCreate, ApiTokenDelete, ApiTokenGetById, ApiTokenGetByUser, UserGetById,
+};
+use crate::db::repositories::UserRepository;
+use async_trait::async_trait;
+use sqlx::{Postgres, Transaction};
+
+#[derive(Clone)]
+pub struct ApiTokenRepository {
+    pool_handler: PoolHandler,
+}
+
+impl ApiTokenRepository {
+    /// Function which retrieves a token by its id, usable within a transaction
+    ///
+    /// # Params
+    /// - params: structure containing the id of the token
+    /// - transaction_handle mutable reference to an ongoing transaction
+    ///
+    /// # Returns
+    /// - Ok(token): on successful connection and retrieval
+    /// - Err(_): otherwise
+    pub async fn get_api_token<'a>(
+        params: &ApiTokenGetById,
+        transaction_handle: &mut Transaction<'a, Postgres>,
+    ) -> DbResultSingle<Option<ApiToken>> {
+        let token = sqlx::query_as!(
+            ApiToken,
+            r#"
+            SELECT *
+            FROM "ApiToken"
+            WHERE id = $1
+            "#,
+            params.id
+        )
+        .fetch_optional(transaction_handle.as_mut())
+        .await?;
+
+        Ok(token)
+    }
+
+    /// Function which checks if the token is correct (existing and not revoked)
+    ///
+    /// # Params
+    /// - token: optional token retrieved from the database
+    ///
+    /// # Returns
+    /// - Ok(token): when the token exists and is not revoked
+    /// - Err(DbError): with appropriate error description otherwise
+    pub fn api_token_is_correct(token: Option<ApiToken>) -> DbResultSingle<ApiToken> {
+        match token {
+            Some(
+                token @ ApiToken {
+                    deleted_at: None, ..
+                },
+            ) => Ok(token),
+            Some(_) => Err(DbError::from(BusinessLogicError::new(
+                BusinessLogicErrorKind::ApiTokenRevoked,
+            ))),
+            None => Err(DbError::from(BusinessLogicError::new(
+                BusinessLogicErrorKind::ApiTokenDoesNotExist,
+            ))),
+        }
+    }
+}
+
+#[async_trait]
+impl DbRepository for ApiTokenRepository {
+    #[inline]
+    fn new(pool_handler: PoolHandler) -> Self {
+        Self { pool_handler }
+    }
+}
+
+#[async_trait]
+impl DbReadOne<ApiTokenGetById, ApiToken> for ApiTokenRepository {
+    /// Gets a token which is not revoked and whose owner is not deleted
+    async fn read_one(&self, params: &ApiTokenGetById) -> DbResultSingle<ApiToken> {
+        let mut tx = self.pool_handler.pool.begin().await?;
+
+        let token = Self::get_api_token(params, &mut tx).await?;
+        let token = Self::api_token_is_correct(token)?;
+
+        let user = UserRepository::get_user(&UserGetById::new(&token.user_id), &mut tx).await?;
+        UserRepository::user_is_correct(user)?;
+
+        tx.commit().await?;
+
+        Ok(token)
+    }
+}
+
+#[async_trait]
+impl DbCreate<ApiTokenCreate, ApiToken> for ApiTokenRepository {
+    /// Creates a new token of a user
+    async fn create(&self, data: &ApiTokenCreate) -> DbResultSingle<ApiToken> {
+        let mut tx = self.pool_handler.pool.begin().await?;
+
+        let user = UserRepository::get_user(&UserGetById::new(&data.user_id), &mut tx).await?;
+        UserRepository::user_is_correct(user)?;
+
+        let token = sqlx::query_as!(
+            ApiToken,
+            r#"
+            INSERT INTO "ApiToken" (user_id, name, token_hash)
+            VALUES ($1, $2, $3)
+            RETURNING *
+            "#,
+            data.user_id,
+            data.name,
+            data.token_hash
+        )
+        .fetch_one(tx.as_mut())
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(token)
+    }
+}
+
+#[async_trait]
+impl DbReadMany<ApiTokenGetByUser, ApiToken> for ApiTokenRepository {
+    /// Gets tokens of a user which are not revoked, the newest first
+    async fn read_many(&self, params: &ApiTokenGetByUser) -> DbResultMultiple<ApiToken> {
+        let tokens = sqlx::query_as!(
+            ApiToken,
+            r#"
+            SELECT *
+            FROM "ApiToken"
+            WHERE user_id = $1 AND deleted_at IS NULL
+            ORDER BY created_at DESC
+            "#,
+            params.user_id
+        )
+        .fetch_all(&*self.pool_handler.pool)
+        .await?;
+
+        Ok(tokens)
+    }
+}
+
+#[async_trait]
+impl DbDelete<ApiTokenDelete, ApiToken> for ApiTokenRepository {
+    /// Revokes a token of the user
+    async fn delete(&self, params: &ApiTokenDelete) -> DbResultMultiple<ApiToken> {
+        let mut tx = self.pool_handler.pool.begin().await?;
+
+        let token = Self::get_api_token(&ApiTokenGetById::new(&params.id), &mut tx).await?;
+        let token = Self::api_token_is_correct(token)?;
+
+        // Tokens of other users are not revealed
+        if token.user_id != params.user_id {
+            return Err(DbError::from(BusinessLogicError::new(
+                BusinessLogicErrorKind::ApiTokenDoesNotExist,
+            )));
+        }
+
+        let revoked_token = sqlx::query_as!(
+            ApiToken,
+            r#"
+            UPDATE "ApiToken"
+            SET deleted_at = now()
+            WHERE id = $1
+            RETURNING *
+            "#,
+            params.id
+        )
+        .fetch_one(tx.as_mut())
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(vec![revoked_token])
+    }
+}
+
+#[async_trait]
+pub trait ApiTokenRepositoryMarkUsed {
+    /// Stores the time of the last successful authentication by the token
+    async fn mark_used(&self, params: &ApiTokenGetById) -> DbResultSingle<()>;
+}
+
+#[async_trait]
+impl ApiTokenRepositoryMarkUsed for ApiTokenRepository {
+    async fn mark_used(&self, params: &ApiTokenGetById) -> DbResultSingle<()> {
+        sqlx::query!(
+            r#"
+            UPDATE "ApiToken"
+            SET last_used_at = now()
+            WHERE id = $1
+            "#,
+            params.id
+        )
+        .execute(&*self.pool_handler.pool)
+        .await?;
+
+        Ok(())
+    }
+}
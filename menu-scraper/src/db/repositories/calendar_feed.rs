@@ -0,0 +1,133 @@
+use crate::db::common::error::{
+    BusinessLogicError, BusinessLogicErrorKind, DbError, DbResultSingle,
+};
+use crate::db::common::{DbCreate, DbReadOne, DbRepository, PoolHandler};
+use crate::db::models::{
+    CalendarFeed, CalendarFeedCreate, CalendarFeedGetByToken, CalendarFeedGetByUser, UserGetById,
+};
+use crate::db::repositories::UserRepository;
+use async_trait::async_trait;
+
+#[derive(Clone)]
+pub struct CalendarFeedRepository {
+    pool_handler: PoolHandler,
+}
+
+impl CalendarFeedRepository {
+    /// Function which checks if the feed exists
+    ///
+    /// # Params
+    /// - feed: optional feed retrieved from the database
+    ///
+    /// # Returns
+    /// - Ok(feed): when the feed exists
+    /// - Err(DbError): with appropriate error description otherwise
+    pub fn calendar_feed_is_correct(feed: Option<CalendarFeed>) -> DbResultSingle<CalendarFeed> {
+        match feed {
+            Some(feed) => Ok(feed),
+            None => Err(DbError::from(BusinessLogicError::new(
+                BusinessLogicErrorKind::CalendarFeedDoesNotExist,
+            ))),
+        }
+    }
+}
+
+#[async_trait]
+impl DbRepository for CalendarFeedRepository {
+    #[inline]
+    fn new(pool_handler: PoolHandler) -> Self {
+        Self { pool_handler }
+    }
+}
+
+#[async_trait]
+impl DbReadOne<CalendarFeedGetByToken, CalendarFeed> for CalendarFeedRepository {
+    /// Gets a feed by its secret token, feeds of deleted users do not exist
+    async fn read_one(&self, params: &CalendarFeedGetByToken) -> DbResultSingle<CalendarFeed> {
+        let mut tx = self.pool_handler.pool.begin().await?;
+
+        let feed = sqlx::query_as!(
+            CalendarFeed,
+            r#"
+            SELECT *
+            FROM "CalendarFeed"
+            WHERE token = $1
+            "#,
+            params.token
+        )
+        .fetch_optional(tx.as_mut())
+        .await?;
+        let feed = Self::calendar_feed_is_correct(feed)?;
+
+        let user = UserRepository::get_user(&UserGetById::new(&feed.user_id), &mut tx).await?;
+        if UserRepository::user_is_correct(user).is_err() {
+            return Err(DbError::from(BusinessLogicError::new(
+                BusinessLogicErrorKind::CalendarFeedDoesNotExist,
+            )));
+        }
+
+        tx.commit().await?;
+
+        Ok(feed)
+    }
+}
+
+#[async_trait]
+impl DbCreate<CalendarFeedCreate, CalendarFeed> for CalendarFeedRepository {
+    /// Creates a feed of a user, the previous feed of the user stops working
+    async fn create(&self, data: &CalendarFeedCreate) -> DbResultSingle<CalendarFeed> {
+        let mut tx = self.pool_handler.pool.begin().await?;
+
+        let user = UserRepository::get_user(&UserGetById::new(&data.user_id), &mut tx).await?;
+        UserRepository::user_is_correct(user)?;
+
+        let feed = sqlx::query_as!(
+            CalendarFeed,
+            r#"
+            INSERT INTO "CalendarFeed" (user_id, token)
+            VALUES ($1, $2)
+            ON CONFLICT (user_id) DO UPDATE SET token = EXCLUDED.token, created_at = now()
+            RETURNING *
+            "#,
+            data.user_id,
+            data.token
+        )
+        .fetch_one(tx.as_mut())
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(feed)
+    }
+}
+
+#[async_trait]
+pub trait CalendarFeedRepositoryGetByUser {
+    /// Gets the feed of a user, None when the user has not created any
+    async fn get_by_user(
+        &self,
+        params: &CalendarFeedGetByUser,
+    ) -> DbResultSingle<Option<CalendarFeed>>;
+}
+
+#[async_trait]
+impl CalendarFeedRepositoryGetByUser for CalendarFeedRepository {
+    async fn get_by_user(
+        &self,
+        params: &CalendarFeedGetByUser,
+    ) -> DbResultSingle<Option<CalendarFeed>> {
+        let feed = sqlx::query_as!(
+            CalendarFeed,
+            r#"
+            SELECT *
+            FROM "CalendarFeed"
+            WHERE user_id = $1
+            "#,
+            params.user_id
+        )
+        .fetch_optional(&*self.pool_handler.pool)
+        .await?;
+
+        Ok(feed)
+    }
+}
@@ -6,7 +6,8 @@ use crate::db::common::{
 };
 use crate::db::models::{
     GetGroupUserByIds, Group, GroupCreate, GroupDelete, GroupGetById, GroupGetGroupsByUser,
-    GroupPreview, GroupUpdate, GroupUser, GroupUserCreate, GroupUserDelete, UserGetById,
+    GroupMember, GroupOwnershipTransfer, GroupPreview, GroupRole, GroupUpdate, GroupUser,
+    GroupUserCheckRole, GroupUserCreate, GroupUserDelete, GroupUserRoleUpdate, UserGetById,
     UserPreview,
 };
 use crate::db::repositories::UserRepository;
@@ -88,7 +89,7 @@ impl GroupRepository {
         let group_user = sqlx::query_as!(
             GroupUser,
             r#"
-            SELECT *
+            SELECT G.id, G.user_id, G.group_id, G.deleted_at, G.role AS "role: GroupRole"
             FROM "GroupUsers" G
             WHERE G.user_id = $1 AND G.group_id = $2
             "#,
@@ -132,6 +133,29 @@ impl GroupRepository {
         user_id: &Uuid,
         group_id: &Uuid,
     ) -> DbResultSingle<()> {
+        Self::check_user_role_tx(tx, user_id, group_id, GroupRole::Member).await?;
+
+        Ok(())
+    }
+
+    /// Checks if user is correct, group is correct and user has at least the required role in
+    /// the group, usable within a transaction
+    ///
+    /// # Params
+    /// - tx: mutable reference to an ongoing transaction
+    /// - user_id: id of the checked user
+    /// - group_id: id of the group
+    /// - required: the least role the user must have
+    ///
+    /// # Returns
+    /// - Ok(role): the role of the user in the group
+    /// - Err(DbError): when the user is not a member or has a lower role
+    pub async fn check_user_role_tx<'a>(
+        tx: &mut Transaction<'a, Postgres>,
+        user_id: &Uuid,
+        group_id: &Uuid,
+        required: GroupRole,
+    ) -> DbResultSingle<GroupRole> {
         // Check that user is correct
         let user = UserRepository::get_user(&UserGetById::new(user_id), tx).await?;
         UserRepository::user_is_correct(user)?;
@@ -143,14 +167,79 @@ impl GroupRepository {
         let group_user =
             Self::get_group_user(&GetGroupUserByIds::new(user_id, group_id), tx).await?;
 
-        // Author is implicitly in the group
-        if group.author_id == *user_id || Self::group_user_is_correct(group_user).is_ok() {
-            return Ok(());
+        // Author is always the owner of the group
+        let role = if group.author_id == *user_id {
+            GroupRole::Owner
+        } else {
+            Self::group_user_is_correct(group_user)
+                .map_err(|_| {
+                    DbError::from(BusinessLogicError::new(
+                        BusinessLogicErrorKind::UserNotMemberOfGroup,
+                    ))
+                })?
+                .role
+        };
+
+        if role < required {
+            return Err(DbError::from(BusinessLogicError::new(
+                BusinessLogicErrorKind::UserNotGroupAdmin,
+            )));
         }
 
-        Err(DbError::from(BusinessLogicError::new(
-            BusinessLogicErrorKind::UserNotMemberOfGroup,
-        )))
+        Ok(role)
+    }
+
+    /// Adds a user to a group as a member, a user who left is added again, usable within a
+    /// transaction
+    ///
+    /// # Params
+    /// - tx: mutable reference to an ongoing transaction
+    /// - user_id: id of the added user
+    /// - group_id: id of the group
+    ///
+    /// # Returns
+    /// - Ok(()): when the user was added
+    /// - Err(DbError): when the user is already a member
+    pub async fn add_member_tx<'a>(
+        tx: &mut Transaction<'a, Postgres>,
+        user_id: &Uuid,
+        group_id: &Uuid,
+    ) -> DbResultSingle<()> {
+        // Check that user is not a member yet
+        if Self::check_user_is_member_tx(tx, user_id, group_id)
+            .await
+            .is_ok()
+        {
+            return Err(DbError::from(BusinessLogicError::new(
+                BusinessLogicErrorKind::UserAlreadyInGroup,
+            )));
+        };
+
+        sqlx::query!(
+            r#"
+            INSERT INTO "GroupUsers" (user_id, group_id)
+            VALUES ($1, $2)
+            ON CONFLICT(user_id, group_id) DO UPDATE SET deleted_at = NULL, role = 'member';
+            "#,
+            user_id,
+            group_id
+        )
+        .execute(tx.as_mut())
+        .await?;
+
+        // Pending invitation is fulfilled by joining the group in any way
+        sqlx::query!(
+            r#"
+            UPDATE "GroupInvitation" SET status = 'accepted', answered_at = now()
+            WHERE user_id = $1 AND group_id = $2 AND status = 'pending'
+            "#,
+            user_id,
+            group_id
+        )
+        .execute(tx.as_mut())
+        .await?;
+
+        Ok(())
     }
 }
 
@@ -203,6 +292,17 @@ impl DbCreate<GroupCreate, Group> for GroupRepository {
         .fetch_one(tx.as_mut())
         .await?;
 
+        sqlx::query!(
+            r#"
+            INSERT INTO "GroupUsers" (user_id, group_id, role)
+            VALUES ($1, $2, 'owner')
+            "#,
+            data.author_id,
+            group.id
+        )
+        .execute(tx.as_mut())
+        .await?;
+
         // Add users
         for user_id in data.users.iter() {
             let user = UserRepository::get_user(&UserGetById::new(user_id), &mut tx).await?;
@@ -212,6 +312,7 @@ impl DbCreate<GroupCreate, Group> for GroupRepository {
                 r#"
                 INSERT INTO "GroupUsers" (user_id, group_id)
                 VALUES ($1, $2)
+                ON CONFLICT (user_id, group_id) DO NOTHING
                 "#,
                 user_id,
                 group.id
@@ -351,6 +452,7 @@ impl GroupRepositoryListUsers for GroupRepository {
         let group = Self::get_group(&GroupGetById::new(&params.id), &mut tx).await?;
         Self::group_is_correct(group)?;
 
+        // The owner has a row in "GroupUsers" as well
         let mut users = sqlx::query_as!(
             UserPreview,
             r#"
@@ -365,23 +467,8 @@ impl GroupRepositoryListUsers for GroupRepository {
         .fetch_all(tx.as_mut())
         .await?;
 
-        let author = sqlx::query_as!(
-            UserPreview,
-            r#"
-            SELECT U.id AS id, U.username AS username, U.profile_picture AS profile_picture
-            FROM "Group" G
-            JOIN "User" U ON G.author_id = U.id
-            WHERE G.id = $1 AND U.deleted_at IS NULL
-            "#,
-            params.id
-        )
-        .fetch_all(tx.as_mut())
-        .await?;
-
         tx.commit().await?;
 
-        users.extend(author);
-
         users.sort_by(|a, b| a.username.cmp(&b.username));
 
         Ok(users)
@@ -399,28 +486,17 @@ impl GroupRepositoryAddUser for GroupRepository {
     async fn add_user_to_group(&self, params: &GroupUserCreate) -> DbResultSingle<()> {
         let mut tx = self.pool_handler.pool.begin().await?;
 
-        // Check that group is correct and user is a member
-        if Self::check_user_is_member_tx(&mut tx, &params.user_id, &params.group_id)
-            .await
-            .is_ok()
-        {
-            return Err(DbError::from(BusinessLogicError::new(
-                BusinessLogicErrorKind::UserAlreadyInGroup,
-            )));
-        };
-
-        sqlx::query!(
-            r#"
-            INSERT INTO "GroupUsers" (user_id, group_id)
-            VALUES ($1, $2)
-            ON CONFLICT(user_id, group_id) DO UPDATE SET deleted_at = NULL;
-            "#,
-            params.user_id,
-            params.group_id
+        // Users are added without an invitation only by admins
+        Self::check_user_role_tx(
+            &mut tx,
+            &params.added_by,
+            &params.group_id,
+            GroupRole::Admin,
         )
-        .execute(tx.as_mut())
         .await?;
 
+        Self::add_member_tx(&mut tx, &params.user_id, &params.group_id).await?;
+
         tx.commit().await?;
 
         Ok(())
@@ -444,7 +520,14 @@ impl GroupRepositoryRemoveUser for GroupRepository {
             &mut tx,
         )
         .await?;
-        Self::group_user_is_correct(group_user)?;
+        let group_user = Self::group_user_is_correct(group_user)?;
+
+        // Group would stay without an owner
+        if group_user.role == GroupRole::Owner {
+            return Err(DbError::from(BusinessLogicError::new(
+                BusinessLogicErrorKind::GroupOwnerCannotLeave,
+            )));
+        }
 
         sqlx::query!(
             r#"
@@ -480,6 +563,10 @@ impl GroupRepositoryRemoveUser for GroupRepository {
 pub trait GroupRepositoryCheckUser {
     /// Checks if user is correct, group is correct and user is a member of the group, returns error if not,
     async fn check_user_is_member(&self, params: &GetGroupUserByIds) -> DbResultSingle<()>;
+
+    /// Checks that the user has at least the role in the group and returns their role, all
+    /// permissions in a group are checked by this
+    async fn check_user_role(&self, params: &GroupUserCheckRole) -> DbResultSingle<GroupRole>;
 }
 
 #[async_trait]
@@ -492,4 +579,151 @@ impl GroupRepositoryCheckUser for GroupRepository {
 
         Ok(())
     }
+
+    async fn check_user_role(&self, params: &GroupUserCheckRole) -> DbResultSingle<GroupRole> {
+        let mut tx = self.pool_handler.pool.begin().await?;
+
+        let role =
+            Self::check_user_role_tx(&mut tx, &params.user_id, &params.group_id, params.role)
+                .await?;
+        tx.commit().await?;
+
+        Ok(role)
+    }
+}
+
+#[async_trait]
+pub trait GroupRepositoryRoles {
+    /// Lists members of a group with their roles
+    async fn list_group_members(&self, params: &GroupGetById) -> DbResultMultiple<GroupMember>;
+
+    /// Changes the role of a member between admin and member
+    async fn set_user_role(&self, params: &GroupUserRoleUpdate) -> DbResultSingle<()>;
+
+    /// Transfers the ownership of a group to its member, the previous owner becomes an admin
+    async fn transfer_ownership(&self, params: &GroupOwnershipTransfer) -> DbResultSingle<Group>;
+}
+
+#[async_trait]
+impl GroupRepositoryRoles for GroupRepository {
+    async fn list_group_members(&self, params: &GroupGetById) -> DbResultMultiple<GroupMember> {
+        let mut tx = self.pool_handler.pool.begin().await?;
+
+        let group = Self::get_group(params, &mut tx).await?;
+        Self::group_is_correct(group)?;
+
+        let members = sqlx::query_as!(
+            GroupMember,
+            r#"
+            SELECT U.id, U.username, U.profile_picture, GU.role AS "role: GroupRole"
+            FROM "GroupUsers" GU
+            JOIN "User" U ON U.id = GU.user_id
+            WHERE GU.group_id = $1 AND GU.deleted_at IS NULL AND U.deleted_at IS NULL
+            ORDER BY GU.role, U.username
+            "#,
+            params.id
+        )
+        .fetch_all(tx.as_mut())
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(members)
+    }
+
+    async fn set_user_role(&self, params: &GroupUserRoleUpdate) -> DbResultSingle<()> {
+        // Ownership is only transferred, never given or taken away
+        if params.role == GroupRole::Owner {
+            return Err(DbError::from(BusinessLogicError::new(
+                BusinessLogicErrorKind::GroupOwnerChange,
+            )));
+        }
+
+        let mut tx = self.pool_handler.pool.begin().await?;
+
+        let role = Self::check_user_role_tx(
+            &mut tx,
+            &params.user_id,
+            &params.group_id,
+            GroupRole::Member,
+        )
+        .await?;
+        if role == GroupRole::Owner {
+            return Err(DbError::from(BusinessLogicError::new(
+                BusinessLogicErrorKind::GroupOwnerChange,
+            )));
+        }
+
+        sqlx::query!(
+            r#"
+            UPDATE "GroupUsers" SET role = $3
+            WHERE user_id = $1 AND group_id = $2
+            "#,
+            params.user_id,
+            params.group_id,
+            params.role as GroupRole
+        )
+        .execute(tx.as_mut())
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn transfer_ownership(&self, params: &GroupOwnershipTransfer) -> DbResultSingle<Group> {
+        let mut tx = self.pool_handler.pool.begin().await?;
+
+        let role = Self::check_user_role_tx(
+            &mut tx,
+            &params.user_id,
+            &params.group_id,
+            GroupRole::Member,
+        )
+        .await?;
+        if role == GroupRole::Owner {
+            return Err(DbError::from(BusinessLogicError::new(
+                BusinessLogicErrorKind::GroupOwnerChange,
+            )));
+        }
+
+        // Previous owner is demoted first, a group has only one owner
+        sqlx::query!(
+            r#"
+            UPDATE "GroupUsers" SET role = 'admin'
+            WHERE group_id = $1 AND role = 'owner'
+            "#,
+            params.group_id
+        )
+        .execute(tx.as_mut())
+        .await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE "GroupUsers" SET role = 'owner'
+            WHERE user_id = $1 AND group_id = $2
+            "#,
+            params.user_id,
+            params.group_id
+        )
+        .execute(tx.as_mut())
+        .await?;
+
+        let group = sqlx::query_as!(
+            Group,
+            r#"
+            UPDATE "Group" SET author_id = $1
+            WHERE id = $2
+            RETURNING *
+            "#,
+            params.user_id,
+            params.group_id
+        )
+        .fetch_one(tx.as_mut())
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(group)
+    }
 }
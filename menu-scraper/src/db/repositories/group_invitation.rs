@@ -0,0 +1,446 @@
+use crate::db::common::error::{
+    BusinessLogicError, BusinessLogicErrorKind, DbError, DbResultMultiple, DbResultSingle,
+};
+use crate::db::common::{DbCreate, DbReadMany, DbRepository, PoolHandler};
+use crate::db::models::{
+    Group, GroupGetById, GroupInvitation, GroupInvitationAnswer, GroupInvitationCreate,
+    GroupInvitationGetByUser, GroupInvitationPreview, GroupJoinLink, GroupJoinLinkCreate,
+    GroupJoinLinkDelete, GroupJoinLinkGetByToken, GroupJoinLinkGetMany, GroupJoinLinkUse,
+    GroupRole, InvitationStatus, UserGetById,
+};
+use crate::db::repositories::{GroupRepository, UserRepository};
+use async_trait::async_trait;
+use chrono::Utc;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct GroupInvitationRepository {
+    pool_handler: PoolHandler,
+}
+
+impl GroupInvitationRepository {
+    /// Function which retrieves an invitation by its id, usable within a transaction
+    ///
+    /// # Params
+    /// - id: id of the invitation
+    /// - transaction_handle mutable reference to an ongoing transaction
+    ///
+    /// # Returns
+    /// - Ok(invitation): on successful connection and retrieval
+    /// - Err(_): otherwise
+    pub async fn get_invitation<'a>(
+        id: &Uuid,
+        transaction_handle: &mut Transaction<'a, Postgres>,
+    ) -> DbResultSingle<Option<GroupInvitation>> {
+        let invitation = sqlx::query_as!(
+            GroupInvitation,
+            r#"
+            SELECT id, group_id, user_id, invited_by, status AS "status: InvitationStatus",
+                created_at, answered_at
+            FROM "GroupInvitation"
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(transaction_handle.as_mut())
+        .await?;
+
+        Ok(invitation)
+    }
+
+    /// Function which checks if the invitation is correct (existing and not answered yet)
+    ///
+    /// # Params
+    /// - invitation: optional invitation retrieved from the database
+    ///
+    /// # Returns
+    /// - Ok(invitation): when the invitation exists and is pending
+    /// - Err(DbError): with appropriate error description otherwise
+    pub fn invitation_is_correct(
+        invitation: Option<GroupInvitation>,
+    ) -> DbResultSingle<GroupInvitation> {
+        match invitation {
+            Some(
+                invitation @ GroupInvitation {
+                    status: InvitationStatus::Pending,
+                    ..
+                },
+            ) => Ok(invitation),
+            Some(_) => Err(DbError::from(BusinessLogicError::new(
+                BusinessLogicErrorKind::InvitationAlreadyAnswered,
+            ))),
+            None => Err(DbError::from(BusinessLogicError::new(
+                BusinessLogicErrorKind::InvitationDoesNotExist,
+            ))),
+        }
+    }
+
+    /// Function which retrieves a join link by its secret token, usable within a transaction
+    ///
+    /// # Params
+    /// - token: secret part of the link URL
+    /// - transaction_handle mutable reference to an ongoing transaction
+    ///
+    /// # Returns
+    /// - Ok(link): on successful connection and retrieval
+    /// - Err(_): otherwise
+    pub async fn get_join_link<'a>(
+        token: &str,
+        transaction_handle: &mut Transaction<'a, Postgres>,
+    ) -> DbResultSingle<Option<GroupJoinLink>> {
+        let link = sqlx::query_as!(
+            GroupJoinLink,
+            r#"
+            SELECT *
+            FROM "GroupJoinLink"
+            WHERE token = $1
+            "#,
+            token
+        )
+        .fetch_optional(transaction_handle.as_mut())
+        .await?;
+
+        Ok(link)
+    }
+
+    /// Function which checks if the join link is correct (existing, not revoked and not expired)
+    ///
+    /// # Params
+    /// - link: optional join link retrieved from the database
+    ///
+    /// # Returns
+    /// - Ok(link): when the link can be used
+    /// - Err(DbError): with appropriate error description otherwise
+    pub fn join_link_is_correct(link: Option<GroupJoinLink>) -> DbResultSingle<GroupJoinLink> {
+        match link {
+            Some(GroupJoinLink {
+                deleted_at: Some(_),
+                ..
+            })
+            | None => Err(DbError::from(BusinessLogicError::new(
+                BusinessLogicErrorKind::JoinLinkDoesNotExist,
+            ))),
+            Some(link) if link.expires_at <= Utc::now() => Err(DbError::from(
+                BusinessLogicError::new(BusinessLogicErrorKind::JoinLinkExpired),
+            )),
+            Some(link) => Ok(link),
+        }
+    }
+}
+
+#[async_trait]
+impl DbRepository for GroupInvitationRepository {
+    #[inline]
+    fn new(pool_handler: PoolHandler) -> Self {
+        Self { pool_handler }
+    }
+}
+
+#[async_trait]
+impl DbCreate<GroupInvitationCreate, GroupInvitation> for GroupInvitationRepository {
+    /// Invites a user to a group, only admins of the group invite
+    async fn create(&self, data: &GroupInvitationCreate) -> DbResultSingle<GroupInvitation> {
+        let mut tx = self.pool_handler.pool.begin().await?;
+
+        GroupRepository::check_user_role_tx(
+            &mut tx,
+            &data.invited_by,
+            &data.group_id,
+            GroupRole::Admin,
+        )
+        .await?;
+
+        let user = UserRepository::get_user(&UserGetById::new(&data.user_id), &mut tx).await?;
+        UserRepository::user_is_correct(user)?;
+
+        if GroupRepository::check_user_is_member_tx(&mut tx, &data.user_id, &data.group_id)
+            .await
+            .is_ok()
+        {
+            return Err(DbError::from(BusinessLogicError::new(
+                BusinessLogicErrorKind::UserAlreadyInGroup,
+            )));
+        }
+
+        let invitation = sqlx::query_as!(
+            GroupInvitation,
+            r#"
+            INSERT INTO "GroupInvitation" (group_id, user_id, invited_by)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (group_id, user_id) WHERE status = 'pending' DO NOTHING
+            RETURNING id, group_id, user_id, invited_by, status AS "status: InvitationStatus",
+                created_at, answered_at
+            "#,
+            data.group_id,
+            data.user_id,
+            data.invited_by
+        )
+        .fetch_optional(tx.as_mut())
+        .await?
+        .ok_or_else(|| {
+            DbError::from(BusinessLogicError::new(
+                BusinessLogicErrorKind::InvitationAlreadyPending,
+            ))
+        })?;
+
+        tx.commit().await?;
+
+        Ok(invitation)
+    }
+}
+
+#[async_trait]
+impl DbReadMany<GroupInvitationGetByUser, GroupInvitationPreview> for GroupInvitationRepository {
+    /// Lists pending invitations of a user, newest first
+    async fn read_many(
+        &self,
+        params: &GroupInvitationGetByUser,
+    ) -> DbResultMultiple<GroupInvitationPreview> {
+        let invitations = sqlx::query_as!(
+            GroupInvitationPreview,
+            r#"
+            SELECT I.id, I.group_id, G.name AS group_name, G.picture AS group_picture,
+                U.username AS invited_by_username, I.created_at
+            FROM "GroupInvitation" I
+            JOIN "Group" G ON I.group_id = G.id
+            JOIN "User" U ON I.invited_by = U.id
+            WHERE I.user_id = $1 AND I.status = 'pending' AND G.deleted_at IS NULL
+            ORDER BY I.created_at DESC
+            "#,
+            params.user_id
+        )
+        .fetch_all(&*self.pool_handler.pool)
+        .await?;
+
+        Ok(invitations)
+    }
+}
+
+#[async_trait]
+pub trait GroupInvitationRepositoryAnswer {
+    /// Accepts or declines an invitation of the user, accepting it adds the user to the group
+    async fn answer(&self, params: &GroupInvitationAnswer) -> DbResultSingle<GroupInvitation>;
+}
+
+#[async_trait]
+impl GroupInvitationRepositoryAnswer for GroupInvitationRepository {
+    async fn answer(&self, params: &GroupInvitationAnswer) -> DbResultSingle<GroupInvitation> {
+        let mut tx = self.pool_handler.pool.begin().await?;
+
+        let invitation = Self::get_invitation(&params.id, &mut tx).await?;
+        let invitation = Self::invitation_is_correct(invitation)?;
+
+        // Invitations of other users look like non-existing ones
+        if invitation.user_id != params.user_id {
+            return Err(DbError::from(BusinessLogicError::new(
+                BusinessLogicErrorKind::InvitationDoesNotExist,
+            )));
+        }
+
+        let group =
+            GroupRepository::get_group(&GroupGetById::new(&invitation.group_id), &mut tx).await?;
+        GroupRepository::group_is_correct(group)?;
+
+        let status = if params.accept {
+            InvitationStatus::Accepted
+        } else {
+            InvitationStatus::Declined
+        };
+
+        let invitation = sqlx::query_as!(
+            GroupInvitation,
+            r#"
+            UPDATE "GroupInvitation" SET status = $2, answered_at = now()
+            WHERE id = $1
+            RETURNING id, group_id, user_id, invited_by, status AS "status: InvitationStatus",
+                created_at, answered_at
+            "#,
+            params.id,
+            status as InvitationStatus
+        )
+        .fetch_one(tx.as_mut())
+        .await?;
+
+        if params.accept {
+            GroupRepository::add_member_tx(&mut tx, &invitation.user_id, &invitation.group_id)
+                .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(invitation)
+    }
+}
+
+#[async_trait]
+pub trait GroupInvitationRepositoryJoinLink {
+    /// Creates a join link of a group, only admins of the group create them
+    async fn create_join_link(&self, data: &GroupJoinLinkCreate) -> DbResultSingle<GroupJoinLink>;
+
+    /// Lists unexpired join links of a group for its admin, newest first
+    async fn list_join_links(
+        &self,
+        params: &GroupJoinLinkGetMany,
+    ) -> DbResultMultiple<GroupJoinLink>;
+
+    /// Revokes a join link, only admins of the group revoke them
+    async fn revoke_join_link(&self, params: &GroupJoinLinkDelete)
+        -> DbResultSingle<GroupJoinLink>;
+
+    /// Gets the group of a usable join link, shown before the user confirms joining it
+    async fn join_link_group(&self, params: &GroupJoinLinkGetByToken) -> DbResultSingle<Group>;
+
+    /// Joins the user to the group of the link, users already in the group just get the group
+    async fn join(&self, params: &GroupJoinLinkUse) -> DbResultSingle<Group>;
+}
+
+#[async_trait]
+impl GroupInvitationRepositoryJoinLink for GroupInvitationRepository {
+    async fn create_join_link(&self, data: &GroupJoinLinkCreate) -> DbResultSingle<GroupJoinLink> {
+        let mut tx = self.pool_handler.pool.begin().await?;
+
+        GroupRepository::check_user_role_tx(
+            &mut tx,
+            &data.created_by,
+            &data.group_id,
+            GroupRole::Admin,
+        )
+        .await?;
+
+        let link = sqlx::query_as!(
+            GroupJoinLink,
+            r#"
+            INSERT INTO "GroupJoinLink" (group_id, token, created_by, expires_at)
+            VALUES ($1, $2, $3, $4)
+            RETURNING *
+            "#,
+            data.group_id,
+            data.token,
+            data.created_by,
+            data.expires_at
+        )
+        .fetch_one(tx.as_mut())
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(link)
+    }
+
+    async fn list_join_links(
+        &self,
+        params: &GroupJoinLinkGetMany,
+    ) -> DbResultMultiple<GroupJoinLink> {
+        let mut tx = self.pool_handler.pool.begin().await?;
+
+        GroupRepository::check_user_role_tx(
+            &mut tx,
+            &params.user_id,
+            &params.group_id,
+            GroupRole::Admin,
+        )
+        .await?;
+
+        let links = sqlx::query_as!(
+            GroupJoinLink,
+            r#"
+            SELECT *
+            FROM "GroupJoinLink"
+            WHERE group_id = $1 AND deleted_at IS NULL AND expires_at > now()
+            ORDER BY created_at DESC
+            "#,
+            params.group_id
+        )
+        .fetch_all(tx.as_mut())
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(links)
+    }
+
+    async fn revoke_join_link(
+        &self,
+        params: &GroupJoinLinkDelete,
+    ) -> DbResultSingle<GroupJoinLink> {
+        let mut tx = self.pool_handler.pool.begin().await?;
+
+        let link = sqlx::query_as!(
+            GroupJoinLink,
+            r#"
+            SELECT *
+            FROM "GroupJoinLink"
+            WHERE id = $1 AND deleted_at IS NULL
+            "#,
+            params.id
+        )
+        .fetch_optional(tx.as_mut())
+        .await?
+        .ok_or_else(|| {
+            DbError::from(BusinessLogicError::new(
+                BusinessLogicErrorKind::JoinLinkDoesNotExist,
+            ))
+        })?;
+
+        GroupRepository::check_user_role_tx(
+            &mut tx,
+            &params.user_id,
+            &link.group_id,
+            GroupRole::Admin,
+        )
+        .await?;
+
+        let link = sqlx::query_as!(
+            GroupJoinLink,
+            r#"
+            UPDATE "GroupJoinLink" SET deleted_at = now()
+            WHERE id = $1
+            RETURNING *
+            "#,
+            params.id
+        )
+        .fetch_one(tx.as_mut())
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(link)
+    }
+
+    async fn join_link_group(&self, params: &GroupJoinLinkGetByToken) -> DbResultSingle<Group> {
+        let mut tx = self.pool_handler.pool.begin().await?;
+
+        let link = Self::get_join_link(&params.token, &mut tx).await?;
+        let link = Self::join_link_is_correct(link)?;
+
+        let group = GroupRepository::get_group(&GroupGetById::new(&link.group_id), &mut tx).await?;
+        let group = GroupRepository::group_is_correct(group)?;
+
+        tx.commit().await?;
+
+        Ok(group)
+    }
+
+    async fn join(&self, params: &GroupJoinLinkUse) -> DbResultSingle<Group> {
+        let mut tx = self.pool_handler.pool.begin().await?;
+
+        let link = Self::get_join_link(&params.token, &mut tx).await?;
+        let link = Self::join_link_is_correct(link)?;
+
+        let group = GroupRepository::get_group(&GroupGetById::new(&link.group_id), &mut tx).await?;
+        let group = GroupRepository::group_is_correct(group)?;
+
+        // Opening the link again only shows the group
+        if GroupRepository::check_user_is_member_tx(&mut tx, &params.user_id, &group.id)
+            .await
+            .is_err()
+        {
+            GroupRepository::add_member_tx(&mut tx, &params.user_id, &group.id).await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(group)
+    }
+}
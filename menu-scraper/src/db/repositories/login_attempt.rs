@@ -0,0 +1,246 @@
+use crate::db::common::error::DbResultSingle;
+use crate::db::common::{DbCreate, DbRepository, PoolHandler};
+use crate::db::models::{
+    LoginAttempt, LoginAttemptBegin, LoginAttemptClear, LoginAttemptCreate, LoginAttemptGetRecent,
+    LoginFailures, LoginThrottle, LoginThrottlePolicy,
+};
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use sqlx::{Postgres, Transaction};
+
+/// Namespaces of advisory locks of logins to an account and from an address
+const ACCOUNT_LOCK: i32 = 1;
+const IP_ADDRESS_LOCK: i32 = 2;
+
+#[derive(Clone)]
+pub struct LoginAttemptRepository {
+    pool_handler: PoolHandler,
+}
+
+impl LoginAttemptRepository {
+    /// Function which gets recent failed attempts of the account and of the address of a login,
+    /// usable within a transaction
+    ///
+    /// # Params
+    /// - params: structure containing the email, the address and the start of the window
+    /// - transaction_handle mutable reference to an ongoing transaction
+    ///
+    /// # Returns
+    /// - Ok(throttle): on successful connection and retrieval
+    /// - Err(_): otherwise
+    pub async fn get_failures<'a>(
+        params: &LoginAttemptGetRecent,
+        transaction_handle: &mut Transaction<'a, Postgres>,
+    ) -> DbResultSingle<LoginThrottle> {
+        let failures = sqlx::query!(
+            r#"
+            SELECT
+                COUNT(*) FILTER (WHERE email = $1 AND NOT account_cleared) AS "account_count!",
+                MAX(created_at) FILTER (WHERE email = $1 AND NOT account_cleared) AS account_last_at,
+                COUNT(*) FILTER (WHERE ip_address = $2) AS "ip_address_count!",
+                MAX(created_at) FILTER (WHERE ip_address = $2) AS ip_address_last_at
+            FROM "LoginAttempt"
+            WHERE (email = $1 OR ip_address = $2) AND created_at >= $3
+            "#,
+            params.email,
+            params.ip_address,
+            params.since
+        )
+        .fetch_one(transaction_handle.as_mut())
+        .await?;
+
+        Ok(LoginThrottle {
+            account: LoginFailures {
+                count: failures.account_count,
+                last_at: failures.account_last_at,
+            },
+            ip_address: LoginFailures {
+                count: failures.ip_address_count,
+                last_at: failures.ip_address_last_at,
+            },
+        })
+    }
+
+    /// Function which removes attempts too old to be counted, usable within a transaction
+    ///
+    /// # Params
+    /// - transaction_handle mutable reference to an ongoing transaction
+    ///
+    /// # Returns
+    /// - Ok(()): on successful connection and removal
+    /// - Err(_): otherwise
+    pub async fn delete_expired<'a>(
+        transaction_handle: &mut Transaction<'a, Postgres>,
+    ) -> DbResultSingle<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM "LoginAttempt"
+            WHERE created_at < $1
+            "#,
+            Utc::now() - Duration::seconds(LoginThrottlePolicy::WINDOW_SECONDS)
+        )
+        .execute(transaction_handle.as_mut())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Function which makes logins to the account and from the address of the attempt wait for
+    /// the ongoing transaction, the locks are released by its end
+    ///
+    /// # Params
+    /// - params: structure containing the email and the address of the attempt
+    /// - transaction_handle mutable reference to an ongoing transaction
+    ///
+    /// # Returns
+    /// - Ok(()): when the locks are acquired
+    /// - Err(_): otherwise
+    pub async fn lock_attempts<'a>(
+        params: &LoginAttemptCreate,
+        transaction_handle: &mut Transaction<'a, Postgres>,
+    ) -> DbResultSingle<()> {
+        // Always locked in the same order, so two logins can not wait for each other
+        for (namespace, key) in [
+            (ACCOUNT_LOCK, &params.email),
+            (IP_ADDRESS_LOCK, &params.ip_address),
+        ] {
+            sqlx::query("SELECT pg_advisory_xact_lock($1, hashtext($2))")
+                .bind(namespace)
+                .bind(key)
+                .execute(transaction_handle.as_mut())
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl DbRepository for LoginAttemptRepository {
+    #[inline]
+    fn new(pool_handler: PoolHandler) -> Self {
+        Self { pool_handler }
+    }
+}
+
+#[async_trait]
+impl DbCreate<LoginAttemptCreate, LoginAttempt> for LoginAttemptRepository {
+    /// Records a failed login attempt, attempts too old to be counted are removed
+    async fn create(&self, data: &LoginAttemptCreate) -> DbResultSingle<LoginAttempt> {
+        let mut tx = self.pool_handler.pool.begin().await?;
+
+        LoginAttemptRepository::delete_expired(&mut tx).await?;
+
+        let attempt = sqlx::query_as!(
+            LoginAttempt,
+            r#"
+            INSERT INTO "LoginAttempt" (email, ip_address)
+            VALUES ($1, $2)
+            RETURNING *
+            "#,
+            data.email,
+            data.ip_address
+        )
+        .fetch_one(tx.as_mut())
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(attempt)
+    }
+}
+
+#[async_trait]
+pub trait LoginAttemptRepositoryThrottle {
+    /// Gets recent failed attempts of the account and of the address of a login
+    async fn get_throttle(&self, params: &LoginAttemptGetRecent) -> DbResultSingle<LoginThrottle>;
+
+    /// Checks the throttling of a login and records its attempt before the password is verified,
+    /// the attempt counts as failed until the account is cleared by a successful login. Concurrent
+    /// logins to the same account or from the same address are evaluated one after another, so
+    /// each of them counts the attempts of the previous ones.
+    async fn begin(&self, params: &LoginAttemptCreate) -> DbResultSingle<LoginAttemptBegin>;
+
+    /// Forgets failed attempts of the account and removes the attempt of the successful login,
+    /// failures of the address are kept so that signing in to an own account does not help
+    /// guessing others
+    async fn clear(&self, params: &LoginAttemptClear) -> DbResultSingle<()>;
+}
+
+#[async_trait]
+impl LoginAttemptRepositoryThrottle for LoginAttemptRepository {
+    async fn get_throttle(&self, params: &LoginAttemptGetRecent) -> DbResultSingle<LoginThrottle> {
+        let mut tx = self.pool_handler.pool.begin().await?;
+
+        let throttle = LoginAttemptRepository::get_failures(params, &mut tx).await?;
+
+        tx.commit().await?;
+
+        Ok(throttle)
+    }
+
+    async fn begin(&self, params: &LoginAttemptCreate) -> DbResultSingle<LoginAttemptBegin> {
+        let mut tx = self.pool_handler.pool.begin().await?;
+
+        LoginAttemptRepository::lock_attempts(params, &mut tx).await?;
+
+        let now = Utc::now();
+        let throttle = LoginAttemptRepository::get_failures(
+            &LoginAttemptGetRecent::new(&params.email, &params.ip_address, now),
+            &mut tx,
+        )
+        .await?;
+
+        if let Some(wait) = throttle.retry_after(now) {
+            tx.commit().await?;
+            return Ok(LoginAttemptBegin::Throttled(wait));
+        }
+
+        LoginAttemptRepository::delete_expired(&mut tx).await?;
+
+        let attempt = sqlx::query_as!(
+            LoginAttempt,
+            r#"
+            INSERT INTO "LoginAttempt" (email, ip_address)
+            VALUES ($1, $2)
+            RETURNING *
+            "#,
+            params.email,
+            params.ip_address
+        )
+        .fetch_one(tx.as_mut())
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(LoginAttemptBegin::Recorded(attempt))
+    }
+
+    async fn clear(&self, params: &LoginAttemptClear) -> DbResultSingle<()> {
+        let mut tx = self.pool_handler.pool.begin().await?;
+
+        sqlx::query!(
+            r#"
+            DELETE FROM "LoginAttempt"
+            WHERE id = $1
+            "#,
+            params.attempt_id
+        )
+        .execute(tx.as_mut())
+        .await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE "LoginAttempt" SET account_cleared = true
+            WHERE email = $1 AND NOT account_cleared
+            "#,
+            params.email
+        )
+        .execute(tx.as_mut())
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+}
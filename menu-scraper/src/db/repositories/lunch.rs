@@ -4,12 +4,15 @@ use crate::db::common::error::{
 };
 use crate::db::common::{DbCreate, DbDelete, DbReadMany, DbReadOne, DbRepository, PoolHandler};
 use crate::db::models::{
-    GroupGetById, Lunch, LunchCreate, LunchDelete, LunchGetById, LunchGetMany, LunchWithGroup,
-    UserGetById,
+    Ballot, GroupGetById, Lunch, LunchCandidate, LunchCandidateCreate, LunchCreate, LunchDelete,
+    LunchGetById, LunchGetMany, LunchRsvp, LunchRsvpCreate, LunchWithGroup, MenuGetById,
+    RsvpStatus, TieBreak, UserGetById, VotingMode, WinnerCandidate,
 };
-use crate::db::repositories::{GroupRepository, UserRepository};
+use crate::db::repositories::{GroupRepository, MenuRepository, UserRepository, VoteRepository};
 use async_trait::async_trait;
+use chrono::Utc;
 use sqlx::{Postgres, QueryBuilder, Transaction};
+use uuid::Uuid;
 
 #[derive(Clone)]
 pub struct LunchRepository {
@@ -33,7 +36,9 @@ impl LunchRepository {
         let lunch = sqlx::query_as!(
             Lunch,
             r#"
-            SELECT *
+            SELECT id, date, group_id, deleted_at, start_time, meeting_point, voting_deadline,
+                tie_break AS "tie_break: TieBreak", longitude, latitude,
+                voting_mode AS "voting_mode: VotingMode", winner_menu_id, finalized_at
             FROM "Lunch" L
             WHERE L.id = $1
             "#,
@@ -68,6 +73,120 @@ impl LunchRepository {
             ))),
         }
     }
+
+    /// Function which stores the winning menu of the lunch once its voting deadline has passed,
+    /// usable within a transaction
+    ///
+    /// # Params
+    /// - params: structure containing the id of the lunch
+    /// - transaction_handle mutable reference to an ongoing transaction
+    ///
+    /// # Returns
+    /// - Ok(lunch): finalized lunch, or unchanged lunch when it is already finalized or its
+    ///   voting has not ended yet
+    /// - Err(DbError): when the lunch does not exist or is deleted
+    pub async fn finalize_lunch<'a>(
+        params: &LunchGetById,
+        transaction_handle: &mut Transaction<'a, Postgres>,
+    ) -> DbResultSingle<Lunch> {
+        // Row is locked, so concurrent finalizations can not pick different winners
+        let lunch = sqlx::query_as!(
+            Lunch,
+            r#"
+            SELECT id, date, group_id, deleted_at, start_time, meeting_point, voting_deadline,
+                tie_break AS "tie_break: TieBreak", longitude, latitude,
+                voting_mode AS "voting_mode: VotingMode", winner_menu_id, finalized_at
+            FROM "Lunch"
+            WHERE id = $1
+            FOR UPDATE
+            "#,
+            params.id
+        )
+        .fetch_optional(transaction_handle.as_mut())
+        .await?;
+        let lunch = Self::lunch_is_correct(lunch)?;
+
+        if lunch.finalized_at.is_some() || !lunch.voting_is_closed(Utc::now()) {
+            return Ok(lunch);
+        }
+
+        // Vetoed menus are neither counted nor can win
+        let ballots = sqlx::query_as!(
+            Ballot,
+            r#"
+            SELECT V.menu_id, V.user_id, V.rank
+            FROM "Vote" V
+            JOIN "Menu" M ON V.menu_id = M.id
+            JOIN "Restaurant" R ON M.restaurant_id = R.id
+            WHERE V.lunch_id = $1 AND V.deleted_at IS NULL AND M.deleted_at IS NULL AND R.deleted_at IS NULL
+                AND NOT EXISTS (
+                    SELECT 1
+                    FROM "Veto" X
+                    WHERE X.lunch_id = $1 AND X.deleted_at IS NULL
+                        AND (X.menu_id = M.id OR X.restaurant_id = R.id)
+                )
+            "#,
+            lunch.id
+        )
+        .fetch_all(transaction_handle.as_mut())
+        .await?;
+        let scores = lunch.voting_mode.tally(&ballots);
+
+        let mut candidates = sqlx::query_as!(
+            WinnerCandidate,
+            r#"
+            SELECT
+                M.id AS menu_id,
+                COUNT(V.id) AS "votes!",
+                (SELECT AVG(I.price)::FLOAT8 FROM "MenuItem" I WHERE I.menu_id = M.id) AS average_price,
+                R.longitude,
+                R.latitude
+            FROM "Vote" V
+            JOIN "Menu" M ON V.menu_id = M.id
+            JOIN "Restaurant" R ON M.restaurant_id = R.id
+            WHERE V.lunch_id = $1 AND V.deleted_at IS NULL AND M.deleted_at IS NULL AND R.deleted_at IS NULL
+                AND NOT EXISTS (
+                    SELECT 1
+                    FROM "Veto" X
+                    WHERE X.lunch_id = $1 AND X.deleted_at IS NULL
+                        AND (X.menu_id = M.id OR X.restaurant_id = R.id)
+                )
+            GROUP BY M.id, R.longitude, R.latitude
+            "#,
+            lunch.id
+        )
+        .fetch_all(transaction_handle.as_mut())
+        .await?;
+
+        // Number of votes is the score of plurality and approval voting only
+        for candidate in &mut candidates {
+            candidate.votes = scores.get(&candidate.menu_id).copied().unwrap_or(0);
+        }
+
+        let winner = lunch.tie_break.pick(
+            &candidates,
+            lunch.longitude.zip(lunch.latitude),
+            Uuid::new_v4().as_u128() as u64,
+        );
+
+        let lunch = sqlx::query_as!(
+            Lunch,
+            r#"
+            UPDATE "Lunch"
+            SET winner_menu_id = $2, finalized_at = now()
+            WHERE id = $1
+            RETURNING id, date, group_id, deleted_at, start_time, meeting_point, voting_deadline,
+                tie_break AS "tie_break: TieBreak", longitude, latitude,
+                voting_mode AS "voting_mode: VotingMode", winner_menu_id, finalized_at
+            "#,
+            lunch.id,
+            winner
+        )
+        .fetch_one(transaction_handle.as_mut())
+        .await?;
+
+        Ok(lunch)
+    }
 }
 
 #[async_trait]
@@ -92,7 +211,9 @@ impl DbCreate<LunchCreate, Lunch> for LunchRepository {
         let lunch = sqlx::query_as!(
             Lunch,
             r#"
-            SELECT *
+            SELECT id, date, group_id, deleted_at, start_time, meeting_point, voting_deadline,
+                tie_break AS "tie_break: TieBreak", longitude, latitude,
+                voting_mode AS "voting_mode: VotingMode", winner_menu_id, finalized_at
             FROM "Lunch"
             WHERE date = $1 AND group_id = $2 AND deleted_at IS NULL
             "#,
@@ -111,17 +232,56 @@ impl DbCreate<LunchCreate, Lunch> for LunchRepository {
         let lunch = sqlx::query_as!(
             Lunch,
             r#"
-            INSERT INTO "Lunch" (date, group_id)
-            VALUES ($1, $2)
-            ON CONFLICT (date, group_id) DO UPDATE SET deleted_at = NULL
-            RETURNING *;
+            INSERT INTO "Lunch" (
+                date, group_id, start_time, meeting_point, voting_deadline, tie_break, longitude,
+                latitude, voting_mode
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            ON CONFLICT (date, group_id) DO UPDATE SET deleted_at = NULL, start_time = $3,
+                meeting_point = $4, voting_deadline = $5, tie_break = $6, longitude = $7,
+                latitude = $8, voting_mode = $9, winner_menu_id = NULL, finalized_at = NULL
+            RETURNING id, date, group_id, deleted_at, start_time, meeting_point, voting_deadline,
+                tie_break AS "tie_break: TieBreak", longitude, latitude,
+                voting_mode AS "voting_mode: VotingMode", winner_menu_id, finalized_at;
             "#,
             data.date,
-            data.group_id
+            data.group_id,
+            data.start_time,
+            data.meeting_point,
+            data.voting_deadline,
+            data.tie_break as TieBreak,
+            data.longitude,
+            data.latitude,
+            data.voting_mode as VotingMode
         )
         .fetch_one(tx.as_mut())
         .await?;
 
+        if let Some(candidates) = &data.candidates {
+            let query = format!(
+                r#"
+                INSERT INTO "LunchCandidate" (lunch_id, menu_id)
+                SELECT $1, M.id
+                FROM "Menu" M
+                JOIN "Restaurant" R ON M.restaurant_id = R.id
+                JOIN "MenuItem" I ON M.id = I.menu_id
+                WHERE M.date = $2 AND M.deleted_at IS NULL AND R.deleted_at IS NULL
+                GROUP BY M.id, R.id
+                ORDER BY {}
+                LIMIT $3
+                ON CONFLICT (lunch_id, menu_id) DO NOTHING
+                "#,
+                MenuRepository::ordering_clause(&candidates.order_by)
+            );
+
+            sqlx::query(&query)
+                .bind(lunch.id)
+                .bind(lunch.date)
+                .bind(candidates.count)
+                .execute(tx.as_mut())
+                .await?;
+        }
+
         tx.commit().await?;
 
         Ok(lunch)
@@ -145,7 +305,9 @@ impl DbDelete<LunchDelete, Lunch> for LunchRepository {
             UPDATE "Lunch"
             SET deleted_at = now()
             WHERE id = $1
-            RETURNING *
+            RETURNING id, date, group_id, deleted_at, start_time, meeting_point, voting_deadline,
+                tie_break AS "tie_break: TieBreak", longitude, latitude,
+                voting_mode AS "voting_mode: VotingMode", winner_menu_id, finalized_at
             "#,
             params.id
         )
@@ -164,6 +326,40 @@ impl DbDelete<LunchDelete, Lunch> for LunchRepository {
         .execute(tx.as_mut())
         .await?;
 
+        // Vetoes would count against the limit of the lunch created again for the date
+        sqlx::query!(
+            r#"
+            UPDATE "Veto"
+            SET deleted_at = now()
+            WHERE lunch_id = $1 AND deleted_at IS NULL
+            "#,
+            params.id
+        )
+        .execute(tx.as_mut())
+        .await?;
+
+        // Answers and nominations are not kept, a lunch created again for the date is brought
+        // back under its id
+        sqlx::query!(
+            r#"
+            DELETE FROM "LunchRsvp"
+            WHERE lunch_id = $1
+            "#,
+            params.id
+        )
+        .execute(tx.as_mut())
+        .await?;
+
+        sqlx::query!(
+            r#"
+            DELETE FROM "LunchCandidate"
+            WHERE lunch_id = $1
+            "#,
+            params.id
+        )
+        .execute(tx.as_mut())
+        .await?;
+
         tx.commit().await?;
 
         Ok(vec![deleted_lunch])
@@ -191,7 +387,8 @@ impl DbReadMany<LunchGetMany, LunchWithGroup> for LunchRepository {
 
         let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new(
             r#"
-            SELECT DISTINCT L.id, L.date, L.group_id, G.name AS group_name, G.picture AS group_picture
+            SELECT DISTINCT L.id, L.date, L.group_id, G.name AS group_name, G.picture AS group_picture,
+                L.start_time, L.meeting_point, L.voting_mode, L.winner_menu_id
             FROM "Lunch" L
             JOIN "Group" G ON L.group_id = G.id
             LEFT OUTER JOIN "GroupUsers" GU ON G.id = GU.group_id
@@ -228,7 +425,7 @@ impl DbReadMany<LunchGetMany, LunchWithGroup> for LunchRepository {
             query_builder.push_bind(to);
         }
 
-        query_builder.push(" ORDER BY L.date ASC");
+        query_builder.push(" ORDER BY L.date ASC, L.start_time ASC");
 
         let lunches = query_builder
             .build_query_as()
@@ -240,3 +437,181 @@ impl DbReadMany<LunchGetMany, LunchWithGroup> for LunchRepository {
         Ok(lunches)
     }
 }
+
+#[async_trait]
+pub trait LunchRepositoryRsvp {
+    /// Stores whether a member of the group is coming to the lunch, replaces their previous answer
+    async fn rsvp(&self, params: &LunchRsvpCreate) -> DbResultSingle<LunchRsvp>;
+
+    /// Lists answers of members who are still in the group
+    async fn list_rsvps(&self, params: &LunchGetById) -> DbResultMultiple<LunchRsvp>;
+}
+
+#[async_trait]
+impl LunchRepositoryRsvp for LunchRepository {
+    async fn rsvp(&self, params: &LunchRsvpCreate) -> DbResultSingle<LunchRsvp> {
+        let mut tx = self.pool_handler.pool.begin().await?;
+
+        let lunch = Self::get_lunch(&LunchGetById::new(&params.lunch_id), &mut tx).await?;
+        let lunch = Self::lunch_is_correct(lunch)?;
+
+        GroupRepository::check_user_is_member_tx(&mut tx, &params.user_id, &lunch.group_id).await?;
+
+        let rsvp = sqlx::query_as!(
+            LunchRsvp,
+            r#"
+            INSERT INTO "LunchRsvp" (lunch_id, user_id, status)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (lunch_id, user_id) DO UPDATE SET status = $3, updated_at = now()
+            RETURNING lunch_id, user_id, status AS "status: RsvpStatus", updated_at
+            "#,
+            params.lunch_id,
+            params.user_id,
+            params.status as RsvpStatus
+        )
+        .fetch_one(tx.as_mut())
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(rsvp)
+    }
+
+    async fn list_rsvps(&self, params: &LunchGetById) -> DbResultMultiple<LunchRsvp> {
+        let mut tx = self.pool_handler.pool.begin().await?;
+
+        let lunch = Self::get_lunch(params, &mut tx).await?;
+        Self::lunch_is_correct(lunch)?;
+
+        // Answers of users who left the group are not shown, the owner has a row as well
+        let rsvps = sqlx::query_as!(
+            LunchRsvp,
+            r#"
+            SELECT R.lunch_id, R.user_id, R.status AS "status: RsvpStatus", R.updated_at
+            FROM "LunchRsvp" R
+            JOIN "Lunch" L ON R.lunch_id = L.id
+            JOIN "GroupUsers" GU ON GU.group_id = L.group_id AND GU.user_id = R.user_id
+            WHERE R.lunch_id = $1 AND GU.deleted_at IS NULL
+            "#,
+            params.id
+        )
+        .fetch_all(tx.as_mut())
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(rsvps)
+    }
+}
+
+#[async_trait]
+pub trait LunchRepositoryNominate {
+    /// Nominates a menu of the day of the lunch, nominating the same menu again returns
+    /// the existing candidate
+    async fn nominate(&self, params: &LunchCandidateCreate) -> DbResultSingle<LunchCandidate>;
+}
+
+#[async_trait]
+impl LunchRepositoryNominate for LunchRepository {
+    async fn nominate(&self, params: &LunchCandidateCreate) -> DbResultSingle<LunchCandidate> {
+        let mut tx = self.pool_handler.pool.begin().await?;
+
+        // Same checks as for voting
+        let menu = MenuRepository::get_menu(&MenuGetById::new(&params.menu_id), &mut tx).await?;
+        let menu = MenuRepository::menu_is_correct(menu)?;
+
+        let lunch = Self::get_lunch(&LunchGetById::new(&params.lunch_id), &mut tx).await?;
+        let lunch = Self::lunch_is_correct(lunch)?;
+
+        if lunch.voting_is_closed(Utc::now()) {
+            return Err(DbError::from(BusinessLogicError::new(
+                BusinessLogicErrorKind::VotingClosed,
+            )));
+        }
+
+        if lunch.date != menu.date {
+            return Err(DbError::from(BusinessLogicError::new(
+                BusinessLogicErrorKind::LunchDateDoesntMatchMenuDate,
+            )));
+        }
+
+        GroupRepository::check_user_is_member_tx(&mut tx, &params.user_id, &lunch.group_id).await?;
+
+        VoteRepository::check_menu_is_not_vetoed(&lunch.id, &menu, &mut tx).await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO "LunchCandidate" (lunch_id, menu_id, user_id)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (lunch_id, menu_id) DO NOTHING
+            "#,
+            params.lunch_id,
+            params.menu_id,
+            params.user_id
+        )
+        .execute(tx.as_mut())
+        .await?;
+
+        let candidate = sqlx::query_as!(
+            LunchCandidate,
+            r#"
+            SELECT *
+            FROM "LunchCandidate"
+            WHERE lunch_id = $1 AND menu_id = $2
+            "#,
+            params.lunch_id,
+            params.menu_id
+        )
+        .fetch_one(tx.as_mut())
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(candidate)
+    }
+}
+
+#[async_trait]
+pub trait LunchRepositoryFinalize {
+    /// Gets a lunch, the winner is stored first when the voting deadline has passed
+    async fn finalize(&self, params: &LunchGetById) -> DbResultSingle<Lunch>;
+
+    /// Stores winners of all lunches whose voting deadline has passed
+    async fn finalize_due(&self) -> DbResultMultiple<Lunch>;
+}
+
+#[async_trait]
+impl LunchRepositoryFinalize for LunchRepository {
+    async fn finalize(&self, params: &LunchGetById) -> DbResultSingle<Lunch> {
+        let mut tx = self.pool_handler.pool.begin().await?;
+
+        let lunch = Self::finalize_lunch(params, &mut tx).await?;
+
+        tx.commit().await?;
+
+        Ok(lunch)
+    }
+
+    async fn finalize_due(&self) -> DbResultMultiple<Lunch> {
+        let mut tx = self.pool_handler.pool.begin().await?;
+
+        let due = sqlx::query!(
+            r#"
+            SELECT id
+            FROM "Lunch"
+            WHERE voting_deadline <= now() AND finalized_at IS NULL AND deleted_at IS NULL
+            "#
+        )
+        .fetch_all(tx.as_mut())
+        .await?;
+
+        let mut lunches = Vec::with_capacity(due.len());
+        for lunch in due {
+            lunches.push(Self::finalize_lunch(&LunchGetById::new(&lunch.id), &mut tx).await?);
+        }
+
+        tx.commit().await?;
+
+        Ok(lunches)
+    }
+}
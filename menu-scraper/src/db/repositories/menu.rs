@@ -1,11 +1,11 @@
 use crate::db::common::error::{
     BusinessLogicError, BusinessLogicErrorKind, DbError, DbResultMultiple, DbResultSingle,
 };
-use crate::db::common::query_parameters::DbOrder;
+use crate::db::common::query_parameters::{DbOrder, DbSearchQuery};
 use crate::db::common::{DbCreate, DbDelete, DbReadMany, DbReadOne, DbRepository, PoolHandler};
 use crate::db::models::{
     DbRestaurantOrderingMethod, Menu, MenuCreate, MenuDelete, MenuGetById, MenuId, MenuReadMany,
-    MenuWithRestaurant, RestaurantGetById,
+    MenuUpsertResult, MenuWithRestaurant, RestaurantGetById,
 };
 use crate::db::models::{MenuCount, MenuGetCount, MenuItem};
 use crate::db::repositories::restaurant::RestaurantRepository;
@@ -39,7 +39,7 @@ impl MenuRepository {
                 M.date AS date,
                 M.restaurant_id AS restaurant_id,
                 M.deleted_at AS deleted_at,
-                ARRAY_AGG((I.id, I.name, I.price, I.size, I.is_soup, I.menu_id)) AS "items!: Vec<MenuItem>"
+                ARRAY_AGG((I.id, I.name, I.price, I.size, I.is_soup, I.menu_id, I.allergens, I.is_vegetarian, I.is_vegan, I.is_gluten_free)) AS "items!: Vec<MenuItem>"
             FROM "Menu" M
             JOIN "MenuItem" I ON M.id = I.menu_id
             WHERE M.id = $1
@@ -76,6 +76,32 @@ impl MenuRepository {
             ))),
         }
     }
+
+    /// Function which creates the `ORDER BY` expression of the ordering method, the query has to
+    /// join menus with their restaurant (`R`) and items (`I`) and group them by menus
+    ///
+    /// # Params
+    /// - order_by: method of the ordering
+    ///
+    /// # Returns
+    /// - expression with the direction of the ordering
+    pub fn ordering_clause(order_by: &DbRestaurantOrderingMethod) -> String {
+        let (order_by, ordering) = match order_by {
+            DbRestaurantOrderingMethod::Price(ord) => ("AVG(I.price)".to_string(), ord),
+            DbRestaurantOrderingMethod::Range(ord, (long, lat)) => (
+                format!(
+                    "ST_DistanceSphere(
+                                ST_MakePoint(R.longitude, R.latitude),
+                                ST_MakePoint({long}, {lat}))"
+                ),
+                ord,
+            ),
+            DbRestaurantOrderingMethod::Random => ("RANDOM()".to_string(), &DbOrder::Asc),
+            DbRestaurantOrderingMethod::Date(ord) => ("M.date".to_string(), ord),
+        };
+
+        format!("{order_by} {ordering}")
+    }
 }
 
 #[async_trait]
@@ -102,89 +128,22 @@ impl DbReadOne<MenuGetById, Menu> for MenuRepository {
 
 #[async_trait]
 impl DbCreate<MenuCreate, Menu> for MenuRepository {
-    /// Create a new menu with its items
+    /// Create a new menu with its items, a menu already stored for the date and restaurant is
+    /// updated the same way as by the upsert
     async fn create(&self, data: &MenuCreate) -> DbResultSingle<Menu> {
-        let mut tx = self.pool_handler.pool.begin().await?;
-
-        let restaurant = RestaurantRepository::get_restaurant(
-            RestaurantGetById::new(&data.restaurant_id),
-            &mut tx,
-        )
-        .await?;
-        RestaurantRepository::restaurant_is_correct(restaurant)?;
-
-        let menu_id = sqlx::query_as!(
-            MenuId,
-            r#"
-            INSERT INTO "Menu" (
-                date, restaurant_id
-            )
-            VALUES ($1, $2)
-            ON CONFLICT (date, restaurant_id) DO NOTHING
-            RETURNING id
-            "#,
-            data.date,
-            data.restaurant_id
-        )
-        .fetch_optional(tx.as_mut())
-        .await?;
-
-        let Some(menu_id) = menu_id else {
-            return Ok(Menu {
-                id: Default::default(),
-                date: Default::default(),
-                restaurant_id: Default::default(),
-                deleted_at: None,
-                items: vec![],
-            });
-        };
+        let result = self.upsert(data).await?;
 
-        for item in data.items.iter() {
-            sqlx::query!(
-                r#"
-                INSERT INTO "MenuItem" (
-                    name, price, size, is_soup, menu_id
-                )
-                VALUES ($1, $2, $3, $4, $5)
-                "#,
-                item.name,
-                item.price,
-                item.size,
-                item.is_soup,
-                menu_id.id
-            )
-            .execute(tx.as_mut())
-            .await?;
-        }
-
-        let menu = Self::get_menu(&MenuGetById::new(&menu_id.id), &mut tx).await?;
-        let menu = Self::menu_is_correct(menu)?;
-
-        tx.commit().await?;
-
-        Ok(menu)
+        Ok(result.menu)
     }
 }
 
 #[async_trait]
 impl DbReadMany<MenuReadMany, MenuWithRestaurant> for MenuRepository {
-    /// Gets menus with basic info about the restaurant as well. Supports filtering by date, pagination and ordering by
-    /// distance, average price of the menu and random
+    /// Gets menus with basic info about the restaurant as well. Supports filtering by date, allergens and vegetarian
+    /// dishes, pagination and ordering by distance, average price of the menu and random
     async fn read_many(&self, params: &MenuReadMany) -> DbResultMultiple<MenuWithRestaurant> {
         // Set correct ordering type
-        let (order_by, ordering) = match &params.order_by {
-            DbRestaurantOrderingMethod::Price(ord) => ("AVG(I.price)".to_string(), ord),
-            DbRestaurantOrderingMethod::Range(ord, (long, lat)) => (
-                format!(
-                    "ST_DistanceSphere(
-                                ST_MakePoint(longitude, latitude),
-                                ST_MakePoint({long}, {lat}))"
-                ),
-                ord,
-            ),
-            DbRestaurantOrderingMethod::Random => ("RANDOM()".to_string(), &DbOrder::Asc),
-            DbRestaurantOrderingMethod::Date(ord) => ("date".to_string(), ord),
-        };
+        let order_by = Self::ordering_clause(&params.order_by);
 
         // Pagination, only if limit is not None
         let pagination = if let Some(limit) = params.limit {
@@ -211,13 +170,31 @@ impl DbReadMany<MenuReadMany, MenuWithRestaurant> for MenuRepository {
                 R.picture AS picture,
                 M.id AS menu_id,
                 M.date AS date,
-                ARRAY_AGG(I.*) AS items
+                ARRAY_AGG(I.*) AS items,
+                COALESCE(
+                    ARRAY_AGG(I.id) FILTER (
+                        WHERE to_tsvector('czech_unaccent', I.name) @@ to_tsquery('czech_unaccent', $5)
+                    ),
+                    '{{}}'
+                ) AS matching_item_ids
             FROM "Restaurant" AS R
             JOIN "Menu" AS M ON R.id = M.restaurant_id
             JOIN "MenuItem" AS I ON M.id = I.menu_id
             WHERE M.date >= $1 AND M.date <= $2 AND M.deleted_at IS NULL AND R.deleted_at IS NULL {restaurant}
+                AND NOT EXISTS (
+                    SELECT 1 FROM "MenuItem" AS A WHERE A.menu_id = M.id AND A.allergens && $3
+                )
+                AND (NOT $4 OR EXISTS (
+                    SELECT 1 FROM "MenuItem" AS V
+                    WHERE V.menu_id = M.id AND V.is_vegetarian AND NOT V.is_soup
+                ))
+                AND ($5::text IS NULL OR EXISTS (
+                    SELECT 1 FROM "MenuItem" AS S
+                    WHERE S.menu_id = M.id
+                        AND to_tsvector('czech_unaccent', S.name) @@ to_tsquery('czech_unaccent', $5)
+                ))
             GROUP BY R.id, R.name, R.street, R.house_number, R.zip_code, R.city, R.picture, M.id, M.date
-            ORDER BY {order_by} {ordering}
+            ORDER BY {order_by}
             {pagination}
             "#
         );
@@ -225,6 +202,9 @@ impl DbReadMany<MenuReadMany, MenuWithRestaurant> for MenuRepository {
         let result = sqlx::query_as::<_, MenuWithRestaurant>(&query)
             .bind(params.date_from)
             .bind(params.date_to)
+            .bind(&params.excluded_allergens)
+            .bind(params.vegetarian_only)
+            .bind(params.search.as_ref().map(DbSearchQuery::as_tsquery))
             .fetch_all(&*self.pool_handler.pool)
             .await?;
 
@@ -273,7 +253,7 @@ impl DbDelete<MenuDelete, Menu> for MenuRepository {
 
 #[async_trait]
 pub trait GetNumberOfMenus {
-    /// Gets number of menus for some range of dates, usable for pagination
+    /// Gets number of menus for some range of dates matching the same filters as `read_many`, usable for pagination
     async fn get_number_of_menus(&self, params: &MenuGetCount) -> DbResultSingle<i64>;
 }
 
@@ -287,9 +267,26 @@ impl GetNumberOfMenus for MenuRepository {
             FROM "Restaurant" AS R
             JOIN "Menu" AS M ON R.id = M.restaurant_id
             WHERE M.date >= $1 AND M.date <= $2 AND M.deleted_at IS NULL AND R.deleted_at IS NULL
+                AND NOT EXISTS (
+                    SELECT 1 FROM "MenuItem" AS A WHERE A.menu_id = M.id AND A.allergens && $3
+                )
+                AND (NOT $4 OR EXISTS (
+                    SELECT 1 FROM "MenuItem" AS V
+                    WHERE V.menu_id = M.id AND V.is_vegetarian AND NOT V.is_soup
+                ))
+                AND ($5::text IS NULL OR EXISTS (
+                    SELECT 1 FROM "MenuItem" AS S
+                    WHERE S.menu_id = M.id
+                        AND to_tsvector('czech_unaccent', S.name) @@ to_tsquery('czech_unaccent', $5)
+                ))
+                AND ($6::uuid IS NULL OR R.id = $6)
             "#,
             params.date_from,
-            params.date_to
+            params.date_to,
+            &params.excluded_allergens,
+            params.vegetarian_only,
+            params.search.as_ref().map(DbSearchQuery::as_tsquery),
+            params.restaurant_id
         )
         .fetch_one(&*self.pool_handler.pool)
         .await?;
@@ -297,3 +294,208 @@ impl GetNumberOfMenus for MenuRepository {
         Ok(count.count.unwrap_or(0))
     }
 }
+
+#[async_trait]
+pub trait MenuRepositoryUpsert {
+    /// Creates a menu or updates items of an already existing menu for the same date and
+    /// restaurant. Items are matched by their name, so unchanged items keep their ids. Deleted
+    /// menu is never restored and stays untouched.
+    async fn upsert(&self, data: &MenuCreate) -> DbResultSingle<MenuUpsertResult>;
+}
+
+#[async_trait]
+impl MenuRepositoryUpsert for MenuRepository {
+    async fn upsert(&self, data: &MenuCreate) -> DbResultSingle<MenuUpsertResult> {
+        // Menu without items could not be read back
+        if data.items.is_empty() {
+            return Err(DbError::from(BusinessLogicError::new(
+                BusinessLogicErrorKind::MenuWithoutItems,
+            )));
+        }
+
+        let mut tx = self.pool_handler.pool.begin().await?;
+
+        let restaurant = RestaurantRepository::get_restaurant(
+            RestaurantGetById::new(&data.restaurant_id),
+            &mut tx,
+        )
+        .await?;
+        RestaurantRepository::restaurant_is_correct(restaurant)?;
+
+        let inserted_menu_id = sqlx::query_as!(
+            MenuId,
+            r#"
+            INSERT INTO "Menu" (
+                date, restaurant_id
+            )
+            VALUES ($1, $2)
+            ON CONFLICT (date, restaurant_id) DO NOTHING
+            RETURNING id
+            "#,
+            data.date,
+            data.restaurant_id
+        )
+        .fetch_optional(tx.as_mut())
+        .await?;
+
+        let created = inserted_menu_id.is_some();
+
+        let menu_id = match inserted_menu_id {
+            Some(menu_id) => menu_id.id,
+            None => {
+                let existing_menu = sqlx::query!(
+                    r#"
+                    SELECT id, deleted_at
+                    FROM "Menu"
+                    WHERE date = $1 AND restaurant_id = $2
+                    "#,
+                    data.date,
+                    data.restaurant_id
+                )
+                .fetch_one(tx.as_mut())
+                .await?;
+
+                if existing_menu.deleted_at.is_some() {
+                    let menu =
+                        Self::get_menu(&MenuGetById::new(&existing_menu.id), &mut tx).await?;
+                    let menu = menu.ok_or_else(|| {
+                        DbError::from(BusinessLogicError::new(
+                            BusinessLogicErrorKind::MenuDoesNotExist,
+                        ))
+                    })?;
+                    tx.commit().await?;
+
+                    return Ok(MenuUpsertResult {
+                        menu,
+                        created: false,
+                        items_inserted: 0,
+                        items_updated: 0,
+                        items_deleted: 0,
+                    });
+                }
+
+                existing_menu.id
+            }
+        };
+
+        let existing_items = sqlx::query_as!(
+            MenuItem,
+            r#"
+            SELECT id, name, price, size, is_soup, menu_id, allergens, is_vegetarian, is_vegan,
+                is_gluten_free
+            FROM "MenuItem"
+            WHERE menu_id = $1
+            "#,
+            menu_id
+        )
+        .fetch_all(tx.as_mut())
+        .await?;
+
+        let mut unmatched_items: Vec<Option<MenuItem>> =
+            existing_items.into_iter().map(Some).collect();
+        let mut items_inserted = 0;
+        let mut items_updated = 0;
+
+        for item in data.items.iter() {
+            // Find the same dish among items which were not matched yet
+            let existing_item = unmatched_items
+                .iter_mut()
+                .find(|existing| {
+                    existing.as_ref().is_some_and(|existing| {
+                        existing.name == item.name && existing.is_soup == item.is_soup
+                    })
+                })
+                .and_then(Option::take);
+
+            match existing_item {
+                Some(existing_item) => {
+                    if existing_item.price == item.price
+                        && existing_item.size == item.size
+                        && existing_item.allergens == item.allergens
+                        && existing_item.is_vegetarian == item.is_vegetarian
+                        && existing_item.is_vegan == item.is_vegan
+                        && existing_item.is_gluten_free == item.is_gluten_free
+                    {
+                        continue;
+                    }
+
+                    sqlx::query!(
+                        r#"
+                        UPDATE "MenuItem"
+                        SET price = $1, size = $2, allergens = $3, is_vegetarian = $4,
+                            is_vegan = $5, is_gluten_free = $6
+                        WHERE id = $7
+                        "#,
+                        item.price,
+                        item.size,
+                        &item.allergens,
+                        item.is_vegetarian,
+                        item.is_vegan,
+                        item.is_gluten_free,
+                        existing_item.id
+                    )
+                    .execute(tx.as_mut())
+                    .await?;
+
+                    items_updated += 1;
+                }
+                None => {
+                    sqlx::query!(
+                        r#"
+                        INSERT INTO "MenuItem" (
+                            name, price, size, is_soup, menu_id, allergens, is_vegetarian,
+                            is_vegan, is_gluten_free
+                        )
+                        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                        "#,
+                        item.name,
+                        item.price,
+                        item.size,
+                        item.is_soup,
+                        menu_id,
+                        &item.allergens,
+                        item.is_vegetarian,
+                        item.is_vegan,
+                        item.is_gluten_free
+                    )
+                    .execute(tx.as_mut())
+                    .await?;
+
+                    items_inserted += 1;
+                }
+            }
+        }
+
+        // Items which are no longer in the menu
+        let removed_item_ids = unmatched_items
+            .into_iter()
+            .flatten()
+            .map(|item| item.id)
+            .collect::<Vec<_>>();
+
+        if !removed_item_ids.is_empty() {
+            sqlx::query!(
+                r#"
+                DELETE FROM "MenuItem"
+                WHERE id = ANY($1)
+                "#,
+                &removed_item_ids
+            )
+            .execute(tx.as_mut())
+            .await?;
+        }
+
+        let menu = Self::get_menu(&MenuGetById::new(&menu_id), &mut tx).await?;
+        let menu = Self::menu_is_correct(menu)?;
+
+        tx.commit().await?;
+
+        Ok(MenuUpsertResult {
+            menu,
+            created,
+            items_inserted,
+            items_updated,
+            items_deleted: removed_item_ids.len(),
+        })
+    }
+}
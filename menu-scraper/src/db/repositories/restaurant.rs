@@ -34,7 +34,8 @@ impl RestaurantRepository {
             r#"
             SELECT id, name, street, house_number, zip_code, city, picture, phone_number, website, email,
                 monday_open, tuesday_open, wednesday_open, thursday_open, friday_open,
-                saturday_open, sunday_open, lunch_served, deleted_at
+                saturday_open, sunday_open, lunch_served, longitude, latitude, geocode_failed_at,
+                deleted_at
             FROM "Restaurant"
             WHERE id = $1
             "#,
@@ -102,12 +103,13 @@ impl DbCreate<RestaurantCreate, Restaurant> for RestaurantRepository {
             r#"
             INSERT INTO "Restaurant" (
                 name, street, house_number, zip_code, city, picture, phone_number, website, email, monday_open, tuesday_open,
-                wednesday_open, thursday_open, friday_open, saturday_open, sunday_open, lunch_served, longitude, latitude
+                wednesday_open, thursday_open, friday_open, saturday_open, sunday_open, lunch_served, longitude, latitude,
+                geocode_failed_at
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20)
             RETURNING id, name, street, house_number, zip_code, city, picture, phone_number, website, email,
                 monday_open, tuesday_open, wednesday_open, thursday_open, friday_open, saturday_open, sunday_open,
-                lunch_served, deleted_at
+                lunch_served, longitude, latitude, geocode_failed_at, deleted_at
             "#,
             data.name,
             data.street,
@@ -127,7 +129,8 @@ impl DbCreate<RestaurantCreate, Restaurant> for RestaurantRepository {
             data.sunday_open,
             data.lunch_served,
             data.longitude,
-            data.latitude
+            data.latitude,
+            data.geocode_failed_at
         )
             .fetch_one(&*self.pool_handler.pool)
             .await?;
@@ -150,6 +153,10 @@ impl DbUpdate<RestaurantUpdate, Restaurant> for RestaurantRepository {
             ("phone_number", &params.phone_number),
             ("website", &params.website),
             ("email", &params.email),
+        ];
+
+        // Opening hours can be cleared
+        let nullable_columns_and_params = [
             ("monday_open", &params.monday_open),
             ("tuesday_open", &params.tuesday_open),
             ("wednesday_open", &params.wednesday_open),
@@ -160,8 +167,23 @@ impl DbUpdate<RestaurantUpdate, Restaurant> for RestaurantRepository {
             ("lunch_served", &params.lunch_served),
         ];
 
+        let location_columns_and_params = [
+            ("longitude", &params.longitude),
+            ("latitude", &params.latitude),
+        ];
+
         // Check if all parameters are none
-        if columns_and_params.map(|x| x.1).iter().all(|x| x.is_none()) {
+        if columns_and_params.map(|x| x.1).iter().all(|x| x.is_none())
+            && nullable_columns_and_params
+                .map(|x| x.1)
+                .iter()
+                .all(|x| x.is_none())
+            && location_columns_and_params
+                .map(|x| x.1)
+                .iter()
+                .all(|x| x.is_none())
+            && params.geocode_failed_at.is_none()
+        {
             return Err(DbError::from(BusinessLogicError::new(
                 BusinessLogicErrorKind::UpdateParametersEmpty,
             )));
@@ -186,12 +208,31 @@ impl DbUpdate<RestaurantUpdate, Restaurant> for RestaurantRepository {
             }
         }
 
+        for (column, value) in nullable_columns_and_params {
+            if let Some(value) = value {
+                separated.push(format!("{column} = "));
+                separated.push_bind_unseparated(value);
+            }
+        }
+
+        for (column, value) in location_columns_and_params {
+            if let Some(value) = value {
+                separated.push(format!("{column} = "));
+                separated.push_bind_unseparated(value);
+            }
+        }
+
+        if let Some(geocode_failed_at) = &params.geocode_failed_at {
+            separated.push("geocode_failed_at = ");
+            separated.push_bind_unseparated(geocode_failed_at);
+        }
+
         // Bind id of the restaurant
         query_builder.push(" WHERE id = ");
         query_builder.push_bind(params.id);
         query_builder.push(" RETURNING id, name, street, house_number, zip_code, picture, city, phone_number, website, email,
             monday_open, tuesday_open, wednesday_open, thursday_open, friday_open, saturday_open, sunday_open,
-            lunch_served, deleted_at");
+            lunch_served, longitude, latitude, geocode_failed_at, deleted_at");
 
         // Construct the query and run it
         let updated_restaurant = query_builder
@@ -221,7 +262,7 @@ impl DbDelete<RestaurantDelete, Restaurant> for RestaurantRepository {
             WHERE id = $1
             RETURNING id, name, street, house_number, zip_code, city, picture, phone_number, website, email,
                 monday_open, tuesday_open, wednesday_open, thursday_open, friday_open, saturday_open, sunday_open,
-                lunch_served, deleted_at
+                lunch_served, longitude, latitude, geocode_failed_at, deleted_at
             "#,
             params.id
         )
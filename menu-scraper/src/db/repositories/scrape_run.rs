@@ -0,0 +1,174 @@
+use crate::db::common::error::{
+    BusinessLogicError, BusinessLogicErrorKind, DbError, DbResultMultiple, DbResultSingle,
+};
+use crate::db::common::{DbCreate, DbReadMany, DbReadOne, DbRepository, PoolHandler};
+use crate::db::models::{
+    ScrapeRun, ScrapeRunCreate, ScrapeRunError, ScrapeRunGetById, ScrapeRunGetMany, ScrapeRunId,
+};
+use async_trait::async_trait;
+use sqlx::{Postgres, Transaction};
+
+#[derive(Clone)]
+pub struct ScrapeRunRepository {
+    pool_handler: PoolHandler,
+}
+
+impl ScrapeRunRepository {
+    /// Function which retrieves a scrape run by its id together with its errors, usable within a
+    /// transaction
+    ///
+    /// # Params
+    /// - params: structure containing the id of the scrape run
+    /// - transaction_handle mutable reference to an ongoing transaction
+    ///
+    /// # Returns
+    /// - Ok(scrape_run): on successful connection and retrieval
+    /// - Err(_): otherwise
+    pub async fn get_scrape_run<'a>(
+        params: &ScrapeRunGetById,
+        transaction_handle: &mut Transaction<'a, Postgres>,
+    ) -> DbResultSingle<Option<ScrapeRun>> {
+        let scrape_run = sqlx::query_as!(
+            ScrapeRun,
+            r#"
+            SELECT
+                S.id AS id,
+                S.started_at AS started_at,
+                S.finished_at AS finished_at,
+                S.restaurants_seen AS restaurants_seen,
+                S.menus_inserted AS menus_inserted,
+                S.menus_updated AS menus_updated,
+                COALESCE(ARRAY_AGG(E.*) FILTER (WHERE E.id IS NOT NULL), '{}') AS "errors!: Vec<ScrapeRunError>"
+            FROM "ScrapeRun" S
+            LEFT JOIN "ScrapeRunError" E ON S.id = E.scrape_run_id
+            WHERE S.id = $1
+            GROUP BY S.id, S.started_at, S.finished_at, S.restaurants_seen, S.menus_inserted, S.menus_updated
+            "#,
+            params.id
+        )
+        .fetch_optional(transaction_handle.as_mut())
+        .await?;
+
+        Ok(scrape_run)
+    }
+
+    /// Function which checks if the scrape run exists
+    ///
+    /// # Params
+    /// - scrape_run: optional scrape run retrieved from the database
+    ///
+    /// # Returns
+    /// - Ok(scrape_run): when the scrape run exists
+    /// - Err(DbError): with appropriate error description otherwise
+    pub fn scrape_run_is_correct(scrape_run: Option<ScrapeRun>) -> DbResultSingle<ScrapeRun> {
+        match scrape_run {
+            Some(scrape_run) => Ok(scrape_run),
+            None => Err(DbError::from(BusinessLogicError::new(
+                BusinessLogicErrorKind::ScrapeRunDoesNotExist,
+            ))),
+        }
+    }
+}
+
+#[async_trait]
+impl DbRepository for ScrapeRunRepository {
+    #[inline]
+    fn new(pool_handler: PoolHandler) -> Self {
+        Self { pool_handler }
+    }
+}
+
+#[async_trait]
+impl DbReadOne<ScrapeRunGetById, ScrapeRun> for ScrapeRunRepository {
+    /// Gets one scrape run from the database with its errors
+    async fn read_one(&self, params: &ScrapeRunGetById) -> DbResultSingle<ScrapeRun> {
+        let mut tx = self.pool_handler.pool.begin().await?;
+
+        let scrape_run = Self::get_scrape_run(params, &mut tx).await?;
+        let scrape_run = Self::scrape_run_is_correct(scrape_run)?;
+        tx.commit().await?;
+
+        Ok(scrape_run)
+    }
+}
+
+#[async_trait]
+impl DbCreate<ScrapeRunCreate, ScrapeRun> for ScrapeRunRepository {
+    /// Stores a finished scrape run with its errors
+    async fn create(&self, data: &ScrapeRunCreate) -> DbResultSingle<ScrapeRun> {
+        let mut tx = self.pool_handler.pool.begin().await?;
+
+        let scrape_run_id = sqlx::query_as!(
+            ScrapeRunId,
+            r#"
+            INSERT INTO "ScrapeRun" (
+                started_at, finished_at, restaurants_seen, menus_inserted, menus_updated
+            )
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id
+            "#,
+            data.started_at,
+            data.finished_at,
+            data.restaurants_seen,
+            data.menus_inserted,
+            data.menus_updated
+        )
+        .fetch_one(tx.as_mut())
+        .await?;
+
+        for error in data.errors.iter() {
+            sqlx::query!(
+                r#"
+                INSERT INTO "ScrapeRunError" (
+                    scrape_run_id, source, restaurant, message
+                )
+                VALUES ($1, $2, $3, $4)
+                "#,
+                scrape_run_id.id,
+                error.source,
+                error.restaurant,
+                error.message
+            )
+            .execute(tx.as_mut())
+            .await?;
+        }
+
+        let scrape_run =
+            Self::get_scrape_run(&ScrapeRunGetById::new(&scrape_run_id.id), &mut tx).await?;
+        let scrape_run = Self::scrape_run_is_correct(scrape_run)?;
+
+        tx.commit().await?;
+
+        Ok(scrape_run)
+    }
+}
+
+#[async_trait]
+impl DbReadMany<ScrapeRunGetMany, ScrapeRun> for ScrapeRunRepository {
+    /// Gets the latest scrape runs with their errors, the newest first
+    async fn read_many(&self, params: &ScrapeRunGetMany) -> DbResultMultiple<ScrapeRun> {
+        let scrape_runs = sqlx::query_as!(
+            ScrapeRun,
+            r#"
+            SELECT
+                S.id AS id,
+                S.started_at AS started_at,
+                S.finished_at AS finished_at,
+                S.restaurants_seen AS restaurants_seen,
+                S.menus_inserted AS menus_inserted,
+                S.menus_updated AS menus_updated,
+                COALESCE(ARRAY_AGG(E.*) FILTER (WHERE E.id IS NOT NULL), '{}') AS "errors!: Vec<ScrapeRunError>"
+            FROM "ScrapeRun" S
+            LEFT JOIN "ScrapeRunError" E ON S.id = E.scrape_run_id
+            GROUP BY S.id, S.started_at, S.finished_at, S.restaurants_seen, S.menus_inserted, S.menus_updated
+            ORDER BY S.started_at DESC
+            LIMIT $1
+            "#,
+            params.limit
+        )
+        .fetch_all(&*self.pool_handler.pool)
+        .await?;
+
+        Ok(scrape_runs)
+    }
+}
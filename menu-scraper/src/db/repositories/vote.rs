@@ -3,12 +3,16 @@ use crate::db::common::error::{
 };
 use crate::db::common::{DbCreate, DbDelete, DbReadMany, DbRepository, PoolHandler};
 use crate::db::models::{
-    LunchGetById, MenuGetById, MenuItem, MenuWithRestaurantAndVotes, Vote, VoteCreate, VoteDelete,
-    VoteGetById, VoteGetMany, VotePreview,
+    LunchGetById, LunchMenuWithRestaurantAndVotes, Menu, MenuGetById, MenuItem,
+    MenuWithRestaurantAndVotes, Veto, VetoCreate, VetoDelete, VetoGetById, VetoPreview, Vote,
+    VoteCreate, VoteDelete, VoteGetById, VoteGetMany, VoteGetManyByLunches, VotePreview,
+    VotingMode, VETO_LIMIT,
 };
 use crate::db::repositories::{GroupRepository, LunchRepository, MenuRepository};
 use async_trait::async_trait;
+use chrono::Utc;
 use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
 
 #[derive(Clone)]
 pub struct VoteRepository {
@@ -44,6 +48,32 @@ impl VoteRepository {
         Ok(vote)
     }
 
+    /// Function which locks the ballot of the user in the lunch until the end of the transaction,
+    /// so concurrent votes of the user replace or rank each other and concurrent vetoes are counted
+    /// one after another. A lock of rows would not cover the first vote of the user.
+    ///
+    /// # Params
+    /// - user_id: id of the voting user
+    /// - lunch_id: id of the lunch
+    /// - transaction_handle mutable reference to an ongoing transaction
+    ///
+    /// # Returns
+    /// - Ok(()): once the lock is held
+    /// - Err(_): otherwise
+    pub async fn lock_ballot<'a>(
+        user_id: &Uuid,
+        lunch_id: &Uuid,
+        transaction_handle: &mut Transaction<'a, Postgres>,
+    ) -> DbResultSingle<()> {
+        sqlx::query("SELECT pg_advisory_xact_lock(hashtextextended($1::text || $2::text, 0))")
+            .bind(user_id)
+            .bind(lunch_id)
+            .execute(transaction_handle.as_mut())
+            .await?;
+
+        Ok(())
+    }
+
     /// Function which checks if the vote is correct (existing and not deleted)
     ///
     /// # Params
@@ -67,6 +97,99 @@ impl VoteRepository {
             ))),
         }
     }
+
+    /// Function which checks that neither the menu nor its restaurant is vetoed in the lunch,
+    /// usable within a transaction
+    ///
+    /// # Params
+    /// - lunch_id: id of the lunch
+    /// - menu: checked menu
+    /// - transaction_handle mutable reference to an ongoing transaction
+    ///
+    /// # Returns
+    /// - Ok(()): when the menu is not vetoed
+    /// - Err(DbError): when it is vetoed or on a database error
+    pub async fn check_menu_is_not_vetoed<'a>(
+        lunch_id: &Uuid,
+        menu: &Menu,
+        transaction_handle: &mut Transaction<'a, Postgres>,
+    ) -> DbResultSingle<()> {
+        let vetoed = sqlx::query!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1
+                FROM "Veto"
+                WHERE lunch_id = $1 AND deleted_at IS NULL AND (menu_id = $2 OR restaurant_id = $3)
+            ) AS "vetoed!"
+            "#,
+            lunch_id,
+            menu.id,
+            menu.restaurant_id
+        )
+        .fetch_one(transaction_handle.as_mut())
+        .await?
+        .vetoed;
+
+        if vetoed {
+            return Err(DbError::from(BusinessLogicError::new(
+                BusinessLogicErrorKind::MenuVetoed,
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Function which retrieves a veto by its id, usable within a transaction
+    ///
+    /// # Params
+    /// - params: structure containing the id of the veto
+    /// - transaction_handle mutable reference to an ongoing transaction
+    ///
+    /// # Returns
+    /// - Ok(veto): on successful connection and retrieval
+    /// - Err(_): otherwise
+    pub async fn get_veto<'a>(
+        params: &VetoGetById,
+        transaction_handle: &mut Transaction<'a, Postgres>,
+    ) -> DbResultSingle<Option<Veto>> {
+        let veto = sqlx::query_as!(
+            Veto,
+            r#"
+            SELECT *
+            FROM "Veto"
+            WHERE id = $1
+            "#,
+            params.id
+        )
+        .fetch_optional(transaction_handle.as_mut())
+        .await?;
+
+        Ok(veto)
+    }
+
+    /// Function which checks if the veto is correct (existing and not withdrawn)
+    ///
+    /// # Params
+    /// - veto: optional veto retrieved from the database
+    ///
+    /// # Returns
+    /// - Ok(veto): when the veto exists and is not withdrawn
+    /// - Err(DbError): with appropriate error description otherwise
+    pub fn veto_is_correct(veto: Option<Veto>) -> DbResultSingle<Veto> {
+        match veto {
+            Some(
+                veto @ Veto {
+                    deleted_at: None, ..
+                },
+            ) => Ok(veto),
+            Some(_) => Err(DbError::from(BusinessLogicError::new(
+                BusinessLogicErrorKind::VetoDeleted,
+            ))),
+            None => Err(DbError::from(BusinessLogicError::new(
+                BusinessLogicErrorKind::VetoDoesNotExist,
+            ))),
+        }
+    }
 }
 
 #[async_trait]
@@ -79,7 +202,8 @@ impl DbRepository for VoteRepository {
 
 #[async_trait]
 impl DbCreate<VoteCreate, Vote> for VoteRepository {
-    /// Creates a new vote for some lunch
+    /// Creates a new vote for some lunch. The previous vote of the user is replaced in plurality
+    /// voting, the menu is ranked as the next choice of the user in ranked-choice voting.
     async fn create(&self, data: &VoteCreate) -> DbResultSingle<Vote> {
         let mut tx = self.pool_handler.pool.begin().await?;
 
@@ -90,6 +214,12 @@ impl DbCreate<VoteCreate, Vote> for VoteRepository {
         let lunch = LunchRepository::get_lunch(&LunchGetById::new(&data.lunch_id), &mut tx).await?;
         let lunch = LunchRepository::lunch_is_correct(lunch)?;
 
+        if lunch.voting_is_closed(Utc::now()) {
+            return Err(DbError::from(BusinessLogicError::new(
+                BusinessLogicErrorKind::VotingClosed,
+            )));
+        }
+
         // Check if lunch and menu have the same date
         if lunch.date != menu.date {
             return Err(DbError::from(BusinessLogicError::new(
@@ -100,18 +230,64 @@ impl DbCreate<VoteCreate, Vote> for VoteRepository {
         // Check if user and group is correct and user is member of the group
         GroupRepository::check_user_is_member_tx(&mut tx, &data.user_id, &lunch.group_id).await?;
 
-        // If user already voted in this lunch, change it to new menu
+        // Votes for vetoed menus would not be counted
+        Self::check_menu_is_not_vetoed(&data.lunch_id, &menu, &mut tx).await?;
+
+        // Other votes of the user are replaced or ranked by this one in every voting mode
+        Self::lock_ballot(&data.user_id, &data.lunch_id, &mut tx).await?;
+
+        let rank = match lunch.voting_mode {
+            VotingMode::Plurality => {
+                // Vote for another menu replaces the previous vote of the user
+                sqlx::query!(
+                    r#"
+                    UPDATE "Vote"
+                    SET deleted_at = now()
+                    WHERE user_id = $1 AND lunch_id = $2 AND menu_id <> $3 AND deleted_at IS NULL
+                    "#,
+                    data.user_id,
+                    data.lunch_id,
+                    data.menu_id
+                )
+                .execute(tx.as_mut())
+                .await?;
+
+                None
+            }
+            VotingMode::Approval => None,
+            VotingMode::Ranked => {
+                let ranks = sqlx::query!(
+                    r#"
+                    SELECT
+                        MAX(rank) FILTER (WHERE menu_id = $3) AS menu_rank,
+                        COALESCE(MAX(rank), 0) AS "last_rank!"
+                    FROM "Vote"
+                    WHERE user_id = $1 AND lunch_id = $2 AND deleted_at IS NULL
+                    "#,
+                    data.user_id,
+                    data.lunch_id,
+                    data.menu_id
+                )
+                .fetch_one(tx.as_mut())
+                .await?;
+
+                // Menu which is already ranked keeps its rank, other menus are the next choice
+                Some(ranks.menu_rank.unwrap_or(ranks.last_rank + 1))
+            }
+        };
+
         let vote = sqlx::query_as!(
             Vote,
             r#"
-            INSERT INTO "Vote" (user_id, lunch_id, menu_id)
-            VALUES ($1, $2, $3)
-            ON CONFLICT (user_id, lunch_id) DO UPDATE SET deleted_at = NULL, menu_id = $3
+            INSERT INTO "Vote" (user_id, lunch_id, menu_id, rank)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (user_id, lunch_id, menu_id) DO UPDATE SET deleted_at = NULL, rank = $4
             RETURNING *;
             "#,
             data.user_id,
             data.lunch_id,
-            data.menu_id
+            data.menu_id,
+            rank
         )
         .fetch_one(tx.as_mut())
         .await?;
@@ -124,12 +300,31 @@ impl DbCreate<VoteCreate, Vote> for VoteRepository {
 
 #[async_trait]
 impl DbDelete<VoteDelete, Vote> for VoteRepository {
+    /// Withdraws a vote of the user
     async fn delete(&self, params: &VoteDelete) -> DbResultMultiple<Vote> {
         let mut tx = self.pool_handler.pool.begin().await?;
 
+        // Ranks of the remaining votes are moved while no other vote of the user is ranked
+        Self::lock_ballot(&params.user_id, &params.lunch_id, &mut tx).await?;
+
         // Check that vote exists and is not already deleted
         let vote = Self::get_vote(&VoteGetById::new(&params.id), &mut tx).await?;
-        Self::vote_is_correct(vote)?;
+        let vote = Self::vote_is_correct(vote)?;
+
+        // Votes of other users and other lunches are not revealed
+        if vote.user_id != params.user_id || vote.lunch_id != params.lunch_id {
+            return Err(DbError::from(BusinessLogicError::new(
+                BusinessLogicErrorKind::VoteDoesNotExist,
+            )));
+        }
+
+        // Votes of finished voting are kept, they decided the winner
+        let lunch = LunchRepository::get_lunch(&LunchGetById::new(&vote.lunch_id), &mut tx).await?;
+        if LunchRepository::lunch_is_correct(lunch)?.voting_is_closed(Utc::now()) {
+            return Err(DbError::from(BusinessLogicError::new(
+                BusinessLogicErrorKind::VotingClosed,
+            )));
+        }
 
         // Delete vote
         let deleted_vote = sqlx::query_as!(
@@ -137,14 +332,31 @@ impl DbDelete<VoteDelete, Vote> for VoteRepository {
             r#"
             UPDATE "Vote"
             SET deleted_at = now()
-            WHERE id = $1
+            WHERE id = $1 AND lunch_id = $2
             RETURNING *
             "#,
-            params.id
+            params.id,
+            params.lunch_id
         )
         .fetch_one(tx.as_mut())
         .await?;
 
+        // Later choices of ranked-choice voting move up
+        if let Some(rank) = deleted_vote.rank {
+            sqlx::query!(
+                r#"
+                UPDATE "Vote"
+                SET rank = rank - 1
+                WHERE user_id = $1 AND lunch_id = $2 AND rank > $3 AND deleted_at IS NULL
+                "#,
+                deleted_vote.user_id,
+                deleted_vote.lunch_id,
+                rank
+            )
+            .execute(tx.as_mut())
+            .await?;
+        }
+
         tx.commit().await?;
 
         Ok(vec![deleted_vote])
@@ -153,7 +365,8 @@ impl DbDelete<VoteDelete, Vote> for VoteRepository {
 
 #[async_trait]
 impl DbReadMany<VoteGetMany, MenuWithRestaurantAndVotes> for VoteRepository {
-    /// Gets votes a lunch grouped by corresponding menu
+    /// Gets votes a lunch grouped by corresponding menu, nominated menus are included even
+    /// without votes and vetoed menus are left out
     async fn read_many(
         &self,
         params: &VoteGetMany,
@@ -179,14 +392,29 @@ impl DbReadMany<VoteGetMany, MenuWithRestaurantAndVotes> for VoteRepository {
                 M.id AS menu_id,
                 M.date AS date,
                 ARRAY_AGG(DISTINCT I.*) AS "items!: Vec<MenuItem>",
-                ARRAY_AGG(DISTINCT (V.id, V.user_id)) AS "votes!: Vec<VotePreview>"
+                ARRAY(
+                    SELECT (VP.id, VP.user_id, VP.rank)
+                    FROM "Vote" VP
+                    WHERE VP.lunch_id = $1 AND VP.menu_id = M.id AND VP.deleted_at IS NULL
+                ) AS "votes!: Vec<VotePreview>"
             FROM "Restaurant" AS R
             JOIN "Menu" AS M ON R.id = M.restaurant_id
             JOIN "MenuItem" AS I ON M.id = I.menu_id
-            JOIN "Vote" AS V ON V.menu_id = M.id
-            WHERE V.lunch_id = $1 AND V.deleted_at IS NULL AND M.deleted_at IS NULL AND R.deleted_at IS NULL
+            LEFT JOIN "Vote" AS V ON V.menu_id = M.id AND V.lunch_id = $1 AND V.deleted_at IS NULL
+            WHERE M.deleted_at IS NULL AND R.deleted_at IS NULL
+                AND (V.id IS NOT NULL OR EXISTS (
+                    SELECT 1
+                    FROM "LunchCandidate" C
+                    WHERE C.lunch_id = $1 AND C.menu_id = M.id
+                ))
+                AND NOT EXISTS (
+                    SELECT 1
+                    FROM "Veto" X
+                    WHERE X.lunch_id = $1 AND X.deleted_at IS NULL
+                        AND (X.menu_id = M.id OR X.restaurant_id = R.id)
+                )
             GROUP BY R.id, R.name, R.street, R.house_number, R.zip_code, R.city, R.picture, M.id, M.date
-            ORDER BY COUNT(V) DESC;
+            ORDER BY COUNT(DISTINCT V.id) DESC;
             "#,
             params.lunch_id
         )
@@ -198,3 +426,227 @@ impl DbReadMany<VoteGetMany, MenuWithRestaurantAndVotes> for VoteRepository {
         Ok(menu_with_votes)
     }
 }
+
+#[async_trait]
+impl DbReadMany<VoteGetManyByLunches, LunchMenuWithRestaurantAndVotes> for VoteRepository {
+    /// Gets votes of several lunches grouped by the lunch and the menu, in one query. Menus are
+    /// included and left out like for a single lunch, deleted lunches are not checked.
+    async fn read_many(
+        &self,
+        params: &VoteGetManyByLunches,
+    ) -> DbResultMultiple<LunchMenuWithRestaurantAndVotes> {
+        let menu_with_votes = sqlx::query_as!(
+            LunchMenuWithRestaurantAndVotes,
+            r#"
+            WITH LM AS (
+                SELECT lunch_id, menu_id
+                FROM "Vote"
+                WHERE lunch_id = ANY($1) AND deleted_at IS NULL
+                UNION
+                SELECT lunch_id, menu_id
+                FROM "LunchCandidate"
+                WHERE lunch_id = ANY($1)
+            )
+            SELECT
+                LM.lunch_id AS "lunch_id!",
+                R.id AS restaurant_id,
+                R.name AS name,
+                R.street AS street,
+                R.house_number AS house_number,
+                R.zip_code AS zip_code,
+                R.city AS city,
+                R.picture AS picture,
+                M.id AS menu_id,
+                M.date AS date,
+                ARRAY_AGG(DISTINCT I.*) AS "items!: Vec<MenuItem>",
+                ARRAY(
+                    SELECT (VP.id, VP.user_id, VP.rank)
+                    FROM "Vote" VP
+                    WHERE VP.lunch_id = LM.lunch_id AND VP.menu_id = M.id AND VP.deleted_at IS NULL
+                ) AS "votes!: Vec<VotePreview>"
+            FROM LM
+            JOIN "Menu" AS M ON M.id = LM.menu_id
+            JOIN "Restaurant" AS R ON R.id = M.restaurant_id
+            JOIN "MenuItem" AS I ON M.id = I.menu_id
+            WHERE M.deleted_at IS NULL AND R.deleted_at IS NULL
+                AND NOT EXISTS (
+                    SELECT 1
+                    FROM "Veto" X
+                    WHERE X.lunch_id = LM.lunch_id AND X.deleted_at IS NULL
+                        AND (X.menu_id = M.id OR X.restaurant_id = R.id)
+                )
+            GROUP BY LM.lunch_id, R.id, R.name, R.street, R.house_number, R.zip_code, R.city, R.picture, M.id, M.date;
+            "#,
+            &params.lunch_ids
+        )
+            .fetch_all(&*self.pool_handler.pool)
+            .await?;
+
+        Ok(menu_with_votes)
+    }
+}
+
+#[async_trait]
+pub trait VoteRepositoryVeto {
+    /// Vetoes a menu (or its whole restaurant) in a lunch, vetoing the same again returns
+    /// the existing veto
+    async fn veto(&self, params: &VetoCreate) -> DbResultSingle<Veto>;
+
+    /// Withdraws a veto of the user in the lunch
+    async fn remove_veto(&self, params: &VetoDelete) -> DbResultSingle<Veto>;
+
+    /// Lists vetoes of a lunch in the order they were cast
+    async fn list_vetoes(&self, params: &VoteGetMany) -> DbResultMultiple<VetoPreview>;
+}
+
+#[async_trait]
+impl VoteRepositoryVeto for VoteRepository {
+    async fn veto(&self, params: &VetoCreate) -> DbResultSingle<Veto> {
+        let mut tx = self.pool_handler.pool.begin().await?;
+
+        // Same checks as for voting
+        let menu = MenuRepository::get_menu(&MenuGetById::new(&params.menu_id), &mut tx).await?;
+        let menu = MenuRepository::menu_is_correct(menu)?;
+
+        let lunch =
+            LunchRepository::get_lunch(&LunchGetById::new(&params.lunch_id), &mut tx).await?;
+        let lunch = LunchRepository::lunch_is_correct(lunch)?;
+
+        if lunch.voting_is_closed(Utc::now()) {
+            return Err(DbError::from(BusinessLogicError::new(
+                BusinessLogicErrorKind::VotingClosed,
+            )));
+        }
+
+        if lunch.date != menu.date {
+            return Err(DbError::from(BusinessLogicError::new(
+                BusinessLogicErrorKind::LunchDateDoesntMatchMenuDate,
+            )));
+        }
+
+        GroupRepository::check_user_is_member_tx(&mut tx, &params.user_id, &lunch.group_id).await?;
+
+        let restaurant_id = params.restaurant.then_some(menu.restaurant_id);
+
+        // Vetoes of the user are counted against the limit one after another
+        Self::lock_ballot(&params.user_id, &params.lunch_id, &mut tx).await?;
+
+        let vetoes = sqlx::query_as!(
+            Veto,
+            r#"
+            SELECT *
+            FROM "Veto"
+            WHERE user_id = $1 AND lunch_id = $2 AND deleted_at IS NULL
+            "#,
+            params.user_id,
+            params.lunch_id
+        )
+        .fetch_all(tx.as_mut())
+        .await?;
+
+        if let Some(veto) = vetoes
+            .iter()
+            .find(|veto| veto.menu_id == menu.id && veto.restaurant_id == restaurant_id)
+        {
+            tx.commit().await?;
+            return Ok(veto.clone());
+        }
+
+        if vetoes.len() as i64 >= VETO_LIMIT {
+            return Err(DbError::from(BusinessLogicError::new(
+                BusinessLogicErrorKind::VetoLimitReached,
+            )));
+        }
+
+        let veto = sqlx::query_as!(
+            Veto,
+            r#"
+            INSERT INTO "Veto" (menu_id, restaurant_id, user_id, lunch_id)
+            VALUES ($1, $2, $3, $4)
+            RETURNING *
+            "#,
+            menu.id,
+            restaurant_id,
+            params.user_id,
+            params.lunch_id
+        )
+        .fetch_one(tx.as_mut())
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(veto)
+    }
+
+    async fn remove_veto(&self, params: &VetoDelete) -> DbResultSingle<Veto> {
+        let mut tx = self.pool_handler.pool.begin().await?;
+
+        let veto = Self::get_veto(&VetoGetById::new(&params.id), &mut tx).await?;
+        let veto = Self::veto_is_correct(veto)?;
+
+        // Vetoes of other users and other lunches cannot be withdrawn
+        if veto.user_id != params.user_id || veto.lunch_id != params.lunch_id {
+            return Err(DbError::from(BusinessLogicError::new(
+                BusinessLogicErrorKind::VetoDoesNotExist,
+            )));
+        }
+
+        let lunch = LunchRepository::get_lunch(&LunchGetById::new(&veto.lunch_id), &mut tx).await?;
+        if LunchRepository::lunch_is_correct(lunch)?.voting_is_closed(Utc::now()) {
+            return Err(DbError::from(BusinessLogicError::new(
+                BusinessLogicErrorKind::VotingClosed,
+            )));
+        }
+
+        let veto = sqlx::query_as!(
+            Veto,
+            r#"
+            UPDATE "Veto"
+            SET deleted_at = now()
+            WHERE id = $1 AND lunch_id = $2
+            RETURNING *
+            "#,
+            params.id,
+            params.lunch_id
+        )
+        .fetch_one(tx.as_mut())
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(veto)
+    }
+
+    async fn list_vetoes(&self, params: &VoteGetMany) -> DbResultMultiple<VetoPreview> {
+        let mut tx = self.pool_handler.pool.begin().await?;
+
+        let lunch =
+            LunchRepository::get_lunch(&LunchGetById::new(&params.lunch_id), &mut tx).await?;
+        LunchRepository::lunch_is_correct(lunch)?;
+
+        let vetoes = sqlx::query_as!(
+            VetoPreview,
+            r#"
+            SELECT
+                X.id,
+                X.user_id,
+                X.menu_id,
+                R.id AS restaurant_id,
+                R.name,
+                X.restaurant_id IS NOT NULL AS "restaurant!"
+            FROM "Veto" X
+            JOIN "Menu" M ON X.menu_id = M.id
+            JOIN "Restaurant" R ON M.restaurant_id = R.id
+            WHERE X.lunch_id = $1 AND X.deleted_at IS NULL
+            ORDER BY X.created_at
+            "#,
+            params.lunch_id
+        )
+        .fetch_all(tx.as_mut())
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(vetoes)
+    }
+}
@@ -1,41 +1,49 @@
-mod app;
-
-use crate::app::handlers::auth::auth_config;
-use crate::app::handlers::group::group_config;
-use crate::app::handlers::index::index_config;
-use crate::app::handlers::lunch::lunch_config;
-use crate::app::handlers::menu::menu_config;
-use crate::app::handlers::registration::registration_config;
-use crate::app::handlers::restaurant::restaurant_config;
-use crate::app::handlers::user::user_config;
-use crate::app::handlers::vote::vote_config;
 use actix_identity::IdentityMiddleware;
 use actix_session::{storage::CookieSessionStore, SessionMiddleware};
 use actix_web::cookie::Key;
 use actix_web::web::{Data, ServiceConfig};
 use actix_web::{web, App, HttpServer};
-use chrono::{FixedOffset, Local};
-use cron::Schedule;
+use db::app::api::api_config;
+use db::app::csrf::Csrf;
+use db::app::handlers::api_token::api_token_config;
+use db::app::handlers::auth::auth_config;
+use db::app::handlers::calendar::calendar_config;
+use db::app::handlers::group::group_config;
+use db::app::handlers::group_invitation::group_invitation_config;
+use db::app::handlers::index::index_config;
+use db::app::handlers::lunch::lunch_config;
+use db::app::handlers::menu::menu_config;
+use db::app::handlers::registration::registration_config;
+use db::app::handlers::restaurant::restaurant_config;
+use db::app::handlers::scrape_run::scrape_run_config;
+use db::app::handlers::user::user_config;
+use db::app::handlers::vote::vote_config;
+use db::app::public_url::PublicUrl;
 use db::db::common::run_migration::run_migration;
 use db::db::common::{DbPoolHandler, DbRepository, PoolHandler};
 use db::db::repositories::{
-    GroupRepository, LunchRepository, MenuRepository, RestaurantRepository, UserRepository,
-    VoteRepository,
+    ApiTokenRepository, CalendarFeedRepository, GroupInvitationRepository, GroupRepository,
+    LoginAttemptRepository, LunchRepository, LunchRepositoryFinalize, MenuRepository,
+    RestaurantRepository, ScrapeRunRepository, UserRepository, VoteRepository,
 };
+use db::scrapping::dietary::DietaryClassifier;
+use db::scrapping::http_client::HttpClient;
+use db::scrapping::service::schedule::ScrapeSchedule;
+use db::scrapping::service::scraping_service::Scraper;
 use env_logger::Env;
 use log::{info, warn};
 use sqlx::postgres::PgPoolOptions;
 use sqlx::{Pool, Postgres};
 use std::env;
-use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
 
-mod scrapping;
-
 const DEFAULT_HOSTNAME: &str = "localhost";
 const DEFAULT_PORT: &str = "8000";
 
+/// How often winners of lunches with passed voting deadline are stored
+const FINALIZATION_PERIOD: Duration = Duration::from_secs(60);
+
 #[actix_web::main]
 async fn main() -> anyhow::Result<()> {
     env_logger::init_from_env(Env::default().default_filter_or("info"));
@@ -59,44 +67,35 @@ async fn main() -> anyhow::Result<()> {
 
     let user_repository = UserRepository::new(PoolHandler::new(pool.clone()));
     let group_repository = GroupRepository::new(PoolHandler::new(pool.clone()));
+    let group_invitation_repository =
+        GroupInvitationRepository::new(PoolHandler::new(pool.clone()));
     let lunch_repository = LunchRepository::new(PoolHandler::new(pool.clone()));
     let menu_repository = MenuRepository::new(PoolHandler::new(pool.clone()));
     let restaurant_repository = RestaurantRepository::new(PoolHandler::new(pool.clone()));
     let vote_repository = VoteRepository::new(PoolHandler::new(pool.clone()));
+    let scrape_run_repository = ScrapeRunRepository::new(PoolHandler::new(pool.clone()));
+    let api_token_repository = ApiTokenRepository::new(PoolHandler::new(pool.clone()));
+    let calendar_feed_repository = CalendarFeedRepository::new(PoolHandler::new(pool.clone()));
+    let login_attempt_repository = LoginAttemptRepository::new(PoolHandler::new(pool.clone()));
 
-    let initial_scrap = scrapping::service::scraping_service::scrap(
-        RestaurantRepository::new(PoolHandler::new(pool.clone())),
-        MenuRepository::new(PoolHandler::new(pool.clone())),
-    );
+    let public_url = PublicUrl::from_env().expect("could not load public base url");
+    let http_client = HttpClient::from_env().expect("could not set up scraping http client");
+    let classifier = DietaryClassifier::from_env().expect("could not load dietary dictionary");
+    let scraper = Scraper::new(pool.clone(), http_client, classifier);
+    let schedule = ScrapeSchedule::from_env().expect("could not parse scraping schedule");
 
-    actix_rt::spawn(async move {
-        let _ = initial_scrap.await;
-    });
-
-    actix_rt::spawn(async move {
-        let expression = "0   8   *     *       *  *  *";
-        let schedule = Schedule::from_str(expression).unwrap();
-        let offset = FixedOffset::east_opt(1 * 3600).unwrap();
-
-        loop {
-            let mut upcoming = schedule.upcoming(offset).take(1);
-            actix_rt::time::sleep(Duration::from_secs(3600)).await;
-            let local = &Local::now();
-
-            if let Some(datetime) = upcoming.next() {
-                if datetime.timestamp() <= local.timestamp() {
-                    let _ = scrapping::service::scraping_service::scrap(
-                        RestaurantRepository::new(PoolHandler::new(pool.clone())),
-                        MenuRepository::new(PoolHandler::new(pool.clone())),
-                    )
-                    .await;
-                }
-            }
-        }
-    });
+    // Scrape right away only when requested or when menus were never scraped before
+    if scrape_on_startup() || !scraper.has_any_run().await? {
+        scraper.spawn();
+    }
+
+    actix_rt::spawn(scraper.clone().run_scheduled(schedule));
+    actix_rt::spawn(finalize_lunches(lunch_repository.clone()));
 
     HttpServer::new(move || {
         App::new()
+            // Checks CSRF tokens of the session, so it is wrapped by the session middleware
+            .wrap(Csrf)
             // Identity middleware
             .wrap(IdentityMiddleware::default())
             .wrap(SessionMiddleware::new(
@@ -110,10 +109,17 @@ async fn main() -> anyhow::Result<()> {
             // Add repositories
             .app_data(Data::new(user_repository.clone()))
             .app_data(Data::new(group_repository.clone()))
+            .app_data(Data::new(group_invitation_repository.clone()))
             .app_data(Data::new(lunch_repository.clone()))
             .app_data(Data::new(menu_repository.clone()))
             .app_data(Data::new(restaurant_repository.clone()))
             .app_data(Data::new(vote_repository.clone()))
+            .app_data(Data::new(scrape_run_repository.clone()))
+            .app_data(Data::new(api_token_repository.clone()))
+            .app_data(Data::new(calendar_feed_repository.clone()))
+            .app_data(Data::new(login_attempt_repository.clone()))
+            .app_data(Data::new(scraper.clone()))
+            .app_data(Data::new(public_url.clone()))
             // Configure endpoints
             .configure(configure_webapp)
     })
@@ -145,18 +151,47 @@ pub fn configure_webapp(config: &mut ServiceConfig) {
             .service(actix_files::Files::new("/static", "./static").prefer_utf8(true))
             // User uploaded files
             .service(actix_files::Files::new("/uploads", "./uploads").prefer_utf8(true))
+            // JSON api
+            .configure(api_config)
             .configure(index_config)
             .configure(registration_config)
             .configure(auth_config)
             .configure(user_config)
+            .configure(api_token_config)
+            .configure(calendar_config)
             .configure(lunch_config)
             .configure(vote_config)
             .configure(menu_config)
             .configure(restaurant_config)
+            .configure(scrape_run_config)
+            .configure(group_invitation_config)
             .configure(group_config),
     );
 }
 
+/// Stores winners of lunches whose voting deadline has passed, lunches shown before that are
+/// finalized right away
+async fn finalize_lunches(lunch_repository: LunchRepository) {
+    let mut interval = actix_rt::time::interval(FINALIZATION_PERIOD);
+
+    loop {
+        interval.tick().await;
+
+        match lunch_repository.finalize_due().await {
+            Ok(lunches) if !lunches.is_empty() => info!("finalized {} lunches", lunches.len()),
+            Ok(_) => {}
+            Err(e) => warn!("could not finalize lunches: {e}"),
+        }
+    }
+}
+
+/// Scraping on every start of the server can be enabled by SCRAPE_ON_STARTUP=true
+fn scrape_on_startup() -> bool {
+    env::var("SCRAPE_ON_STARTUP")
+        .map(|value| value.eq_ignore_ascii_case("true") || value == "1")
+        .unwrap_or(false)
+}
+
 fn parse_host() -> String {
     let hostname = env::var("HOSTNAME").unwrap_or(DEFAULT_HOSTNAME.to_string());
     let port = env::var("PORT").unwrap_or(DEFAULT_PORT.to_string());
@@ -0,0 +1,401 @@
+use anyhow::Context;
+use serde::Deserialize;
+use std::env;
+use std::fs;
+
+/// Beginnings of words marking meat or fish, written without diacritics
+const MEAT_KEYWORDS: &[&str] = &[
+    "maso",
+    "masa",
+    "masem",
+    "masov",
+    "masic",
+    "vepr",
+    "hovez",
+    "kurec",
+    "kure",
+    "kurat",
+    "kruta",
+    "kruti",
+    "krutou",
+    "kachn",
+    "kachni",
+    "husa",
+    "husi",
+    "jehne",
+    "jehnec",
+    "telec",
+    "zverin",
+    "srnc",
+    "jelen",
+    "divocak",
+    "kanci",
+    "kralic",
+    "kralik",
+    "drubez",
+    "slanin",
+    "sunk",
+    "klobas",
+    "parek",
+    "park",
+    "burt",
+    "salam",
+    "spek",
+    "skvark",
+    "skvarek",
+    "uzen",
+    "rizek",
+    "rizk",
+    "steak",
+    "burger",
+    "hamburg",
+    "sekan",
+    "karbanat",
+    "fasir",
+    "kotlet",
+    "panenk",
+    "krkov",
+    "plecko",
+    "plecka",
+    "plecku",
+    "zebr",
+    "zebir",
+    "kolen",
+    "bucek",
+    "buck",
+    "jatr",
+    "jazyk",
+    "drstk",
+    "tlacenk",
+    "jitrnic",
+    "jelit",
+    "kebab",
+    "gyros",
+    "cevap",
+    "prsa",
+    "prsic",
+    "prsou",
+    "stehn",
+    "kridl",
+    "kridel",
+    "pastik",
+    "tatarak",
+    "rosten",
+    "svickov",
+    "gulas",
+    "ptac",
+    "vrabc",
+    "vrabec",
+    "cordon",
+    "spiz",
+    "ragu",
+    "ryb",
+    "losos",
+    "tunak",
+    "tresk",
+    "pstruh",
+    "kapr",
+    "candat",
+    "krevet",
+    "sardin",
+    "ancov",
+    "morsk plod",
+    "chobotn",
+    "kalamar",
+    "musle",
+    "bacon",
+    "chicken",
+    "beef",
+    "pork",
+    "ham",
+    "prosciut",
+    "pancet",
+    "choriz",
+    "pepperon",
+    "tuna",
+    "salmon",
+    "roastbeef",
+];
+
+/// Beginnings of words marking animal products which are not vegan
+const ANIMAL_PRODUCT_KEYWORDS: &[&str] = &[
+    "syr",
+    "mlek",
+    "mlec",
+    "smetan",
+    "masl",
+    "vejc",
+    "vajec",
+    "vajic",
+    "jogurt",
+    "tvaroh",
+    "medem",
+    "medov",
+    "medu",
+    "mozzarel",
+    "parmaz",
+    "parmez",
+    "niva",
+    "eidam",
+    "hermelin",
+    "balkan",
+    "feta",
+    "ricot",
+    "mascarpon",
+    "gorgonzol",
+    "cheddar",
+    "gouda",
+    "emental",
+    "brynz",
+    "slehac",
+    "omelet",
+    "majonez",
+    "tatarsk",
+    "besamel",
+];
+
+/// Beginnings of words of main ingredients of vegetarian dishes, the dish is vegetarian when it
+/// names one of them and no meat
+const VEGETARIAN_KEYWORDS: &[&str] = &[
+    "syr", "zelenin", "houb", "hrib", "zampion", "tofu", "tempeh", "seitan", "soj", "cock",
+    "fazol", "cizrn", "hrach", "falafel", "kvetak", "brokolic", "spenat", "dyn", "cuket", "lilek",
+    "okurk", "rajc", "tvaroh", "vejc", "vajec", "vajic", "omelet", "halloumi", "mozzarel",
+    "hermelin", "palacin", "livanc", "ovocn",
+];
+
+/// Explicit marks of vegetarian dishes
+const VEGETARIAN_MARKS: &[&str] = &["vegetarian", "vegan", "veggie", "bez mas"];
+
+/// Explicit marks of vegan dishes
+const VEGAN_MARKS: &[&str] = &["vegan"];
+
+/// Explicit marks of gluten-free dishes
+const GLUTEN_FREE_MARKS: &[&str] = &["bezlepk", "bez lepk", "gluten free"];
+
+/// Allergens which are never vegetarian - crustaceans, fish, molluscs
+const MEAT_ALLERGENS: &[i32] = &[2, 4, 14];
+
+/// Allergens which are never vegan - eggs, milk
+const ANIMAL_PRODUCT_ALLERGENS: &[i32] = &[3, 7];
+
+/// Gluten allergen - cereals containing gluten
+const GLUTEN_ALLERGEN: i32 = 1;
+
+/// Dietary tags of a menu item
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DietaryTags {
+    pub vegetarian: bool,
+    pub vegan: bool,
+    pub gluten_free: bool,
+}
+
+/// Dictionary correcting the keyword classification. Every word of a phrase is matched as the
+/// beginning of a word in the name, diacritics and case are ignored.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct DietaryDictionary {
+    /// Phrases of vegetarian dishes which contain meat keywords, e.g. "sójový guláš"
+    #[serde(default)]
+    pub allow: Vec<String>,
+    /// Phrases of dishes which are never vegetarian, e.g. "carbonara"
+    #[serde(default)]
+    pub deny: Vec<String>,
+}
+
+impl DietaryDictionary {
+    /// Dictionary used by default
+    pub fn builtin() -> Self {
+        let phrases = |phrases: &[&str]| phrases.iter().map(ToString::to_string).collect();
+
+        Self {
+            // Stems, so declined forms match as well
+            allow: phrases(&[
+                "sójov mas",
+                "sójov guláš",
+                "sójov nudl",
+                "sójov říz",
+                "zeleninov guláš",
+                "houbov guláš",
+                "hříbkov guláš",
+                "fazolov guláš",
+                "čočkov guláš",
+                "zeleninov ragú",
+                "zeleninov vývar",
+                "houbov vývar",
+                "zeleninov karbanát",
+                "zeleninov špíz",
+                "zeleninov burger",
+                "fazolov burger",
+                "cizrnov burger",
+                "květákov říz",
+                "květákov steak",
+                "celerov říz",
+                "celerov steak",
+                "sýrov říz",
+                "uzen sýr",
+                "uzen tofu",
+                "rybízov",
+            ]),
+            deny: phrases(&["vývar", "carbonar", "boloňsk", "bolognes", "rybí"]),
+        }
+    }
+
+    /// Adds phrases of another dictionary
+    pub fn extend(&mut self, other: DietaryDictionary) {
+        self.allow.extend(other.allow);
+        self.deny.extend(other.deny);
+    }
+}
+
+/// Classifies menu items to vegetarian, vegan and gluten-free by keywords in their names and
+/// their allergens
+#[derive(Debug, Clone)]
+pub struct DietaryClassifier {
+    allow: Vec<Vec<String>>,
+    deny: Vec<Vec<String>>,
+}
+
+impl Default for DietaryClassifier {
+    fn default() -> Self {
+        Self::new(DietaryDictionary::builtin())
+    }
+}
+
+impl DietaryClassifier {
+    pub fn new(dictionary: DietaryDictionary) -> Self {
+        let deny = MEAT_KEYWORDS
+            .iter()
+            .map(ToString::to_string)
+            .chain(dictionary.deny);
+
+        Self {
+            allow: dictionary.allow.iter().map(|p| phrase_words(p)).collect(),
+            deny: deny.map(|p| phrase_words(&p)).collect(),
+        }
+    }
+
+    /// Uses the builtin dictionary extended by the JSON dictionary (`{"allow": [], "deny": []}`)
+    /// from the path in DIETARY_DICTIONARY environment variable, when set
+    pub fn from_env() -> anyhow::Result<Self> {
+        let mut dictionary = DietaryDictionary::builtin();
+
+        if let Ok(path) = env::var("DIETARY_DICTIONARY") {
+            let content = fs::read_to_string(&path)
+                .with_context(|| format!("could not read dietary dictionary {path}"))?;
+            let custom = serde_json::from_str(&content)
+                .with_context(|| format!("invalid dietary dictionary {path}"))?;
+            dictionary.extend(custom);
+        }
+
+        Ok(Self::new(dictionary))
+    }
+
+    /// Classifies menu item by its name and allergens. Tags are given only when the name says so,
+    /// vegetarian by a mark or a meatless main ingredient, vegan and gluten-free by a mark. The
+    /// allergens can only deny the tags, menus often list them incompletely.
+    pub fn classify(&self, name: &str, allergens: &[i32]) -> DietaryTags {
+        let words = phrase_words(name);
+        let has_mark = |marks: &[&str]| marks.iter().any(|mark| find(&words, mark).is_some());
+
+        // Words of allowed phrases are not checked for meat
+        let mut allowed = vec![false; words.len()];
+        let mut has_allowed_phrase = false;
+        for phrase in &self.allow {
+            for start in phrase_positions(&words, phrase) {
+                allowed[start..start + phrase.len()].fill(true);
+                has_allowed_phrase = true;
+            }
+        }
+
+        let contains_meat = self.deny.iter().any(|phrase| {
+            phrase_positions(&words, phrase)
+                .any(|start| !allowed[start..start + phrase.len()].iter().all(|a| *a))
+        });
+        let has_allergen = |listed: &[i32]| allergens.iter().any(|a| listed.contains(a));
+
+        let is_meatless = (has_allowed_phrase || has_mark(VEGETARIAN_KEYWORDS)) && !contains_meat;
+        let vegetarian =
+            (has_mark(VEGETARIAN_MARKS) || is_meatless) && !has_allergen(MEAT_ALLERGENS);
+
+        let contains_animal_products = ANIMAL_PRODUCT_KEYWORDS
+            .iter()
+            .any(|keyword| find(&words, keyword).is_some())
+            || has_allergen(ANIMAL_PRODUCT_ALLERGENS);
+        let vegan = vegetarian && has_mark(VEGAN_MARKS) && !contains_animal_products;
+
+        let gluten_free = has_mark(GLUTEN_FREE_MARKS) && !allergens.contains(&GLUTEN_ALLERGEN);
+
+        DietaryTags {
+            vegetarian,
+            vegan,
+            gluten_free,
+        }
+    }
+}
+
+/// Finds the first position of the phrase given as a string
+fn find(words: &[String], phrase: &str) -> Option<usize> {
+    phrase_positions(words, &phrase_words(phrase)).next()
+}
+
+/// Positions of all occurrences of the phrase, every word of the phrase has to be a beginning of
+/// the corresponding word
+fn phrase_positions<'a>(
+    words: &'a [String],
+    phrase: &'a [String],
+) -> impl Iterator<Item = usize> + 'a {
+    let last_start = (words.len() + 1).saturating_sub(phrase.len().max(1));
+
+    (0..last_start).filter(move |start| {
+        !phrase.is_empty()
+            && phrase
+                .iter()
+                .zip(&words[*start..])
+                .all(|(keyword, word)| word.starts_with(keyword.as_str()))
+    })
+}
+
+/// Splits text to lowercase words without diacritics
+fn phrase_words(text: &str) -> Vec<String> {
+    remove_diacritics(&text.to_lowercase())
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(ToString::to_string)
+        .collect()
+}
+
+/// Replaces Czech and Slovak letters with diacritics by their base letters
+pub fn remove_diacritics(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            'á' | 'ä' => 'a',
+            'č' => 'c',
+            'ď' => 'd',
+            'é' | 'ě' => 'e',
+            'í' => 'i',
+            'ĺ' | 'ľ' => 'l',
+            'ň' => 'n',
+            'ó' | 'ô' | 'ö' => 'o',
+            'ř' | 'ŕ' => 'r',
+            'š' => 's',
+            'ť' => 't',
+            'ú' | 'ů' | 'ü' => 'u',
+            'ý' => 'y',
+            'ž' => 'z',
+            'Á' | 'Ä' => 'A',
+            'Č' => 'C',
+            'Ď' => 'D',
+            'É' | 'Ě' => 'E',
+            'Í' => 'I',
+            'Ĺ' | 'Ľ' => 'L',
+            'Ň' => 'N',
+            'Ó' | 'Ô' | 'Ö' => 'O',
+            'Ř' | 'Ŕ' => 'R',
+            'Š' => 'S',
+            'Ť' => 'T',
+            'Ú' | 'Ů' | 'Ü' => 'U',
+            'Ý' => 'Y',
+            'Ž' => 'Z',
+            c => c,
+        })
+        .collect()
+}
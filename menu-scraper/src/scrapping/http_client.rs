@@ -0,0 +1,205 @@
+use anyhow::Context;
+use reqwest::{redirect, Client, Response, StatusCode};
+use std::collections::HashMap;
+use std::env;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::Semaphore;
+use tokio::time::{sleep_until, Instant};
+
+const USER_AGENT: &str = concat!(
+    "menu-scraper/",
+    env!("CARGO_PKG_VERSION"),
+    " (+https://github.com/vboucek/menu-scraper)"
+);
+
+const DEFAULT_CONCURRENCY: usize = 8;
+const DEFAULT_HOST_INTERVAL_MS: u64 = 50;
+const DEFAULT_TIMEOUT_SECS: u64 = 10;
+const DEFAULT_RETRIES: u32 = 2;
+const DEFAULT_RETRY_BACKOFF: Duration = Duration::from_millis(500);
+const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Configuration of the scraping HTTP client
+#[derive(Debug, Clone)]
+pub struct HttpClientConfig {
+    /// Maximal number of requests in flight
+    pub concurrency: usize,
+    /// Minimal delay between two requests to the same host
+    pub host_interval: Duration,
+    /// Timeout of one request (including reading the body)
+    pub timeout: Duration,
+    /// Number of retries of a failed request
+    pub retries: u32,
+    /// Delay before the first retry, doubled before every next one
+    pub retry_backoff: Duration,
+}
+
+impl Default for HttpClientConfig {
+    fn default() -> Self {
+        Self {
+            concurrency: DEFAULT_CONCURRENCY,
+            host_interval: Duration::from_millis(DEFAULT_HOST_INTERVAL_MS),
+            timeout: Duration::from_secs(DEFAULT_TIMEOUT_SECS),
+            retries: DEFAULT_RETRIES,
+            retry_backoff: DEFAULT_RETRY_BACKOFF,
+        }
+    }
+}
+
+impl HttpClientConfig {
+    /// Loads the configuration from SCRAPE_CONCURRENCY, SCRAPE_HOST_INTERVAL_MS,
+    /// SCRAPE_TIMEOUT_SECS and SCRAPE_RETRIES environment variables, missing values are defaulted
+    pub fn from_env() -> anyhow::Result<Self> {
+        fn var<T: FromStr>(name: &str, default: T) -> anyhow::Result<T>
+        where
+            T::Err: std::error::Error + Send + Sync + 'static,
+        {
+            match env::var(name) {
+                Ok(value) => value
+                    .trim()
+                    .parse()
+                    .with_context(|| format!("invalid value of {name}")),
+                Err(_) => Ok(default),
+            }
+        }
+
+        Ok(Self {
+            concurrency: var("SCRAPE_CONCURRENCY", DEFAULT_CONCURRENCY)?.max(1),
+            host_interval: Duration::from_millis(var(
+                "SCRAPE_HOST_INTERVAL_MS",
+                DEFAULT_HOST_INTERVAL_MS,
+            )?),
+            timeout: Duration::from_secs(var("SCRAPE_TIMEOUT_SECS", DEFAULT_TIMEOUT_SECS)?),
+            retries: var("SCRAPE_RETRIES", DEFAULT_RETRIES)?,
+            retry_backoff: DEFAULT_RETRY_BACKOFF,
+        })
+    }
+}
+
+/// HTTP client shared by all menu sources, limits the number of requests in flight, rate limits
+/// requests to each host and retries failed requests with exponential backoff
+#[derive(Debug, Clone)]
+pub struct HttpClient {
+    client: Client,
+    /// Client which does not follow redirects, used for resolving redirect targets
+    no_redirect_client: Client,
+    config: HttpClientConfig,
+    permits: Arc<Semaphore>,
+    /// Time when the next request can be sent to the host
+    next_request_at: Arc<Mutex<HashMap<String, Instant>>>,
+}
+
+impl HttpClient {
+    pub fn new(config: HttpClientConfig) -> anyhow::Result<Self> {
+        let builder = || {
+            Client::builder()
+                .user_agent(USER_AGENT)
+                .timeout(config.timeout)
+                .connect_timeout(config.timeout)
+        };
+
+        Ok(Self {
+            client: builder().build()?,
+            no_redirect_client: builder().redirect(redirect::Policy::none()).build()?,
+            permits: Arc::new(Semaphore::new(config.concurrency)),
+            next_request_at: Arc::new(Mutex::new(HashMap::new())),
+            config,
+        })
+    }
+
+    pub fn from_env() -> anyhow::Result<Self> {
+        Self::new(HttpClientConfig::from_env()?)
+    }
+
+    /// Maximal number of requests in flight, usable for scraping restaurants concurrently
+    pub fn concurrency(&self) -> usize {
+        self.config.concurrency
+    }
+
+    /// Downloads the page, non-success status is an error
+    pub async fn get_text(&self, url: &str) -> anyhow::Result<String> {
+        let response = self.send(&self.client, url).await?;
+        let response = response.error_for_status()?;
+
+        Ok(response.text().await?)
+    }
+
+    /// Gets the target of a redirect, the url itself when it does not redirect
+    pub async fn resolve_redirect(&self, url: &str) -> anyhow::Result<String> {
+        let response = self.send(&self.no_redirect_client, url).await?;
+
+        if response.status().is_redirection() {
+            if let Some(location) = response.headers().get(reqwest::header::LOCATION) {
+                if let Ok(location) = location.to_str() {
+                    return Ok(location.to_string());
+                }
+            }
+        }
+
+        Ok(url.to_string())
+    }
+
+    async fn send(&self, client: &Client, url: &str) -> anyhow::Result<Response> {
+        let host = reqwest::Url::parse(url)
+            .with_context(|| format!("invalid url {url}"))?
+            .host_str()
+            .unwrap_or_default()
+            .to_string();
+
+        let mut attempt = 0;
+        loop {
+            let result = {
+                let _permit = self.permits.acquire().await?;
+                self.wait_for_host(&host).await;
+                client.get(url).send().await
+            };
+
+            let retryable = match &result {
+                Ok(response) => is_retryable_status(response.status()),
+                Err(e) => e.is_timeout() || e.is_connect(),
+            };
+
+            if !retryable || attempt >= self.config.retries {
+                return Ok(result?);
+            }
+
+            attempt += 1;
+            tokio::time::sleep(retry_backoff(self.config.retry_backoff, attempt)).await;
+        }
+    }
+
+    /// Waits until a request to the host can be sent and reserves the slot for it
+    async fn wait_for_host(&self, host: &str) {
+        let send_at = {
+            let mut next_request_at = self
+                .next_request_at
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+            let now = Instant::now();
+            let send_at = next_request_at
+                .get(host)
+                .map_or(now, |next| (*next).max(now));
+            next_request_at.insert(host.to_string(), send_at + self.config.host_interval);
+
+            send_at
+        };
+
+        sleep_until(send_at).await;
+    }
+}
+
+/// Delay before the retry, e.g. 0.5 s, 1 s, 2 s... for the first retries. It stops growing at
+/// 30 s, so any number of retries is fine.
+pub fn retry_backoff(base: Duration, attempt: u32) -> Duration {
+    let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+
+    base.saturating_mul(factor).min(MAX_RETRY_BACKOFF)
+}
+
+/// Too many requests and server errors may pass later, other statuses are final
+pub fn is_retryable_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
@@ -1 +1,4 @@
+pub mod dietary;
+pub mod http_client;
 pub mod service;
+pub mod source;
@@ -1 +1,3 @@
+pub mod progress;
+pub mod schedule;
 pub mod scraping_service;
@@ -0,0 +1,93 @@
+use crate::db::models::ScrapeRun;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::sync::{Arc, Mutex, MutexGuard};
+use uuid::Uuid;
+
+/// Progress of the currently running (or the last finished) scrape
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ScrapeProgress {
+    pub running: bool,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+    /// Source which is being scraped right now
+    pub current_source: Option<String>,
+    pub restaurants_seen: usize,
+    pub restaurants_processed: usize,
+    pub failures: usize,
+    /// Id of the stored scrape run, once the scrape is finished
+    pub scrape_run_id: Option<Uuid>,
+    /// Error which stopped the whole scrape
+    pub error: Option<String>,
+}
+
+/// Shared handle to the scrape progress, only one scrape can run at a time
+#[derive(Debug, Clone, Default)]
+pub struct ScrapeProgressHandle {
+    progress: Arc<Mutex<ScrapeProgress>>,
+}
+
+impl ScrapeProgressHandle {
+    fn lock(&self) -> MutexGuard<'_, ScrapeProgress> {
+        // Progress is only informative, so poisoned lock is still usable
+        self.progress
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Current state of the progress
+    pub fn snapshot(&self) -> ScrapeProgress {
+        self.lock().clone()
+    }
+
+    /// Marks a new scrape as running
+    ///
+    /// # Returns
+    /// - true: when the scrape can start
+    /// - false: when another scrape is already running
+    pub fn try_start(&self) -> bool {
+        let mut progress = self.lock();
+        if progress.running {
+            return false;
+        }
+
+        *progress = ScrapeProgress {
+            running: true,
+            started_at: Some(Utc::now()),
+            ..ScrapeProgress::default()
+        };
+
+        true
+    }
+
+    pub fn source_started(&self, source: &str, restaurants: usize) {
+        let mut progress = self.lock();
+        progress.current_source = Some(source.to_string());
+        progress.restaurants_seen += restaurants;
+    }
+
+    pub fn restaurant_processed(&self, failed: bool) {
+        let mut progress = self.lock();
+        progress.restaurants_processed += 1;
+        if failed {
+            progress.failures += 1;
+        }
+    }
+
+    pub fn source_failed(&self) {
+        self.lock().failures += 1;
+    }
+
+    /// Marks the scrape as finished
+    pub fn finish(&self, result: &anyhow::Result<ScrapeRun>) {
+        let mut progress = self.lock();
+        progress.running = false;
+        progress.current_source = None;
+        progress.finished_at = Some(Utc::now());
+
+        match result {
+            Ok(scrape_run) => progress.scrape_run_id = Some(scrape_run.id),
+            Err(e) => progress.error = Some(format!("{e:#}")),
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use cron::Schedule;
+use std::env;
+use std::str::FromStr;
+
+/// Default schedule, every day at 8:00
+const DEFAULT_SCHEDULE: &str = "0 0 8 * * *";
+const DEFAULT_TIMEZONE: &str = "Europe/Prague";
+
+/// Times of the day when the menus are scraped
+#[derive(Debug, Clone)]
+pub struct ScrapeSchedule {
+    schedules: Vec<Schedule>,
+    timezone: Tz,
+}
+
+impl ScrapeSchedule {
+    /// Parses the schedule
+    ///
+    /// # Params
+    /// - expressions: cron expressions (`sec min hour day month weekday [year]`) separated by `;`,
+    ///   e.g. `0 0 8 * * *; 0 30 10 * * *` for scraping at 8:00 and 10:30
+    /// - timezone: IANA name of the timezone the expressions are evaluated in
+    pub fn parse(expressions: &str, timezone: &str) -> anyhow::Result<Self> {
+        let schedules = expressions
+            .split(';')
+            .map(str::trim)
+            .filter(|expression| !expression.is_empty())
+            .map(|expression| {
+                Schedule::from_str(expression)
+                    .with_context(|| format!("invalid cron expression `{expression}`"))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        if schedules.is_empty() {
+            anyhow::bail!("scraping schedule is empty");
+        }
+
+        let timezone = Tz::from_str(timezone)
+            .map_err(|e| anyhow::anyhow!("invalid timezone `{timezone}`: {e}"))?;
+
+        Ok(Self {
+            schedules,
+            timezone,
+        })
+    }
+
+    /// Loads the schedule from SCRAPE_SCHEDULE and SCRAPE_TIMEZONE environment variables,
+    /// every day at 8:00 in Europe/Prague by default
+    pub fn from_env() -> anyhow::Result<Self> {
+        let expressions =
+            env::var("SCRAPE_SCHEDULE").unwrap_or_else(|_| DEFAULT_SCHEDULE.to_string());
+        let timezone = env::var("SCRAPE_TIMEZONE").unwrap_or_else(|_| DEFAULT_TIMEZONE.to_string());
+
+        Self::parse(&expressions, &timezone)
+    }
+
+    /// Gets the first scheduled time strictly after the given time, daylight saving time is
+    /// respected by the timezone
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let after = after.with_timezone(&self.timezone);
+
+        self.schedules
+            .iter()
+            .filter_map(|schedule| schedule.after(&after).next())
+            .min()
+            .map(|next| next.with_timezone(&Utc))
+    }
+}
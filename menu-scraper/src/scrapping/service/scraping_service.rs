@@ -1,491 +1,443 @@
+use crate::db::common::error::DbResultSingle;
+use crate::db::common::{
+    DbCreate, DbPoolHandler, DbReadMany, DbReadOne, DbRepository, DbUpdate, PoolHandler,
+};
+use crate::db::models::{
+    Restaurant, RestaurantCreate, RestaurantGetById, RestaurantGetByNameAndAddress,
+    RestaurantUpdate, ScrapeRun, ScrapeRunCreate, ScrapeRunErrorCreate, ScrapeRunGetMany,
+};
+use crate::db::repositories::{
+    MenuRepository, MenuRepositoryUpsert, RestaurantRepository, ScrapeRunRepository,
+    SearchRestaurant,
+};
+use crate::scrapping::dietary::DietaryClassifier;
+use crate::scrapping::http_client::HttpClient;
+use crate::scrapping::service::progress::{ScrapeProgress, ScrapeProgressHandle};
+use crate::scrapping::service::schedule::ScrapeSchedule;
+use crate::scrapping::source::{configured_sources, MenuSource, ScrapedRestaurant};
 use actix_rt::task;
-use anyhow::Context;
-use chrono::NaiveDate;
-use db::db::common::DbCreate;
-use db::db::models::{MenuCreate, MenuItemCreate, RestaurantCreate, RestaurantGetByNameAndAddress};
-use db::db::repositories::{MenuRepository, RestaurantRepository, SearchRestaurant};
+use chrono::{Duration, Utc};
 use geocoding::{Forward, Opencage};
-use regex::Regex;
-use reqwest::{redirect, Client};
-use scraper::element_ref::Select;
-use scraper::{Html, Selector};
+use log::{error, info, warn};
+use sqlx::PgPool;
 use std::env;
-use uuid::Uuid;
+use std::sync::Arc;
 
-struct RestaurantAddress {
-    street: String,
-    number: String,
-    zip: String,
-    city: String,
-}
+/// Time after which geocoding of an address which failed before is tried again
+const GEOCODE_RETRY_COOLDOWN: Duration = Duration::days(7);
 
-pub async fn scrap(
+/// Runs scraping of all configured sources, makes sure only one scrape runs at a time and keeps
+/// track of its progress
+#[derive(Clone)]
+pub struct Scraper {
     restaurant_repo: RestaurantRepository,
     menu_repo: MenuRepository,
-) -> anyhow::Result<()> {
-    let html_content = reqwest::get("https://www.menicka.cz/brno.html")
-        .await?
-        .text()
-        .await?;
-    let document = Html::parse_document(&html_content);
-    let html_selector = Selector::parse("div.menicka_detail").unwrap();
-    let menu_list = document.select(&html_selector);
-
-    for menu in menu_list {
-        let restaurant_link = menu
-            .select(&Selector::parse("a.noborder").unwrap())
-            .next()
-            .context("No restaurant link")?
-            .value()
-            .attr("href")
-            .context("No restaurant link")?
-            .to_owned();
-
-        let _ = scrap_restaurant(
-            restaurant_link,
-            &restaurant_repo.clone(),
-            &menu_repo.clone(),
-        )
-        .await;
-    }
-    Ok(())
+    scrape_run_repo: ScrapeRunRepository,
+    progress: ScrapeProgressHandle,
+    client: HttpClient,
+    classifier: Arc<DietaryClassifier>,
 }
 
-async fn get_restaurant_html(link: String) -> anyhow::Result<Html> {
-    let response = reqwest::get(link).await?;
-    let html_content = response.text().await?;
-    Ok(Html::parse_document(&html_content))
-}
+impl Scraper {
+    pub fn new(pool: Arc<PgPool>, client: HttpClient, classifier: DietaryClassifier) -> Self {
+        Self {
+            restaurant_repo: RestaurantRepository::new(PoolHandler::new(pool.clone())),
+            menu_repo: MenuRepository::new(PoolHandler::new(pool.clone())),
+            scrape_run_repo: ScrapeRunRepository::new(PoolHandler::new(pool)),
+            progress: ScrapeProgressHandle::default(),
+            client,
+            classifier: Arc::new(classifier),
+        }
+    }
 
-async fn scrap_restaurant(
-    link: String,
-    restaurant_repo: &RestaurantRepository,
-    menu_repo: &MenuRepository,
-) -> anyhow::Result<()> {
-    let document = get_restaurant_html(link).await?;
-    let name = get_restaurant_name(&document)?.replace("&amp;", "");
-    let address = get_restaurant_address(&document)?;
+    /// Progress of the running or the last finished scrape
+    pub fn progress(&self) -> ScrapeProgress {
+        self.progress.snapshot()
+    }
 
-    let open_hours = get_restaurant_open_hours(&document);
-    let lunch_time = get_lunch_time(&document);
-    let img_link = get_image_link(&document);
-    let phone = get_restaurant_phone(&document).await;
-    let email = get_restaurant_email(&document).await;
-    let www = get_restaurant_www(&document).await;
+    /// Checks if menus were ever scraped
+    pub async fn has_any_run(&self) -> DbResultSingle<bool> {
+        let runs = self
+            .scrape_run_repo
+            .read_many(&ScrapeRunGetMany { limit: 1 })
+            .await?;
 
-    let get_restaurant = RestaurantGetByNameAndAddress {
-        name,
-        street: address.street,
-        house_number: address.number,
-        zip_code: address.zip,
-        city: address.city,
-    };
+        Ok(!runs.is_empty())
+    }
 
-    let found_restaurant = restaurant_repo.search_restaurant(&get_restaurant).await?;
+    /// Scrapes all configured sources and waits for the result
+    ///
+    /// # Returns
+    /// - None: when another scrape is already running
+    /// - Some(result): result of the scrape otherwise
+    pub async fn run(&self) -> Option<anyhow::Result<ScrapeRun>> {
+        if !self.progress.try_start() {
+            return None;
+        }
 
-    if let Some(restaurant_id) = found_restaurant {
-        let menus = get_restaurant_menus(&document, restaurant_id.id)?;
-        for menu in menus {
-            if menu.items.is_empty() {
-                continue;
-            }
+        Some(self.run_started().await)
+    }
 
-            menu_repo.create(&menu).await?;
+    /// Starts scraping in the background
+    ///
+    /// # Returns
+    /// - true: when the scrape was started
+    /// - false: when another scrape is already running
+    pub fn spawn(&self) -> bool {
+        // Claimed before spawning, so two requests can not both start a scrape
+        if !self.progress.try_start() {
+            return false;
         }
-    } else {
-        let address = format!(
-            "{} {}, {} {}, Czech Republic",
-            get_restaurant.street,
-            get_restaurant.house_number,
-            get_restaurant.zip_code,
-            get_restaurant.city,
-        );
-
-        let res = task::spawn_blocking(move || {
-            let oc = Opencage::new(env::var("GEO_KEY").expect("GEO_KEY must be set."));
-            oc.forward(&address)
-        })
-        .await;
 
-        let (long, lat) = match res {
-            Ok(Ok(v)) => match v[..] {
-                [point, ..] => (Some(point.x()), Some(point.y())),
-                _ => (None, None),
-            },
-            _ => (None, None),
-        };
+        let scraper = self.clone();
+        actix_rt::spawn(async move {
+            // Failure is logged and kept in the progress
+            let _ = scraper.run_started().await;
+        });
 
-        let restaurant_create = RestaurantCreate {
-            name: get_restaurant.name,
-            street: get_restaurant.street,
-            house_number: get_restaurant.house_number,
-            zip_code: get_restaurant.zip_code,
-            city: get_restaurant.city,
-            picture: img_link,
-            phone_number: phone,
-            website: www,
-            email,
-            monday_open: open_hours[0].to_owned(),
-            tuesday_open: open_hours[1].to_owned(),
-            wednesday_open: open_hours[2].to_owned(),
-            thursday_open: open_hours[3].to_owned(),
-            friday_open: open_hours[4].to_owned(),
-            saturday_open: open_hours[5].to_owned(),
-            sunday_open: open_hours[6].to_owned(),
-            lunch_served: lunch_time,
-            longitude: long,
-            latitude: lat,
-        };
+        true
+    }
 
-        let restaurant_id = restaurant_repo.create(&restaurant_create).await?;
-        let menus = get_restaurant_menus(&document, restaurant_id.id)?;
-        for menu in menus {
-            if menu.items.is_empty() {
-                continue;
-            }
+    /// Scrapes all configured sources, the scrape has to be started in the progress already
+    async fn run_started(&self) -> anyhow::Result<ScrapeRun> {
+        let result = scrap(
+            &configured_sources(&self.client),
+            self.restaurant_repo.clone(),
+            self.menu_repo.clone(),
+            self.scrape_run_repo.clone(),
+            &self.classifier,
+            &self.progress,
+        )
+        .await;
 
-            menu_repo.create(&menu).await?;
+        if let Err(e) = &result {
+            error!("scraping failed: {e:#}");
         }
-    }
 
-    Ok(())
-}
-
-fn get_restaurant_menus(html: &Html, restaurant_id: Uuid) -> anyhow::Result<Vec<MenuCreate>> {
-    let selector = Selector::parse("div.menicka").unwrap();
-    let menu_elements = html.select(&selector);
-    let mut menus: Vec<MenuCreate> = Vec::new();
-    for menu_element in menu_elements {
-        let date_selector = Selector::parse("div.nadpis").unwrap();
-        let date_element = menu_element
-            .select(&date_selector)
-            .next()
-            .context("No restaurant date")?;
-        let title = remove_trailing_tags(date_element.inner_html())
-            .trim()
-            .to_string();
-        let date = parse_menu_date_from_title(title.as_str())?;
-
-        let soups_selector = Selector::parse("li.polevka").unwrap();
-        let soup_elements = menu_element.select(&soups_selector);
-        let soups = get_menu_soups(soup_elements)?;
-        let meals_selector = Selector::parse("li.jidlo").unwrap();
-        let meals_element = menu_element.select(&meals_selector);
-        let meals = get_menu_meals(meals_element)?;
-        let mut menu_items: Vec<MenuItemCreate> = Vec::new();
-        menu_items.extend(soups);
-        menu_items.extend(meals);
-        let menu = MenuCreate {
-            date,
-            items: menu_items,
-            restaurant_id,
-        };
+        self.progress.finish(&result);
 
-        menus.push(menu);
+        result
     }
 
-    Ok(menus)
-}
-
-fn get_menu_meals(select: Select) -> anyhow::Result<Vec<MenuItemCreate>> {
-    let mut meals: Vec<MenuItemCreate> = Vec::new();
-    for meal_element in select {
-        let name_selector = Selector::parse("div.polozka").unwrap();
-        let name = meal_element.select(&name_selector).next();
-        let Some(name) = name else {
-            return Ok(meals);
-        };
-        let name = get_cleared_meal_name(
-            remove_trailing_tags(remove_leading_tags(name.inner_html()).replace("&nbsp;", " "))
-                .trim(),
-        );
+    /// Scrapes the menus at the scheduled times, never returns
+    pub async fn run_scheduled(self, schedule: ScrapeSchedule) {
+        loop {
+            let Some(next) = schedule.next_after(Utc::now()) else {
+                warn!("scraping schedule has no upcoming time, scheduled scraping stopped");
+                return;
+            };
 
-        let size = extract_food_size(name.as_str()).trim().to_string();
-        let name = name.replace(size.as_str(), "").trim().to_string();
+            info!("next scraping scheduled at {next}");
 
-        let price_selector = Selector::parse("div.cena").unwrap();
-        let price = meal_element.select(&price_selector).next();
+            // Sleep can not be negative, next run is in the past when scraping took too long
+            let wait = (next - Utc::now()).to_std().unwrap_or_default();
+            actix_rt::time::sleep(wait).await;
 
-        let mut item = MenuItemCreate {
-            name,
-            is_soup: false,
-            size,
-            price: 0,
-        };
-
-        let Some(price) = price else {
-            meals.push(item);
-            continue;
-        };
-        let price_string: String = price
-            .inner_html()
-            .chars()
-            .filter(char::is_ascii_digit)
-            .collect();
-        item.price = price_string.parse()?;
-        meals.push(item);
+            if self.run().await.is_none() {
+                info!("skipping scheduled scraping, another scraping is running");
+            }
+        }
     }
-
-    Ok(meals)
 }
 
-fn get_menu_soups(select: Select) -> anyhow::Result<Vec<MenuItemCreate>> {
-    let mut soups: Vec<MenuItemCreate> = Vec::new();
-    for soup_element in select {
-        let name_selector = Selector::parse("div.polozka").unwrap();
-        let name = soup_element.select(&name_selector).next();
-        let Some(name) = name else {
-            return Ok(soups);
+/// Scrapes all given menu sources and stores their restaurants and menus with dietary tags of their
+/// items. Failure of one source or one restaurant is logged and the rest is still stored.
+/// Statistics and failures of the run are stored as a scrape run.
+pub async fn scrap(
+    sources: &[Box<dyn MenuSource>],
+    restaurant_repo: RestaurantRepository,
+    menu_repo: MenuRepository,
+    scrape_run_repo: ScrapeRunRepository,
+    classifier: &DietaryClassifier,
+    progress: &ScrapeProgressHandle,
+) -> anyhow::Result<ScrapeRun> {
+    let started_at = Utc::now();
+    let mut restaurants_seen = 0;
+    let mut menus_inserted = 0;
+    let mut menus_updated = 0;
+    let mut errors = Vec::new();
+
+    for source in sources {
+        let results = match source.fetch().await {
+            Ok(results) => results,
+            Err(e) => {
+                warn!("scraping source {} failed: {e:#}", source.name());
+                progress.source_failed();
+                errors.push(ScrapeRunErrorCreate {
+                    source: source.name().to_string(),
+                    restaurant: None,
+                    message: format!("{e:#}"),
+                });
+                continue;
+            }
         };
-        let name = remove_trailing_tags(name.inner_html())
-            .trim()
-            .replace("&nbsp;", " ");
 
-        let size = extract_food_size(name.as_str()).trim().to_string();
-        let name = name.replace(size.as_str(), "").trim().to_string();
+        info!(
+            "scraped {} restaurants from {}",
+            results.len(),
+            source.name()
+        );
+        restaurants_seen += results.len() as i32;
+        progress.source_started(source.name(), results.len());
+
+        for result in results {
+            let scraped = match result {
+                Ok(scraped) => scraped,
+                Err(e) => {
+                    warn!("scraping restaurant failed: {e}");
+                    progress.restaurant_processed(true);
+                    errors.push(ScrapeRunErrorCreate {
+                        source: source.name().to_string(),
+                        restaurant: Some(e.link),
+                        message: format!("{:#}", e.error),
+                    });
+                    continue;
+                }
+            };
+
+            let name = scraped.restaurant.name.clone();
+            match store_restaurant(scraped, &restaurant_repo, &menu_repo, classifier).await {
+                Ok(stored) => {
+                    menus_inserted += stored.menus_inserted;
+                    menus_updated += stored.menus_updated;
+                    progress.restaurant_processed(false);
+                }
+                Err(e) => {
+                    warn!("storing restaurant {name} failed: {e:#}");
+                    progress.restaurant_processed(true);
+                    errors.push(ScrapeRunErrorCreate {
+                        source: source.name().to_string(),
+                        restaurant: Some(name),
+                        message: format!("{e:#}"),
+                    });
+                }
+            }
+        }
+    }
 
-        let price_selector = Selector::parse("div.cena").unwrap();
-        let price = soup_element.select(&price_selector).next();
+    let scrape_run = scrape_run_repo
+        .create(&ScrapeRunCreate {
+            started_at,
+            finished_at: Utc::now(),
+            restaurants_seen,
+            menus_inserted,
+            menus_updated,
+            errors,
+        })
+        .await?;
 
-        let mut item = MenuItemCreate {
-            is_soup: true,
-            name,
-            price: 0,
-            size,
-        };
-        let Some(price) = price else {
-            soups.push(item);
-            continue;
-        };
-        let price_string: String = price
-            .inner_html()
-            .chars()
-            .filter(char::is_ascii_digit)
-            .collect();
-        item.price = price_string.parse()?;
-        soups.push(item);
-    }
+    info!(
+        "scraping finished: {} restaurants, {} new menus, {} updated menus, {} failures",
+        scrape_run.restaurants_seen,
+        scrape_run.menus_inserted,
+        scrape_run.menus_updated,
+        scrape_run.errors.len()
+    );
 
-    Ok(soups)
+    Ok(scrape_run)
 }
 
-fn parse_menu_date_from_title(title: &str) -> anyhow::Result<NaiveDate> {
-    let date_string = title
-        .split(' ')
-        .last()
-        .context("Error while parsing menu date")?;
-    let date_arr = date_string
-        .split('.')
-        .map(ToString::to_string)
-        .collect::<Vec<String>>();
-    let date = NaiveDate::from_ymd_opt(
-        date_arr[2].parse()?,
-        date_arr[1].parse()?,
-        date_arr[0].parse()?,
-    )
-    .context("Error constructing menu date")?;
-    Ok(date)
+/// Number of menus stored for one restaurant
+#[derive(Debug, Default)]
+struct StoredMenus {
+    menus_inserted: i32,
+    menus_updated: i32,
 }
 
-async fn get_restaurant_phone(html: &Html) -> Option<String> {
-    let link = html
-        .select(&Selector::parse("a.telefon").unwrap())
-        .next()?
-        .value()
-        .attr("href")?
-        .to_owned();
-    let link = link.replacen('.', "https://www.menicka.cz", 1);
-    let html_content = reqwest::get(link).await.ok()?.text().await.ok()?;
-    let document = Html::parse_document(&html_content);
-    let selector = Selector::parse("a").unwrap();
-    let mut phone_element = document.select(&selector);
-    let phone = phone_element.next()?;
-    Some(phone.inner_html())
-}
+/// Stores scraped restaurant (if it does not exist yet) and its menus, already existing menus
+/// are updated
+async fn store_restaurant(
+    scraped: ScrapedRestaurant,
+    restaurant_repo: &RestaurantRepository,
+    menu_repo: &MenuRepository,
+    classifier: &DietaryClassifier,
+) -> anyhow::Result<StoredMenus> {
+    let ScrapedRestaurant { restaurant, menus } = scraped;
 
-async fn get_restaurant_email(html: &Html) -> Option<String> {
-    let link = html
-        .select(&Selector::parse("a.email").unwrap())
-        .next()?
-        .value()
-        .attr("href")?
-        .to_owned();
-    let link = link.replacen('.', "https://www.menicka.cz", 1);
-    let html_content = reqwest::get(link).await.ok()?.text().await.ok()?;
-    let document = Html::parse_document(&html_content);
-    let selector = Selector::parse("a").unwrap();
-    let mut email_element = document.select(&selector);
-    let email = email_element.next()?;
-    Some(email.inner_html().trim().to_string())
-}
+    let get_restaurant = RestaurantGetByNameAndAddress {
+        name: restaurant.name.clone(),
+        street: restaurant.street.clone(),
+        house_number: restaurant.house_number.clone(),
+        zip_code: restaurant.zip_code.clone(),
+        city: restaurant.city.clone(),
+    };
 
-async fn get_restaurant_www(html: &Html) -> Option<String> {
-    let link = html
-        .select(&Selector::parse("a.web").unwrap())
-        .next()?
-        .value()
-        .attr("href")?
-        .to_owned();
-    let link = link.replacen('.', "https://www.menicka.cz", 1);
+    let found_restaurant = restaurant_repo.search_restaurant(&get_restaurant).await?;
 
-    resolve_redirect(link).await.ok()
-}
+    let restaurant_id = if let Some(restaurant_id) = found_restaurant {
+        let stored = restaurant_repo
+            .read_one(&RestaurantGetById::new(&restaurant_id.id))
+            .await?;
+        refresh_restaurant(stored, restaurant, restaurant_repo).await?;
+        restaurant_id.id
+    } else {
+        let restaurant = geocode_restaurant(restaurant).await;
+        restaurant_repo.create(&restaurant).await?.id
+    };
 
-async fn resolve_redirect(url: String) -> Result<String, reqwest::Error> {
-    let client = Client::builder()
-        .redirect(redirect::Policy::none())
-        .build()?;
-    let response = client.get(url.clone()).send().await?;
+    let mut stored = StoredMenus::default();
+    for mut menu in menus {
+        if menu.items.is_empty() {
+            continue;
+        }
 
-    if response.status().is_redirection() {
-        if let Some(location) = response.headers().get("Location") {
-            if let Ok(location) = location.to_str() {
-                return Ok(location.to_string());
-            }
+        menu.restaurant_id = restaurant_id;
+        for item in menu.items.iter_mut() {
+            let tags = classifier.classify(&item.name, &item.allergens);
+            item.is_vegetarian = tags.vegetarian;
+            item.is_vegan = tags.vegan;
+            item.is_gluten_free = tags.gluten_free;
+        }
+
+        let result = menu_repo.upsert(&menu).await?;
+
+        if result.created {
+            stored.menus_inserted += 1;
+        } else if result.is_changed() {
+            stored.menus_updated += 1;
         }
     }
 
-    Ok(url)
+    Ok(stored)
 }
 
-fn get_image_link(html: &Html) -> Option<String> {
-    let restaurant_links = html
-        .select(&Selector::parse("img.photo").unwrap())
-        .collect::<Vec<_>>();
-
-    if restaurant_links.is_empty() {
-        return None;
+/// Updates details of an already stored restaurant which changed since the last scrape
+async fn refresh_restaurant(
+    stored: Restaurant,
+    mut scraped: RestaurantCreate,
+    restaurant_repo: &RestaurantRepository,
+) -> anyhow::Result<()> {
+    // Restaurant stored without location gets another chance, after a while when geocoding of
+    // its address failed before
+    if (stored.longitude.is_none() || stored.latitude.is_none())
+        && stored
+            .geocode_failed_at
+            .is_none_or(|failed_at| Utc::now() - failed_at >= GEOCODE_RETRY_COOLDOWN)
+    {
+        scraped = geocode_restaurant(scraped).await;
     }
 
-    let restaurant_link = if restaurant_links.len() > 1 {
-        restaurant_links[1]
-    } else {
-        restaurant_links[0]
-    };
-    let src = restaurant_link.value().attr("src");
-    let relative_link = src.map(ToString::to_string);
-
-    if let Some(link) = relative_link {
-        return Some(link.replace("..", "https://www.menicka.cz"));
+    if let Some(update) = restaurant_update(&stored, &scraped) {
+        info!("updating details of restaurant {}", stored.name);
+        restaurant_repo.update(&update).await?;
     }
 
-    None
+    Ok(())
 }
 
-fn get_lunch_time(html: &Html) -> Option<String> {
-    let selector = Selector::parse("div.obedovycas").unwrap();
-    let mut div = html.select(&selector);
-    let time = div.next();
-    match time {
-        None => None,
-        Some(lunch) => {
-            let html = Html::parse_document(lunch.inner_html().as_str());
-            let em = html.select(&Selector::parse("em").unwrap()).next();
-            em.map(|lunch_time| lunch_time.inner_html().trim().to_string())
+/// Computes update of the stored restaurant from the scraped one, None when nothing changed
+///
+/// Missing contacts and location never overwrite the stored ones, e.g. contact page which failed
+/// to load does not mean the contact was removed. Opening hours are on the page of the restaurant
+/// itself, so they are taken as scraped, missing ones included. Name and address are not
+/// compared, because the restaurant was found by them.
+pub fn restaurant_update(
+    stored: &Restaurant,
+    scraped: &RestaurantCreate,
+) -> Option<RestaurantUpdate> {
+    fn changed<T: PartialEq + Clone>(stored: &Option<T>, scraped: &Option<T>) -> Option<T> {
+        match scraped {
+            Some(value) if stored.as_ref() != Some(value) => Some(value.clone()),
+            _ => None,
         }
     }
-}
 
-fn get_restaurant_open_hours(html: &Html) -> Vec<Option<String>> {
-    let selector = Selector::parse("span.cas").unwrap();
-    let times = html.select(&selector);
-    let mut result: Vec<Option<String>> = Vec::new();
-    for time in times {
-        let time_str = time.inner_html();
-        if time_str.is_empty() {
-            result.push(None);
-        } else {
-            result.push(Some(time_str));
-        }
+    fn replaced<T: PartialEq + Clone>(stored: &T, scraped: &T) -> Option<T> {
+        (stored != scraped).then(|| scraped.clone())
     }
 
-    result
-}
+    // Failure of geocoding is remembered until the restaurant gets its location
+    let geocode_failed_at = if scraped.longitude.is_some() && scraped.latitude.is_some() {
+        stored.geocode_failed_at.map(|_| None)
+    } else {
+        scraped.geocode_failed_at.map(Some)
+    };
 
-fn get_restaurant_name(html: &Html) -> anyhow::Result<String> {
-    let html = html
-        .select(&Selector::parse("h1").unwrap())
-        .next()
-        .context("Error getting restaurant name")?
-        .inner_html();
-    Ok(remove_trailing_tags(html))
-}
+    let update = RestaurantUpdate {
+        id: stored.id,
+        name: None,
+        street: None,
+        house_number: None,
+        zip_code: None,
+        city: None,
+        picture: changed(&stored.picture, &scraped.picture),
+        phone_number: changed(&stored.phone_number, &scraped.phone_number),
+        website: changed(&stored.website, &scraped.website),
+        email: changed(&stored.email, &scraped.email),
+        monday_open: replaced(&stored.monday_open, &scraped.monday_open),
+        tuesday_open: replaced(&stored.tuesday_open, &scraped.tuesday_open),
+        wednesday_open: replaced(&stored.wednesday_open, &scraped.wednesday_open),
+        thursday_open: replaced(&stored.thursday_open, &scraped.thursday_open),
+        friday_open: replaced(&stored.friday_open, &scraped.friday_open),
+        saturday_open: replaced(&stored.saturday_open, &scraped.saturday_open),
+        sunday_open: replaced(&stored.sunday_open, &scraped.sunday_open),
+        lunch_served: replaced(&stored.lunch_served, &scraped.lunch_served),
+        longitude: changed(&stored.longitude, &scraped.longitude),
+        latitude: changed(&stored.latitude, &scraped.latitude),
+        geocode_failed_at,
+    };
 
-fn get_restaurant_address(html: &Html) -> anyhow::Result<RestaurantAddress> {
-    let address_html = html
-        .select(&Selector::parse("div.adresa").unwrap())
-        .next()
-        .context("No restaurant address")?
-        .inner_html();
-
-    let address = Html::parse_document(&address_html)
-        .select(&Selector::parse("a").unwrap())
-        .next()
-        .context("No restaurant address")?
-        .inner_html();
-
-    let mut arr = address.split(", ");
-    let street = arr
-        .next()
-        .context("No restaurant street")?
-        .trim()
-        .to_string();
-    let number = arr
-        .next()
-        .context("No restaurant number")?
-        .trim()
-        .to_string();
-    let zip = arr.next().context("No restaurant zip")?.trim().to_string();
-    let city = arr.next().context("No restaurant city")?.trim().to_string();
-
-    if let Some(fifth_thing) = arr.next() {
-        return Ok(RestaurantAddress {
-            street: number,
-            number: zip,
-            zip: city,
-            city: fifth_thing.trim().to_string(),
-        });
+    let contacts = [
+        &update.picture,
+        &update.phone_number,
+        &update.website,
+        &update.email,
+    ];
+    let opening_hours = [
+        &update.monday_open,
+        &update.tuesday_open,
+        &update.wednesday_open,
+        &update.thursday_open,
+        &update.friday_open,
+        &update.saturday_open,
+        &update.sunday_open,
+        &update.lunch_served,
+    ];
+
+    if contacts.iter().all(|contact| contact.is_none())
+        && opening_hours.iter().all(|hours| hours.is_none())
+        && update.longitude.is_none()
+        && update.latitude.is_none()
+        && update.geocode_failed_at.is_none()
+    {
+        return None;
     }
 
-    Ok(RestaurantAddress {
-        street,
-        number,
-        zip,
-        city,
-    })
+    Some(update)
 }
 
-fn remove_trailing_tags(str: String) -> String {
-    let regex = Regex::new("^(.*?)<").unwrap();
-    let m = regex.find(str.as_str());
-    match m {
-        None => str,
-        Some(m) => str[m.start()..m.end() - 1].to_string(),
+/// Fills in location of a restaurant, if the source did not provide it. The time of the failure
+/// is set when the address could not be geocoded.
+async fn geocode_restaurant(mut restaurant: RestaurantCreate) -> RestaurantCreate {
+    if restaurant.longitude.is_some() && restaurant.latitude.is_some() {
+        return restaurant;
     }
-}
 
-fn remove_leading_tags(str: String) -> String {
-    let regex = Regex::new("^(.*?)</span>").unwrap();
-    let m = regex.find(str.as_str());
-    match m {
-        None => str,
-        Some(m) => str[m.end()..str.len()].to_string(),
-    }
-}
+    let address = format!(
+        "{} {}, {} {}, Czech Republic",
+        restaurant.street, restaurant.house_number, restaurant.zip_code, restaurant.city,
+    );
 
-fn extract_food_size(str: &str) -> String {
-    let regex = Regex::new(r"([\d.]+|0([,.])\s*\d+)\s*(g|ml|l)\s").unwrap();
-    let m = regex.find(str);
-    match m {
-        None => String::new(),
-        Some(m) => str[m.start()..m.end()].to_string(),
+    let res = task::spawn_blocking(move || {
+        let oc = Opencage::new(env::var("GEO_KEY").expect("GEO_KEY must be set."));
+        oc.forward(&address)
+    })
+    .await;
+
+    let (long, lat) = match res {
+        Ok(Ok(v)) => match v[..] {
+            [point, ..] => (Some(point.x()), Some(point.y())),
+            _ => (None, None),
+        },
+        _ => (None, None),
+    };
+
+    restaurant.longitude = long;
+    restaurant.latitude = lat;
+    if long.is_none() || lat.is_none() {
+        warn!("geocoding of restaurant {} failed", restaurant.name);
+        restaurant.geocode_failed_at = Some(Utc::now());
     }
-}
 
-fn get_cleared_meal_name(str: &str) -> String {
-    let regex =
-        Regex::new(r"^(T\. \d+.\s+|[A-Z]\)\.\s+|[A-Za-z]+\s+\d+:\s*|\d+\.\s+|[A-E]\s+|\d+\)\s+)")
-            .unwrap();
-    let cleaned = regex.replace(str, "");
-    cleaned.to_string()
+    restaurant
 }
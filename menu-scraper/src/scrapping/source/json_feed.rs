@@ -0,0 +1,157 @@
+use crate::db::models::{MenuCreate, MenuItemCreate, RestaurantCreate};
+use crate::scrapping::http_client::HttpClient;
+use crate::scrapping::source::menu_source::{
+    MenuSource, RestaurantScrapError, RestaurantScrapResult, ScrapedRestaurant,
+};
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use serde::Deserialize;
+use uuid::Uuid;
+
+/// Restaurant in the JSON feed
+#[derive(Debug, Clone, Deserialize)]
+pub struct FeedRestaurant {
+    pub name: String,
+    pub street: String,
+    pub house_number: String,
+    pub zip_code: String,
+    pub city: String,
+    pub picture: Option<String>,
+    pub phone_number: Option<String>,
+    pub website: Option<String>,
+    pub email: Option<String>,
+    /// Opening hours from monday to sunday
+    #[serde(default)]
+    pub opening_hours: Vec<Option<String>>,
+    pub lunch_served: Option<String>,
+    pub longitude: Option<f64>,
+    pub latitude: Option<f64>,
+    #[serde(default)]
+    pub menus: Vec<FeedMenu>,
+}
+
+/// Daily menu in the JSON feed
+#[derive(Debug, Clone, Deserialize)]
+pub struct FeedMenu {
+    pub date: NaiveDate,
+    pub items: Vec<FeedMenuItem>,
+}
+
+/// Menu item in the JSON feed
+#[derive(Debug, Clone, Deserialize)]
+pub struct FeedMenuItem {
+    pub name: String,
+    #[serde(default)]
+    pub price: i32,
+    #[serde(default)]
+    pub size: String,
+    #[serde(default)]
+    pub is_soup: bool,
+    /// Numbers of EU allergens (1-14)
+    #[serde(default)]
+    pub allergens: Vec<i32>,
+}
+
+impl From<FeedRestaurant> for ScrapedRestaurant {
+    fn from(feed: FeedRestaurant) -> Self {
+        let open = |day: usize| feed.opening_hours.get(day).cloned().flatten();
+
+        let restaurant = RestaurantCreate {
+            name: feed.name,
+            street: feed.street,
+            house_number: feed.house_number,
+            zip_code: feed.zip_code,
+            city: feed.city,
+            picture: feed.picture,
+            phone_number: feed.phone_number,
+            website: feed.website,
+            email: feed.email,
+            monday_open: open(0),
+            tuesday_open: open(1),
+            wednesday_open: open(2),
+            thursday_open: open(3),
+            friday_open: open(4),
+            saturday_open: open(5),
+            sunday_open: open(6),
+            lunch_served: feed.lunch_served,
+            longitude: feed.longitude,
+            latitude: feed.latitude,
+            geocode_failed_at: None,
+        };
+
+        let menus = feed
+            .menus
+            .into_iter()
+            .map(|menu| MenuCreate {
+                date: menu.date,
+                restaurant_id: Uuid::nil(),
+                items: menu
+                    .items
+                    .into_iter()
+                    .map(|mut item| {
+                        item.allergens.sort_unstable();
+                        item.allergens.dedup();
+
+                        MenuItemCreate {
+                            name: item.name,
+                            price: item.price,
+                            size: item.size,
+                            is_soup: item.is_soup,
+                            allergens: item.allergens,
+                            is_vegetarian: false,
+                            is_vegan: false,
+                            is_gluten_free: false,
+                        }
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        ScrapedRestaurant { restaurant, menus }
+    }
+}
+
+/// Menu source reading restaurants and menus from a JSON feed (array of `FeedRestaurant`)
+#[derive(Debug, Clone)]
+pub struct JsonFeedSource {
+    url: String,
+    client: HttpClient,
+}
+
+impl JsonFeedSource {
+    pub fn new(url: &str, client: HttpClient) -> Self {
+        Self {
+            url: url.to_owned(),
+            client,
+        }
+    }
+}
+
+#[async_trait]
+impl MenuSource for JsonFeedSource {
+    fn name(&self) -> &str {
+        &self.url
+    }
+
+    async fn fetch(&self) -> anyhow::Result<Vec<RestaurantScrapResult>> {
+        let content = self.client.get_text(&self.url).await?;
+
+        // Parse restaurants one by one, so one malformed restaurant does not discard the whole feed
+        let values: Vec<serde_json::Value> = serde_json::from_str(&content)?;
+
+        let results = values
+            .into_iter()
+            .enumerate()
+            .map(|(index, value)| {
+                serde_json::from_value::<FeedRestaurant>(value)
+                    .map(ScrapedRestaurant::from)
+                    .map_err(|error| RestaurantScrapError {
+                        link: format!("{}#{}", self.url, index),
+                        error: error.into(),
+                    })
+            })
+            .collect();
+
+        Ok(results)
+    }
+}
@@ -0,0 +1,90 @@
+use crate::scrapping::http_client::HttpClient;
+use crate::scrapping::source::menicka_parser::{
+    parse_contact_page, parse_restaurant_links, parse_restaurant_page,
+};
+use crate::scrapping::source::menu_source::{
+    MenuSource, RestaurantScrapError, RestaurantScrapResult, ScrapedRestaurant,
+};
+use async_trait::async_trait;
+use futures::stream::{self, StreamExt};
+use scraper::Html;
+
+const MENICKA_BRNO_URL: &str = "https://www.menicka.cz/brno.html";
+
+/// Menu source scraping restaurants listed on menicka.cz
+#[derive(Debug, Clone)]
+pub struct MenickaSource {
+    url: String,
+    client: HttpClient,
+}
+
+impl MenickaSource {
+    /// Creates source scraping restaurants of Brno
+    pub fn new(client: HttpClient) -> Self {
+        Self {
+            url: MENICKA_BRNO_URL.to_string(),
+            client,
+        }
+    }
+}
+
+#[async_trait]
+impl MenuSource for MenickaSource {
+    fn name(&self) -> &str {
+        "menicka.cz"
+    }
+
+    async fn fetch(&self) -> anyhow::Result<Vec<RestaurantScrapResult>> {
+        let links = parse_restaurant_links(&get_html(&self.client, &self.url).await?)?;
+
+        // Restaurants are scraped concurrently, the client limits requests in flight and rate
+        // limits them
+        let results = stream::iter(links)
+            .map(|link| async move {
+                scrap_restaurant(&self.client, &link)
+                    .await
+                    .map_err(|error| RestaurantScrapError { link, error })
+            })
+            .buffered(self.client.concurrency())
+            .collect()
+            .await;
+
+        Ok(results)
+    }
+}
+
+async fn get_html(client: &HttpClient, link: &str) -> anyhow::Result<Html> {
+    let html_content = client.get_text(link).await?;
+    Ok(Html::parse_document(&html_content))
+}
+
+async fn scrap_restaurant(client: &HttpClient, link: &str) -> anyhow::Result<ScrapedRestaurant> {
+    let page = parse_restaurant_page(&get_html(client, link).await?)?;
+    let mut restaurant = page.restaurant;
+
+    let (phone_number, email, website) = tokio::join!(
+        get_contact(client, page.phone_link),
+        get_contact(client, page.email_link),
+        get_website(client, page.website_link),
+    );
+
+    restaurant.phone_number = phone_number;
+    restaurant.email = email;
+    restaurant.website = website;
+
+    Ok(ScrapedRestaurant {
+        restaurant,
+        menus: page.menus,
+    })
+}
+
+/// Fetches page with a contact of the restaurant, contact is optional so any failure results in None
+async fn get_contact(client: &HttpClient, link: Option<String>) -> Option<String> {
+    let document = get_html(client, &link?).await.ok()?;
+    parse_contact_page(&document)
+}
+
+/// Website of the restaurant is behind a redirect on menicka.cz
+async fn get_website(client: &HttpClient, link: Option<String>) -> Option<String> {
+    client.resolve_redirect(&link?).await.ok()
+}
@@ -0,0 +1,488 @@
+use crate::db::models::{MenuCreate, MenuItemCreate, RestaurantCreate};
+use anyhow::Context;
+use chrono::NaiveDate;
+use regex::Regex;
+use scraper::element_ref::Select;
+use scraper::{ElementRef, Html, Selector};
+use uuid::Uuid;
+
+const MENICKA_URL: &str = "https://www.menicka.cz";
+
+/// Address of the restaurant as listed on menicka.cz
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RestaurantAddress {
+    pub street: String,
+    pub number: String,
+    pub zip: String,
+    pub city: String,
+}
+
+/// Everything parsed from the detail page of a restaurant. Contacts are hidden behind separate
+/// pages on menicka.cz, so only links to them are parsed and fetching is left to the caller.
+#[derive(Debug, Clone)]
+pub struct RestaurantPage {
+    pub restaurant: RestaurantCreate,
+    pub menus: Vec<MenuCreate>,
+    pub phone_link: Option<String>,
+    pub email_link: Option<String>,
+    pub website_link: Option<String>,
+}
+
+/// Parses links to detail pages of restaurants from the listing page
+pub fn parse_restaurant_links(html: &Html) -> anyhow::Result<Vec<String>> {
+    let html_selector = Selector::parse("div.menicka_detail").unwrap();
+    let menu_list = html.select(&html_selector);
+
+    let mut links = Vec::new();
+    for menu in menu_list {
+        let restaurant_link = menu
+            .select(&Selector::parse("a.noborder").unwrap())
+            .next()
+            .context("No restaurant link")?
+            .value()
+            .attr("href")
+            .context("No restaurant link")?
+            .to_owned();
+
+        links.push(restaurant_link);
+    }
+
+    Ok(links)
+}
+
+/// Parses the detail page of a restaurant, menus are returned with nil restaurant id
+pub fn parse_restaurant_page(html: &Html) -> anyhow::Result<RestaurantPage> {
+    let name = get_restaurant_name(html)?.replace("&amp;", "");
+    let address = get_restaurant_address(html)?;
+    let open_hours = get_restaurant_open_hours(html);
+
+    let restaurant = RestaurantCreate {
+        name,
+        street: address.street,
+        house_number: address.number,
+        zip_code: address.zip,
+        city: address.city,
+        picture: get_image_link(html),
+        phone_number: None,
+        website: None,
+        email: None,
+        monday_open: open_hours.first().cloned().flatten(),
+        tuesday_open: open_hours.get(1).cloned().flatten(),
+        wednesday_open: open_hours.get(2).cloned().flatten(),
+        thursday_open: open_hours.get(3).cloned().flatten(),
+        friday_open: open_hours.get(4).cloned().flatten(),
+        saturday_open: open_hours.get(5).cloned().flatten(),
+        sunday_open: open_hours.get(6).cloned().flatten(),
+        lunch_served: get_lunch_time(html),
+        // Restaurant is geocoded by the scraping service while its location is not stored
+        longitude: None,
+        latitude: None,
+        geocode_failed_at: None,
+    };
+
+    Ok(RestaurantPage {
+        restaurant,
+        menus: get_restaurant_menus(html, Uuid::nil())?,
+        phone_link: get_contact_link(html, "a.telefon"),
+        email_link: get_contact_link(html, "a.email"),
+        website_link: get_contact_link(html, "a.web"),
+    })
+}
+
+/// Parses the page with a phone number or email of the restaurant
+pub fn parse_contact_page(html: &Html) -> Option<String> {
+    let selector = Selector::parse("a").unwrap();
+    let contact = html.select(&selector).next()?;
+    Some(contact.inner_html().trim().to_string())
+}
+
+/// Gets absolute link of the contact (phone, email, website) page
+fn get_contact_link(html: &Html, selector: &str) -> Option<String> {
+    let link = html
+        .select(&Selector::parse(selector).unwrap())
+        .next()?
+        .value()
+        .attr("href")?
+        .to_owned();
+
+    Some(link.replacen('.', MENICKA_URL, 1))
+}
+
+/// Parses all daily menus listed on the detail page of a restaurant
+pub fn get_restaurant_menus(html: &Html, restaurant_id: Uuid) -> anyhow::Result<Vec<MenuCreate>> {
+    let selector = Selector::parse("div.menicka").unwrap();
+    let menu_elements = html.select(&selector);
+    let mut menus: Vec<MenuCreate> = Vec::new();
+    for menu_element in menu_elements {
+        let date_selector = Selector::parse("div.nadpis").unwrap();
+        let date_element = menu_element
+            .select(&date_selector)
+            .next()
+            .context("No restaurant date")?;
+        let title = remove_trailing_tags(date_element.inner_html())
+            .trim()
+            .to_string();
+        let date = parse_menu_date_from_title(title.as_str())?;
+
+        let soups_selector = Selector::parse("li.polevka").unwrap();
+        let soup_elements = menu_element.select(&soups_selector);
+        let soups = get_menu_soups(soup_elements)?;
+        let meals_selector = Selector::parse("li.jidlo").unwrap();
+        let meals_element = menu_element.select(&meals_selector);
+        let meals = get_menu_meals(meals_element)?;
+        let mut menu_items: Vec<MenuItemCreate> = Vec::new();
+        menu_items.extend(soups);
+        menu_items.extend(meals);
+        let menu = MenuCreate {
+            date,
+            items: menu_items,
+            restaurant_id,
+        };
+
+        menus.push(menu);
+    }
+
+    Ok(menus)
+}
+
+pub fn get_menu_meals(select: Select) -> anyhow::Result<Vec<MenuItemCreate>> {
+    let mut meals: Vec<MenuItemCreate> = Vec::new();
+    for meal_element in select {
+        let name_selector = Selector::parse("div.polozka").unwrap();
+        let name_element = meal_element.select(&name_selector).next();
+        let Some(name_element) = name_element else {
+            return Ok(meals);
+        };
+        let name = get_cleared_meal_name(
+            remove_trailing_tags(
+                remove_leading_tags(name_element.inner_html()).replace("&nbsp;", " "),
+            )
+            .trim(),
+        );
+        let (name, allergens) = get_item_allergens(name_element, &name);
+
+        let size = extract_food_size(name.as_str()).trim().to_string();
+        let name = name.replace(size.as_str(), "").trim().to_string();
+
+        let price_selector = Selector::parse("div.cena").unwrap();
+        let price = meal_element.select(&price_selector).next();
+
+        let mut item = MenuItemCreate {
+            name,
+            is_soup: false,
+            size,
+            price: 0,
+            allergens,
+            is_vegetarian: false,
+            is_vegan: false,
+            is_gluten_free: false,
+        };
+
+        if let Some(price) = price {
+            item.price = parse_price(price)?;
+        }
+        meals.push(item);
+    }
+
+    Ok(meals)
+}
+
+pub fn get_menu_soups(select: Select) -> anyhow::Result<Vec<MenuItemCreate>> {
+    let mut soups: Vec<MenuItemCreate> = Vec::new();
+    for soup_element in select {
+        let name_selector = Selector::parse("div.polozka").unwrap();
+        let name_element = soup_element.select(&name_selector).next();
+        let Some(name_element) = name_element else {
+            return Ok(soups);
+        };
+        let name = remove_trailing_tags(name_element.inner_html())
+            .trim()
+            .replace("&nbsp;", " ");
+        let (name, allergens) = get_item_allergens(name_element, &name);
+
+        let size = extract_food_size(name.as_str()).trim().to_string();
+        let name = name.replace(size.as_str(), "").trim().to_string();
+
+        let price_selector = Selector::parse("div.cena").unwrap();
+        let price = soup_element.select(&price_selector).next();
+
+        let mut item = MenuItemCreate {
+            is_soup: true,
+            name,
+            price: 0,
+            size,
+            allergens,
+            is_vegetarian: false,
+            is_vegan: false,
+            is_gluten_free: false,
+        };
+        if let Some(price) = price {
+            item.price = parse_price(price)?;
+        }
+        soups.push(item);
+    }
+
+    Ok(soups)
+}
+
+/// Gets allergens of a menu item together with its name without them. Menicka.cz lists allergens
+/// in an `em` element after the name, some restaurants write them at the end of the name itself.
+fn get_item_allergens(name_element: ElementRef, name: &str) -> (String, Vec<i32>) {
+    let (name, mut allergens) = extract_allergens(name);
+
+    let em_selector = Selector::parse("em").unwrap();
+    let listed = name_element
+        .select(&em_selector)
+        .filter_map(|em| parse_allergen_list(&em.inner_html()))
+        .flatten();
+    allergens.extend(listed);
+
+    allergens.sort_unstable();
+    allergens.dedup();
+
+    (name, allergens)
+}
+
+/// Splits allergens written at the end of the meal name, e.g. "Guláš, knedlík 1,3,7" or
+/// "Guláš (A: 1, 3, 7)". A single number is taken as allergen only when it is marked as one, so
+/// names like "Menu 2" stay untouched, and numbers like "1.5" only after "A:" or "alergeny".
+pub fn extract_allergens(name: &str) -> (String, Vec<i32>) {
+    let regex = Regex::new(
+        r"(?i)\s*(\()?\s*(a:|a\.|alergeny:?)?\s*\b((?:\d{1,2}[a-h]?\s*[,.;]\s*)*\d{1,2}[a-h]?)\s*\)?\s*$",
+    )
+    .unwrap();
+
+    let Some(captures) = regex.captures(name) else {
+        return (name.to_string(), Vec::new());
+    };
+
+    let list = &captures[3];
+    // "1.5" is rather a decimal number (e.g. a volume) than allergens, unless they are named
+    let is_decimal = list.as_bytes().windows(3).any(|window| {
+        window[0].is_ascii_digit() && window[1] == b'.' && window[2].is_ascii_digit()
+    });
+    let is_marked = captures.get(2).is_some()
+        || (!is_decimal && (captures.get(1).is_some() || list.contains([',', '.', ';'])));
+
+    match parse_allergen_list(list) {
+        Some(allergens) if is_marked => {
+            let name = name[..captures.get(0).unwrap().start()].trim().to_string();
+            (name, allergens)
+        }
+        _ => (name.to_string(), Vec::new()),
+    }
+}
+
+/// Parses list of allergen numbers, e.g. "1a,3,7". Allergens are numbered 1-14 in the EU, letters
+/// specifying the kind of cereal or nut are ignored. Anything else means the text is not a list
+/// of allergens.
+pub fn parse_allergen_list(list: &str) -> Option<Vec<i32>> {
+    let mut allergens = Vec::new();
+
+    for allergen in list
+        .split([',', '.', ';', ' '])
+        .map(str::trim)
+        .filter(|allergen| !allergen.is_empty())
+    {
+        let number: i32 = allergen
+            .trim_end_matches(|c: char| c.is_ascii_alphabetic())
+            .parse()
+            .ok()?;
+
+        if !(1..=14).contains(&number) {
+            return None;
+        }
+        allergens.push(number);
+    }
+
+    if allergens.is_empty() {
+        return None;
+    }
+
+    allergens.sort_unstable();
+    allergens.dedup();
+    Some(allergens)
+}
+
+/// Parses price from the price element, price element without any digits means the price is
+/// not listed
+fn parse_price(price: ElementRef) -> anyhow::Result<i32> {
+    let price_string: String = price
+        .inner_html()
+        .chars()
+        .filter(char::is_ascii_digit)
+        .collect();
+
+    if price_string.is_empty() {
+        return Ok(0);
+    }
+
+    Ok(price_string.parse()?)
+}
+
+/// Parses date of the menu from its title, e.g. "Pondělí 15.1.2024"
+pub fn parse_menu_date_from_title(title: &str) -> anyhow::Result<NaiveDate> {
+    let date_string = title
+        .split(' ')
+        .next_back()
+        .context("Error while parsing menu date")?;
+    let date_arr = date_string
+        .split('.')
+        .map(ToString::to_string)
+        .collect::<Vec<String>>();
+
+    if date_arr.len() != 3 {
+        return Err(anyhow::anyhow!("Error while parsing menu date"));
+    }
+
+    let date = NaiveDate::from_ymd_opt(
+        date_arr[2].parse()?,
+        date_arr[1].parse()?,
+        date_arr[0].parse()?,
+    )
+    .context("Error constructing menu date")?;
+    Ok(date)
+}
+
+fn get_image_link(html: &Html) -> Option<String> {
+    let restaurant_links = html
+        .select(&Selector::parse("img.photo").unwrap())
+        .collect::<Vec<_>>();
+
+    if restaurant_links.is_empty() {
+        return None;
+    }
+
+    let restaurant_link = if restaurant_links.len() > 1 {
+        restaurant_links[1]
+    } else {
+        restaurant_links[0]
+    };
+    let src = restaurant_link.value().attr("src");
+    let relative_link = src.map(ToString::to_string);
+
+    if let Some(link) = relative_link {
+        return Some(link.replace("..", MENICKA_URL));
+    }
+
+    None
+}
+
+fn get_lunch_time(html: &Html) -> Option<String> {
+    let selector = Selector::parse("div.obedovycas").unwrap();
+    let mut div = html.select(&selector);
+    let time = div.next();
+    match time {
+        None => None,
+        Some(lunch) => {
+            let html = Html::parse_document(lunch.inner_html().as_str());
+            let em = html.select(&Selector::parse("em").unwrap()).next();
+            em.map(|lunch_time| lunch_time.inner_html().trim().to_string())
+        }
+    }
+}
+
+fn get_restaurant_open_hours(html: &Html) -> Vec<Option<String>> {
+    let selector = Selector::parse("span.cas").unwrap();
+    let times = html.select(&selector);
+    let mut result: Vec<Option<String>> = Vec::new();
+    for time in times {
+        let time_str = time.inner_html();
+        if time_str.is_empty() {
+            result.push(None);
+        } else {
+            result.push(Some(time_str));
+        }
+    }
+
+    result
+}
+
+fn get_restaurant_name(html: &Html) -> anyhow::Result<String> {
+    let html = html
+        .select(&Selector::parse("h1").unwrap())
+        .next()
+        .context("Error getting restaurant name")?
+        .inner_html();
+    Ok(remove_trailing_tags(html))
+}
+
+/// Parses address of the restaurant, some restaurants list their name as the first part of the address
+pub fn get_restaurant_address(html: &Html) -> anyhow::Result<RestaurantAddress> {
+    let address_html = html
+        .select(&Selector::parse("div.adresa").unwrap())
+        .next()
+        .context("No restaurant address")?
+        .inner_html();
+
+    let address = Html::parse_document(&address_html)
+        .select(&Selector::parse("a").unwrap())
+        .next()
+        .context("No restaurant address")?
+        .inner_html();
+
+    let mut arr = address.split(", ");
+    let street = arr
+        .next()
+        .context("No restaurant street")?
+        .trim()
+        .to_string();
+    let number = arr
+        .next()
+        .context("No restaurant number")?
+        .trim()
+        .to_string();
+    let zip = arr.next().context("No restaurant zip")?.trim().to_string();
+    let city = arr.next().context("No restaurant city")?.trim().to_string();
+
+    if let Some(fifth_thing) = arr.next() {
+        return Ok(RestaurantAddress {
+            street: number,
+            number: zip,
+            zip: city,
+            city: fifth_thing.trim().to_string(),
+        });
+    }
+
+    Ok(RestaurantAddress {
+        street,
+        number,
+        zip,
+        city,
+    })
+}
+
+fn remove_trailing_tags(str: String) -> String {
+    let regex = Regex::new("^(.*?)<").unwrap();
+    let m = regex.find(str.as_str());
+    match m {
+        None => str,
+        Some(m) => str[m.start()..m.end() - 1].to_string(),
+    }
+}
+
+fn remove_leading_tags(str: String) -> String {
+    let regex = Regex::new("^(.*?)</span>").unwrap();
+    let m = regex.find(str.as_str());
+    match m {
+        None => str,
+        Some(m) => str[m.end()..str.len()].to_string(),
+    }
+}
+
+pub fn extract_food_size(str: &str) -> String {
+    let regex = Regex::new(r"([\d.]+|0([,.])\s*\d+)\s*(g|ml|l)\s").unwrap();
+    let m = regex.find(str);
+    match m {
+        None => String::new(),
+        Some(m) => str[m.start()..m.end()].to_string(),
+    }
+}
+
+pub fn get_cleared_meal_name(str: &str) -> String {
+    let regex =
+        Regex::new(r"^(T\. \d+.\s+|[A-Z]\)\.\s+|[A-Za-z]+\s+\d+:\s*|\d+\.\s+|[A-E]\s+|\d+\)\s+)")
+            .unwrap();
+    let cleaned = regex.replace(str, "");
+    cleaned.to_string()
+}
@@ -0,0 +1,45 @@
+use crate::db::models::{MenuCreate, RestaurantCreate};
+use async_trait::async_trait;
+use std::fmt::{Display, Formatter};
+
+/// Restaurant scraped by some menu source together with its menus
+///
+/// Restaurant of the menus is not known before the restaurant is stored, so sources leave
+/// `restaurant_id` of the menus as nil and the scraping service fills it in.
+#[derive(Debug, Clone)]
+pub struct ScrapedRestaurant {
+    pub restaurant: RestaurantCreate,
+    pub menus: Vec<MenuCreate>,
+}
+
+/// Error of scraping a single restaurant, carries the link (or other identifier) of the restaurant
+/// so the failure can be reported
+#[derive(Debug)]
+pub struct RestaurantScrapError {
+    pub link: String,
+    pub error: anyhow::Error,
+}
+
+impl Display for RestaurantScrapError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {:#}", self.link, self.error)
+    }
+}
+
+/// Result of scraping a single restaurant
+pub type RestaurantScrapResult = Result<ScrapedRestaurant, RestaurantScrapError>;
+
+/// Provider of restaurants and their menus (menicka.cz, restaurant website, JSON feed...)
+#[async_trait]
+pub trait MenuSource: Send + Sync {
+    /// Name of the source, used for logging
+    fn name(&self) -> &str;
+
+    /// Scrapes all restaurants provided by this source
+    ///
+    /// # Returns
+    /// - Ok(results): when the source is reachable, contains result for every restaurant, so one
+    ///   broken restaurant page does not discard the others
+    /// - Err(_): when the whole source failed (e.g. listing page is not available)
+    async fn fetch(&self) -> anyhow::Result<Vec<RestaurantScrapResult>>;
+}
@@ -0,0 +1,24 @@
+pub use {json_feed::*, menicka::*, menu_source::*};
+
+pub mod json_feed;
+pub mod menicka;
+pub mod menicka_parser;
+pub mod menu_source;
+
+use crate::scrapping::http_client::HttpClient;
+use std::env;
+
+/// Creates all configured menu sources - menicka.cz is always used, additional JSON feeds can be
+/// registered with comma separated urls in the MENU_FEED_URLS environment variable. All sources
+/// share the given client.
+pub fn configured_sources(client: &HttpClient) -> Vec<Box<dyn MenuSource>> {
+    let mut sources: Vec<Box<dyn MenuSource>> = vec![Box::new(MenickaSource::new(client.clone()))];
+
+    if let Ok(urls) = env::var("MENU_FEED_URLS") {
+        for url in urls.split(',').map(str::trim).filter(|url| !url.is_empty()) {
+            sources.push(Box::new(JsonFeedSource::new(url, client.clone())));
+        }
+    }
+
+    sources
+}
@@ -53,9 +53,11 @@ VALUES ('79c4d79a-303e-4a95-b1af-e8203a3d3b14', 'Hovězí vývar se zeleninou, m
         'd704d684-f68b-487a-8062-4d1bb2b5797d');
 
 
-INSERT INTO "GroupUsers" (id, user_id, group_id, deleted_at)
+INSERT INTO "GroupUsers" (id, user_id, group_id, deleted_at, role)
 VALUES ('b12a7839-4b1f-427d-9acd-d0d6eb8c39f0', 'c831db0d-23bf-4a88-8974-332fdea327cd',
-        '4a51b8d6-c7dc-428b-bee6-97706063a0ae', null);
+        '4a51b8d6-c7dc-428b-bee6-97706063a0ae', null, 'member'),
+       ('0f6c3d47-5a8e-4c1b-9e2d-7b3a9f1e6c24', 'bfadb3a0-287c-4b5b-9132-cd977217a694',
+        '4a51b8d6-c7dc-428b-bee6-97706063a0ae', null, 'owner');
 
 INSERT INTO "Lunch" (id, date, group_id, deleted_at)
 VALUES ('645ae55a-190e-4b5d-b47b-0c00c9f4ce0d', '2024-01-15', '4a51b8d6-c7dc-428b-bee6-97706063a0ae', null);